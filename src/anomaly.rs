@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Months, NaiveDate};
+
+use crate::model::Expense;
+use crate::subscriptions;
+
+/// Fewest expenses at a payee or in a category to judge an amount against.
+const MIN_HISTORY: usize = 6;
+
+/// How far above the median an unusual amount is, in robust standard
+/// deviations (1.4826 × the median absolute deviation).
+const OUTLIER_SCORE: f64 = 3.5;
+
+/// An unusual amount is also at least this many times the median, so a
+/// payee that always charges about the same isn't flagged for small moves.
+const OUTLIER_RATIO: f64 = 2.0;
+
+/// Identical charges at most this many days apart may be duplicates.
+const DUPLICATE_DAYS: i64 = 3;
+
+/// Months before a month averaged for a category's usual spending.
+pub const TRAILING_MONTHS: u32 = 3;
+
+/// A category month is a spike at this many times the trailing average...
+const SPIKE_RATIO: f64 = 1.5;
+
+/// ...and when the extra is at least this share of an average month's
+/// total spending, so small categories don't spike over a few dollars.
+const SPIKE_SHARE: f64 = 0.05;
+
+/// Months checked for spikes, the current one included.
+const SPIKE_MONTHS: u32 = 12;
+
+/// What an amount was compared with.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Basis {
+    /// Expenses with the same description.
+    Payee(String),
+    Category(String),
+}
+
+/// Why an expense looks unusual.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reason {
+    /// Far above the typical (median) amount for its payee or, without
+    /// enough history there, its category.
+    Outlier { basis: Basis, typical: f64 },
+    /// The same amount, description and category as the charge `of` on
+    /// `date`, shortly before.
+    Duplicate { of: u64, date: NaiveDate },
+}
+
+#[derive(Debug, Clone)]
+pub struct Flag {
+    pub id: u64,
+    pub reason: Reason,
+}
+
+/// A month a category spent well above its trailing average.
+#[derive(Debug, Clone)]
+pub struct Spike {
+    pub category: String,
+    /// The first day of the month.
+    pub month: NaiveDate,
+    pub spent: f64,
+    /// Average monthly spending over the `TRAILING_MONTHS` before.
+    pub average: f64,
+}
+
+impl Spike {
+    pub fn ratio(&self) -> f64 {
+        self.spent / self.average
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// Newest expense first; an expense can have more than one flag.
+    pub flags: Vec<Flag>,
+    /// Newest month first, then the biggest jump.
+    pub spikes: Vec<Spike>,
+}
+
+impl Report {
+    pub fn reasons(&self, id: u64) -> impl Iterator<Item = &Reason> {
+        self.flags
+            .iter()
+            .filter(move |f| f.id == id)
+            .map(|f| &f.reason)
+    }
+
+    /// Flagged expenses, each once, newest first.
+    pub fn flagged_ids(&self) -> Vec<u64> {
+        let mut ids: Vec<u64> = Vec::new();
+        for flag in &self.flags {
+            if !ids.contains(&flag.id) {
+                ids.push(flag.id);
+            }
+        }
+        ids
+    }
+}
+
+/// Look for unusual amounts, likely duplicates and category spikes up to
+/// `today`.
+pub fn detect(expenses: &[Expense], today: NaiveDate) -> Report {
+    let past: Vec<&Expense> = expenses.iter().filter(|e| e.date <= today).collect();
+    let mut flags = outliers(&past);
+    flags.extend(duplicates(&past));
+
+    let dates: HashMap<u64, NaiveDate> = past.iter().map(|e| (e.id, e.date)).collect();
+    flags.sort_by_key(|f| std::cmp::Reverse((dates.get(&f.id).copied(), f.id)));
+
+    Report {
+        flags,
+        spikes: spikes(&past, today),
+    }
+}
+
+fn payee(expense: &Expense) -> String {
+    expense.description.trim().to_lowercase()
+}
+
+fn median(sorted: &[f64]) -> f64 {
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+/// The median and robust standard deviation of `amounts`.
+fn spread(mut amounts: Vec<f64>) -> (f64, f64) {
+    amounts.sort_by(f64::total_cmp);
+    let typical = median(&amounts);
+    let mut deviations: Vec<f64> = amounts.iter().map(|a| (a - typical).abs()).collect();
+    deviations.sort_by(f64::total_cmp);
+    (typical, 1.4826 * median(&deviations))
+}
+
+fn outliers(expenses: &[&Expense]) -> Vec<Flag> {
+    let mut by_payee: HashMap<String, Vec<f64>> = HashMap::new();
+    let mut by_category: HashMap<String, Vec<f64>> = HashMap::new();
+    for e in expenses.iter().filter(|e| e.amount > 0.0) {
+        if !payee(e).is_empty() {
+            by_payee.entry(payee(e)).or_default().push(e.amount);
+        }
+        by_category
+            .entry(e.category.to_string())
+            .or_default()
+            .push(e.amount);
+    }
+    let by_payee: HashMap<String, (f64, f64)> = by_payee
+        .into_iter()
+        .filter(|(_, amounts)| amounts.len() >= MIN_HISTORY)
+        .map(|(name, amounts)| (name, spread(amounts)))
+        .collect();
+    let by_category: HashMap<String, (f64, f64)> = by_category
+        .into_iter()
+        .filter(|(_, amounts)| amounts.len() >= MIN_HISTORY)
+        .map(|(name, amounts)| (name, spread(amounts)))
+        .collect();
+
+    expenses
+        .iter()
+        .filter(|e| e.amount > 0.0)
+        .filter_map(|e| {
+            let (basis, (typical, deviation)) = match by_payee.get(&payee(e)) {
+                Some(stats) => (Basis::Payee(e.description.trim().to_string()), *stats),
+                None => {
+                    let category = e.category.to_string();
+                    let stats = *by_category.get(&category)?;
+                    (Basis::Category(category), stats)
+                }
+            };
+            let unusual = e.amount >= typical * OUTLIER_RATIO
+                && e.amount - typical > OUTLIER_SCORE * deviation;
+            unusual.then_some(Flag {
+                id: e.id,
+                reason: Reason::Outlier { basis, typical },
+            })
+        })
+        .collect()
+}
+
+/// Later charges that repeat an earlier one within `DUPLICATE_DAYS`.
+/// Recurring series are left out: repeating is what they do. So are habits
+/// like a daily coffee, where most repeats come that close.
+fn duplicates(expenses: &[&Expense]) -> Vec<Flag> {
+    let templates: Vec<&Expense> = expenses
+        .iter()
+        .copied()
+        .filter(|e| e.is_recurring)
+        .collect();
+    let mut groups: HashMap<(String, String, i64), Vec<&Expense>> = HashMap::new();
+    for e in expenses {
+        if templates.iter().any(|t| subscriptions::in_series(t, e)) {
+            continue;
+        }
+        let cents = (e.amount * 100.0).round() as i64;
+        groups
+            .entry((payee(e), e.category.to_string(), cents))
+            .or_default()
+            .push(e);
+    }
+
+    let mut flags = Vec::new();
+    for mut group in groups.into_values().filter(|g| g.len() > 1) {
+        group.sort_by_key(|e| (e.date, e.id));
+        let close = |pair: &[&Expense]| (pair[1].date - pair[0].date).num_days() <= DUPLICATE_DAYS;
+        let gaps = group.len() - 1;
+        if gaps > 1 && group.windows(2).filter(|pair| close(pair)).count() * 2 > gaps {
+            continue;
+        }
+        for pair in group.windows(2) {
+            if close(pair) {
+                flags.push(Flag {
+                    id: pair[1].id,
+                    reason: Reason::Duplicate {
+                        of: pair[0].id,
+                        date: pair[0].date,
+                    },
+                });
+            }
+        }
+    }
+    flags
+}
+
+fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// Category months in the last `SPIKE_MONTHS` well above the average of the
+/// `TRAILING_MONTHS` before. Months without that much history behind them
+/// aren't judged.
+fn spikes(expenses: &[&Expense], today: NaiveDate) -> Vec<Spike> {
+    let Some(first) = expenses.iter().map(|e| month_start(e.date)).min() else {
+        return Vec::new();
+    };
+    let mut totals: HashMap<(String, NaiveDate), f64> = HashMap::new();
+    let mut months: HashMap<NaiveDate, f64> = HashMap::new();
+    for e in expenses {
+        let month = month_start(e.date);
+        *totals.entry((e.category.to_string(), month)).or_default() += e.amount;
+        *months.entry(month).or_default() += e.amount;
+    }
+    let trailing = |month: NaiveDate, amount: &dyn Fn(NaiveDate) -> f64| {
+        (1..=TRAILING_MONTHS)
+            .filter_map(|k| month.checked_sub_months(Months::new(k)))
+            .fold(0.0, |total, m| total + amount(m))
+            / TRAILING_MONTHS as f64
+    };
+
+    let current = month_start(today);
+    let mut spikes = Vec::new();
+    for k in 0..SPIKE_MONTHS {
+        let Some(month) = current.checked_sub_months(Months::new(k)) else {
+            break;
+        };
+        let Some(earliest) = month.checked_sub_months(Months::new(TRAILING_MONTHS)) else {
+            break;
+        };
+        if earliest < first {
+            break;
+        }
+        let usual_month = trailing(month, &|m| months.get(&m).copied().unwrap_or(0.0));
+        for ((category, _), &spent) in totals.iter().filter(|((_, m), _)| *m == month) {
+            let average = trailing(month, &|m| {
+                totals.get(&(category.clone(), m)).copied().unwrap_or(0.0)
+            });
+            if average > 0.0
+                && spent >= average * SPIKE_RATIO
+                && spent - average >= usual_month * SPIKE_SHARE
+            {
+                spikes.push(Spike {
+                    category: category.clone(),
+                    month,
+                    spent,
+                    average,
+                });
+            }
+        }
+    }
+    spikes.sort_by(|a, b| {
+        b.month
+            .cmp(&a.month)
+            .then_with(|| b.ratio().total_cmp(&a.ratio()))
+    });
+    spikes
+}
@@ -1,19 +1,49 @@
 use anyhow::{Context, Result};
-use chrono::{Datelike, Local, NaiveDate};
+use chrono::{Datelike, Days, Local, Months, NaiveDate};
+use std::collections::HashSet;
 
-use crate::model::{Budget, Category, Currency, Expense, Recurrence};
+use crate::anomaly::{self, Report};
+use crate::forecast::{self, Forecast};
+use crate::keymap::{Action, KeyChord, KeyContext, Keymap};
+use crate::model::{
+    parse_tags, Budget, Category, Column, Contribution, Currency, Envelope, Expense, Goal,
+    GoalProgress, Loan, LoanSummary, Period, Recurrence, SortOrder, Template,
+};
+use crate::prompts;
+use crate::query::Query;
+use crate::quick_add;
 use crate::storage;
+use crate::subscriptions::{self, Candidate, Subscription};
+use crate::text_input::{CharFilter, TextInput};
+use crate::theme::{Theme, BUILTIN_THEMES};
+use crate::utils;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Tab {
     Dashboard,
     Expenses,
     Monthly,
+    Yearly,
+    Heatmap,
+    Budgets,
+    Goals,
+    Loans,
+    Subscriptions,
 }
 
 impl Tab {
     pub fn titles() -> Vec<&'static str> {
-        vec!["Dashboard [1]", "Expenses [2]", "Monthly [3]"]
+        vec![
+            "Dashboard [1]",
+            "Expenses [2]",
+            "Monthly [3]",
+            "Yearly [4]",
+            "Heatmap [5]",
+            "Budgets [6]",
+            "Goals [7]",
+            "Loans [8]",
+            "Subscriptions [9]",
+        ]
     }
 
     pub fn index(&self) -> usize {
@@ -21,6 +51,12 @@ impl Tab {
             Tab::Dashboard => 0,
             Tab::Expenses => 1,
             Tab::Monthly => 2,
+            Tab::Yearly => 3,
+            Tab::Heatmap => 4,
+            Tab::Budgets => 5,
+            Tab::Goals => 6,
+            Tab::Loans => 7,
+            Tab::Subscriptions => 8,
         }
     }
 
@@ -29,6 +65,12 @@ impl Tab {
             0 => Tab::Dashboard,
             1 => Tab::Expenses,
             2 => Tab::Monthly,
+            3 => Tab::Yearly,
+            4 => Tab::Heatmap,
+            5 => Tab::Budgets,
+            6 => Tab::Goals,
+            7 => Tab::Loans,
+            8 => Tab::Subscriptions,
             _ => Tab::Dashboard,
         }
     }
@@ -42,6 +84,27 @@ pub enum InputMode {
     EditForm,
     HelpPopup,
     ConfirmDelete,
+    ColumnEditor,
+    BulkMenu,
+    BulkInput,
+    ConfirmBulk,
+    TemplatePicker,
+    QuickAdd,
+    CommandPalette,
+    DayDetail,
+    BudgetAlert,
+    GoalPrompt,
+    LoanPrompt,
+    CancelSubscription,
+    SubscriptionPrice,
+    AnomalyReview,
+}
+
+/// What the Goals tab prompt is entering.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GoalPrompt {
+    NewGoal,
+    Contribute,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -49,6 +112,7 @@ pub enum FormField {
     Amount,
     Category,
     Description,
+    Tags,
     Date,
     Recurring,
     RecurrenceType,
@@ -59,7 +123,8 @@ impl FormField {
         match self {
             FormField::Amount => FormField::Category,
             FormField::Category => FormField::Description,
-            FormField::Description => FormField::Date,
+            FormField::Description => FormField::Tags,
+            FormField::Tags => FormField::Date,
             FormField::Date => FormField::Recurring,
             FormField::Recurring => FormField::RecurrenceType,
             FormField::RecurrenceType => FormField::Amount,
@@ -71,7 +136,8 @@ impl FormField {
             FormField::Amount => FormField::RecurrenceType,
             FormField::Category => FormField::Amount,
             FormField::Description => FormField::Category,
-            FormField::Date => FormField::Description,
+            FormField::Tags => FormField::Description,
+            FormField::Date => FormField::Tags,
             FormField::Recurring => FormField::Date,
             FormField::RecurrenceType => FormField::Recurring,
         }
@@ -80,29 +146,34 @@ impl FormField {
 
 #[derive(Debug, Clone)]
 pub struct FormState {
-    pub amount_input: String,
+    pub amount_input: TextInput,
     pub category_index: usize,
-    pub custom_category: String,
-    pub description_input: String,
-    pub date_input: String,
+    pub custom_category: TextInput,
+    pub description_input: TextInput,
+    pub tags_input: TextInput,
+    pub date_input: TextInput,
     pub is_recurring: bool,
     pub recurrence_index: usize,
     pub active_field: FormField,
     pub editing_id: Option<u64>,
+    /// Day highlighted in the date picker, while it is open.
+    pub calendar: Option<NaiveDate>,
 }
 
 impl Default for FormState {
     fn default() -> Self {
         Self {
-            amount_input: String::new(),
+            amount_input: TextInput::default(),
             category_index: 0,
-            custom_category: String::new(),
-            description_input: String::new(),
-            date_input: Local::now().format("%Y-%m-%d").to_string(),
+            custom_category: TextInput::default(),
+            description_input: TextInput::default(),
+            tags_input: TextInput::default(),
+            date_input: TextInput::new(Local::now().format("%Y-%m-%d").to_string()),
             is_recurring: false,
             recurrence_index: 0,
             active_field: FormField::Amount,
             editing_id: None,
+            calendar: None,
         }
     }
 }
@@ -110,14 +181,15 @@ impl Default for FormState {
 impl FormState {
     pub fn from_expense(expense: &Expense) -> Self {
         Self {
-            amount_input: format!("{:.2}", expense.amount),
+            amount_input: TextInput::new(format!("{:.2}", expense.amount)),
             category_index: expense.category.to_index(),
             custom_category: match &expense.category {
-                Category::Other(s) => s.clone(),
-                _ => String::new(),
+                Category::Other(s) => TextInput::new(s.as_str()),
+                _ => TextInput::default(),
             },
-            description_input: expense.description.clone(),
-            date_input: expense.date.format("%Y-%m-%d").to_string(),
+            description_input: TextInput::new(expense.description.as_str()),
+            tags_input: TextInput::new(expense.tags.join(", ")),
+            date_input: TextInput::new(expense.date.format("%Y-%m-%d").to_string()),
             is_recurring: expense.is_recurring,
             recurrence_index: expense
                 .recurrence
@@ -125,23 +197,129 @@ impl FormState {
                 .unwrap_or(0),
             active_field: FormField::Amount,
             editing_id: Some(expense.id),
+            calendar: None,
         }
     }
 
-    pub fn to_expense(&self, id: u64) -> Option<Expense> {
-        let amount: f64 = self.amount_input.parse().ok()?;
-        if amount <= 0.0 {
-            return None;
+    /// A new expense pre-filled from a template, dated today.
+    pub fn from_template(template: &Template) -> Self {
+        Self {
+            amount_input: TextInput::new(
+                template.amount.map(|a| format!("{:.2}", a)).unwrap_or_default(),
+            ),
+            category_index: template.category.to_index(),
+            custom_category: match &template.category {
+                Category::Other(s) => TextInput::new(s.as_str()),
+                _ => TextInput::default(),
+            },
+            description_input: TextInput::new(template.description.as_str()),
+            tags_input: TextInput::new(template.tags.join(", ")),
+            ..Self::default()
+        }
+    }
+
+    /// A copy of `expense` as a new one-off entry dated today. Copying a
+    /// recurring entry as recurring would start a second series.
+    pub fn duplicate(expense: &Expense) -> Self {
+        Self {
+            editing_id: None,
+            date_input: FormState::default().date_input,
+            is_recurring: false,
+            recurrence_index: 0,
+            ..Self::from_expense(expense)
+        }
+    }
+
+    /// The text input behind the active field and the characters it
+    /// accepts, if the field takes typed text.
+    pub fn active_input(&mut self) -> Option<(&mut TextInput, CharFilter)> {
+        match self.active_field {
+            FormField::Amount => Some((&mut self.amount_input, |c| {
+                c.is_ascii_digit() || " .+-*/x()".contains(c)
+            })),
+            FormField::Category if self.category_index == 9 => {
+                Some((&mut self.custom_category, |_| true))
+            }
+            FormField::Description => Some((&mut self.description_input, |_| true)),
+            FormField::Tags => Some((&mut self.tags_input, |_| true)),
+            FormField::Date => Some((&mut self.date_input, |_| true)),
+            _ => None,
+        }
+    }
+
+    /// The amount field evaluated, so `45.80/3` works, rounded to cents.
+    pub fn amount(&self) -> Result<f64, String> {
+        let value = utils::eval_amount(self.amount_input.as_str())?;
+        Ok((value * 100.0).round() / 100.0)
+    }
+
+    /// The date field resolved against today, so `yesterday` or `-3d` work.
+    pub fn date(&self) -> Result<NaiveDate, String> {
+        utils::parse_date(self.date_input.as_str(), utils::today())
+    }
+
+    pub fn open_calendar(&mut self) {
+        self.active_field = FormField::Date;
+        self.calendar = Some(self.date().unwrap_or_else(|_| utils::today()));
+    }
+
+    /// Move the date picker's highlight, unless that leaves the calendar.
+    pub fn move_calendar(&mut self, step: impl FnOnce(NaiveDate) -> Option<NaiveDate>) {
+        if let Some(date) = self.calendar.and_then(step) {
+            self.calendar = Some(date);
         }
-        let category = Category::from_index(
+    }
+
+    /// Close the date picker, writing the highlighted day into the field.
+    pub fn pick_calendar_date(&mut self) {
+        if let Some(date) = self.calendar.take() {
+            self.date_input = TextInput::new(date.format("%Y-%m-%d").to_string());
+        }
+    }
+
+    pub fn category(&self) -> Category {
+        Category::from_index(
             self.category_index,
             if self.category_index == 9 {
-                Some(self.custom_category.clone())
+                Some(self.custom_category.as_str().to_string())
             } else {
                 None
             },
-        );
-        let date = NaiveDate::parse_from_str(&self.date_input, "%Y-%m-%d").ok()?;
+        )
+    }
+
+    /// The form as a template, named after its description. A blank amount
+    /// makes a template that asks for the amount each time.
+    pub fn to_template(&self) -> Result<Template, String> {
+        let amount = match self.amount_input.as_str().trim() {
+            "" => None,
+            _ => match self.amount()? {
+                amount if amount > 0.0 => Some(amount),
+                _ => return Err("Amount must be positive, or left empty".to_string()),
+            },
+        };
+        let category = self.category();
+        let description = self.description_input.as_str().trim().to_string();
+        Ok(Template {
+            name: if description.is_empty() {
+                category.to_string()
+            } else {
+                description.clone()
+            },
+            amount,
+            category,
+            description,
+            tags: parse_tags(self.tags_input.as_str()),
+        })
+    }
+
+    pub fn to_expense(&self, id: u64) -> Option<Expense> {
+        let amount = self.amount().ok()?;
+        if amount <= 0.0 {
+            return None;
+        }
+        let category = self.category();
+        let date = self.date().ok()?;
         let recurrence = if self.is_recurring {
             Some(Recurrence::from_index(self.recurrence_index))
         } else {
@@ -152,35 +330,321 @@ impl FormState {
             id,
             amount,
             category,
-            self.description_input.clone(),
+            self.description_input.as_str().to_string(),
             date,
             self.is_recurring,
             recurrence,
-        ))
+        )
+        .with_tags(parse_tags(self.tags_input.as_str())))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BulkActionKind {
+    Delete,
+    SetCategory,
+    AddTag,
+    ShiftDate,
+    ToggleRecurring,
+    Export,
+}
+
+impl BulkActionKind {
+    pub fn all() -> &'static [BulkActionKind] {
+        &[
+            BulkActionKind::Delete,
+            BulkActionKind::SetCategory,
+            BulkActionKind::AddTag,
+            BulkActionKind::ShiftDate,
+            BulkActionKind::ToggleRecurring,
+            BulkActionKind::Export,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BulkActionKind::Delete => "Delete",
+            BulkActionKind::SetCategory => "Change category",
+            BulkActionKind::AddTag => "Add tag",
+            BulkActionKind::ShiftDate => "Shift date",
+            BulkActionKind::ToggleRecurring => "Toggle recurring",
+            BulkActionKind::Export => "Export selection",
+        }
+    }
+
+    pub fn needs_input(&self) -> bool {
+        matches!(
+            self,
+            BulkActionKind::SetCategory | BulkActionKind::AddTag | BulkActionKind::ShiftDate
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum BulkAction {
+    Delete,
+    SetCategory(Category),
+    AddTag(Vec<String>),
+    ShiftDate(i64),
+    ToggleRecurring,
+    Export,
+}
+
+impl BulkAction {
+    pub fn describe(&self) -> String {
+        match self {
+            BulkAction::Delete => "Delete".to_string(),
+            BulkAction::SetCategory(c) => format!("Set category to {}", c),
+            BulkAction::AddTag(tags) => format!("Add tag {}", tags.join(", ")),
+            BulkAction::ShiftDate(days) => format!("Shift date by {:+} days", days),
+            BulkAction::ToggleRecurring => "Toggle recurring".to_string(),
+            BulkAction::Export => "Export".to_string(),
+        }
+    }
+}
+
+/// A row in the command palette.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaletteEntry {
+    Action(Action),
+    /// Open the Monthly tab at the month starting on this date, typed as
+    /// the query.
+    GoToMonth(NaiveDate),
+}
+
+/// What the Monthly tab shows: the category breakdown, or a comparison
+/// with an earlier month.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MonthlyView {
+    #[default]
+    Breakdown,
+    VsPreviousMonth,
+    VsLastYear,
+}
+
+impl MonthlyView {
+    pub fn next(&self) -> Self {
+        match self {
+            MonthlyView::Breakdown => MonthlyView::VsPreviousMonth,
+            MonthlyView::VsPreviousMonth => MonthlyView::VsLastYear,
+            MonthlyView::VsLastYear => MonthlyView::Breakdown,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MonthlyView::Breakdown => "breakdown",
+            MonthlyView::VsPreviousMonth => "vs previous month",
+            MonthlyView::VsLastYear => "vs same month last year",
+        }
+    }
+}
+
+/// One category's spending in two months, for the Monthly comparison.
+#[derive(Debug, Clone)]
+pub struct CategoryChange {
+    pub category: String,
+    pub current: f64,
+    pub previous: f64,
+}
+
+impl CategoryChange {
+    pub fn delta(&self) -> f64 {
+        self.current - self.previous
+    }
+}
+
+/// The change from `previous` to `current` as a percentage of `previous`;
+/// `None` if there was nothing before.
+pub fn percent_change(current: f64, previous: f64) -> Option<f64> {
+    (previous > 0.0).then(|| (current - previous) / previous * 100.0)
+}
+
+/// Where a category's budget stands for an expense about to be saved.
+#[derive(Debug, Clone)]
+pub struct BudgetImpact {
+    pub category: Category,
+    /// The period the expense falls in, e.g. "this week".
+    pub period: String,
+    pub envelope: Envelope,
+    /// Spent in the period without and with the expense.
+    pub before: f64,
+    pub after: f64,
+    /// The highest alert threshold the expense takes spending past.
+    pub crossed: Option<f64>,
+}
+
+impl BudgetImpact {
+    pub fn left_before(&self) -> f64 {
+        self.envelope.available() - self.before
+    }
+
+    pub fn left_after(&self) -> f64 {
+        self.envelope.available() - self.after
+    }
+
+    pub fn percent_after(&self) -> f64 {
+        self.envelope.percent_used(self.after)
+    }
+}
+
+/// State of the bulk action menu and its follow-up input.
+#[derive(Debug, Clone, Default)]
+pub struct BulkState {
+    pub menu_index: usize,
+    pub kind: Option<BulkActionKind>,
+    pub input: TextInput,
+    pub category_index: usize,
+    pub pending: Option<BulkAction>,
+}
+
+impl BulkState {
+    pub fn build_action(&self) -> Result<BulkAction, String> {
+        match self.kind {
+            Some(BulkActionKind::Delete) => Ok(BulkAction::Delete),
+            Some(BulkActionKind::SetCategory) => Ok(BulkAction::SetCategory(Category::from_index(
+                self.category_index,
+                if self.category_index == 9 {
+                    Some(self.input.as_str().to_string())
+                } else {
+                    None
+                },
+            ))),
+            Some(BulkActionKind::AddTag) => {
+                let tags = parse_tags(self.input.as_str());
+                if tags.is_empty() {
+                    Err("Enter at least one tag".to_string())
+                } else {
+                    Ok(BulkAction::AddTag(tags))
+                }
+            }
+            Some(BulkActionKind::ShiftDate) => self
+                .input
+                .as_str()
+                .trim()
+                .trim_start_matches('+')
+                .parse::<i64>()
+                .ok()
+                .filter(|d| *d != 0)
+                .ok_or_else(|| "Enter a non-zero number of days, e.g. +7 or -3".to_string())
+                .and_then(|days| {
+                    chrono::Duration::try_days(days)
+                        .map(|_| BulkAction::ShiftDate(days))
+                        .ok_or_else(|| format!("A shift of {} days is out of range", days))
+                }),
+            Some(BulkActionKind::ToggleRecurring) => Ok(BulkAction::ToggleRecurring),
+            Some(BulkActionKind::Export) => Ok(BulkAction::Export),
+            None => Err("No action selected".to_string()),
+        }
     }
 }
 
+/// Snapshot of the expense list taken before a change, restored by undo.
+struct UndoEntry {
+    label: String,
+    expenses: Vec<Expense>,
+}
+
+const UNDO_LIMIT: usize = 50;
+
 pub struct App {
     pub running: bool,
     pub active_tab: Tab,
     pub input_mode: InputMode,
     pub expenses: Vec<Expense>,
     pub budgets: Vec<Budget>,
+    /// Why budgets.csv couldn't be loaded; budgets aren't saved while set.
+    pub budgets_error: Option<String>,
     pub currency: Currency,
 
     // Expenses tab state
     pub expense_table_index: usize,
-    pub search_query: String,
+    pub search_query: TextInput,
+    pub search_filter: Query,
+    pub search_error: Option<String>,
     pub filtered_indices: Vec<usize>,
     pub show_recurring_only: bool,
+    pub columns: Vec<Column>,
+    pub sort: SortOrder,
+    pub mouse_enabled: bool,
+    pub monthly_cap: Option<f64>,
+    pub column_editor_index: usize,
+    pub selected_ids: HashSet<u64>,
+    pub range_anchor: Option<u64>,
+    pub bulk: BulkState,
+    undo_stack: Vec<UndoEntry>,
 
     // Monthly tab state
     pub selected_month: u32,
     pub selected_year: i32,
+    pub monthly_view: MonthlyView,
+
+    // Yearly tab state
+    pub overview_year: i32,
+
+    // Heatmap tab state
+    pub heatmap_day: NaiveDate,
+
+    // Budgets tab state
+    pub budget_index: usize,
+
+    // Savings goals and the Goals tab prompt
+    pub goals: Vec<Goal>,
+    pub contributions: Vec<Contribution>,
+    /// Why goals.csv or contributions.csv couldn't be loaded; neither is
+    /// saved while set.
+    pub goals_error: Option<String>,
+    pub goal_index: usize,
+    pub goal_prompt: GoalPrompt,
+    pub goal_input: TextInput,
+
+    // Loans and the Loans tab prompt
+    pub loans: Vec<Loan>,
+    /// Why loans.csv couldn't be loaded; loans aren't saved while set.
+    pub loans_error: Option<String>,
+    pub loan_index: usize,
+    pub loan_input: TextInput,
+
+    // Subscriptions tab: one selection across the series and then the
+    // detected candidates, and the cancel prompt
+    pub subscription_index: usize,
+    pub subscription_input: TextInput,
+
+    // Selection in the unusual-spending review list
+    pub anomaly_index: usize,
 
     // Form state
     pub form: FormState,
 
+    // Quick-add templates and the one-line prompt
+    pub templates: Vec<Template>,
+    pub template_index: usize,
+    pub quick_add: TextInput,
+
+    // Appearance
+    pub theme: Theme,
+    /// The theme named in the config. Saved in place of `theme.name`, which
+    /// is the monochrome override while NO_COLOR is set.
+    pub theme_name: String,
+
+    // Command palette
+    pub palette_query: TextInput,
+    pub palette_index: usize,
+
+    // Keybindings
+    pub keymap: Keymap,
+    pub pending_keys: Vec<KeyChord>,
+    pub help_scroll: u16,
+
+    /// The alert shown after a save takes a budget past a threshold.
+    pub budget_alert: Option<BudgetImpact>,
+    /// Budgets past their lowest alert threshold, kept up to date with the
+    /// expenses so the status bar doesn't walk every envelope each frame.
+    pub budget_warnings: Vec<(String, f64)>,
+    /// Unusual amounts, likely duplicates and category spikes so far, kept
+    /// up to date with the expenses like the warnings.
+    pub anomalies: Report,
+
     // Status message
     pub status_message: Option<String>,
 }
@@ -188,40 +652,163 @@ pub struct App {
 impl App {
     pub fn new() -> Result<Self> {
         let expenses = storage::load_expenses().unwrap_or_default();
-        let budgets = storage::load_budgets().unwrap_or_default();
-        let currency = storage::load_currency().unwrap_or_default();
-        let now = Local::now();
+        // A budgets file that doesn't parse is left alone rather than
+        // overwritten with an empty list on the next save.
+        let (budgets, budgets_error) = match storage::load_budgets() {
+            Ok(budgets) => (budgets, None),
+            Err(e) => (Vec::new(), Some(format!("budgets.csv: {:#}", e))),
+        };
+        let templates = storage::load_templates().unwrap_or_default();
+        let (goals, contributions, goals_error) =
+            match (storage::load_goals(), storage::load_contributions()) {
+                (Ok(goals), Ok(contributions)) => (goals, contributions, None),
+                (Err(e), _) => (Vec::new(), Vec::new(), Some(format!("goals.csv: {:#}", e))),
+                (_, Err(e)) => (
+                    Vec::new(),
+                    Vec::new(),
+                    Some(format!("contributions.csv: {:#}", e)),
+                ),
+            };
+        let config = storage::load_config().unwrap_or_default();
+        let (keymap, keymap_error) = load_keymap();
+        let (theme, theme_error) = load_theme(&config.theme);
 
-        let mut app = Self {
+        let (loans, loans_error) = match storage::load_loans() {
+            Ok(loans) => (loans, None),
+            Err(e) => (Vec::new(), Some(format!("loans.csv: {:#}", e))),
+        };
+        let load_error = budgets_error
+            .clone()
+            .or(goals_error.clone())
+            .or(loans_error.clone());
+        let mut app = Self::empty(&config, keymap, theme);
+        app.expenses = expenses;
+        app.budgets = budgets;
+        app.budgets_error = budgets_error;
+        app.goals = goals;
+        app.contributions = contributions;
+        app.goals_error = goals_error;
+        app.loans = loans;
+        app.loans_error = loans_error;
+        app.templates = templates;
+        app.status_message = load_error.or(keymap_error).or(theme_error);
+
+        app.generate_recurring_expenses();
+        app.update_filtered_indices();
+        Ok(app)
+    }
+
+    /// An app with no data loaded, showing this month.
+    fn empty(config: &storage::Config, keymap: Keymap, theme: Theme) -> Self {
+        let now = Local::now();
+        Self {
             running: true,
             active_tab: Tab::Dashboard,
             input_mode: InputMode::Normal,
-            expenses,
-            budgets,
-            currency,
+            expenses: Vec::new(),
+            budgets: Vec::new(),
+            budgets_error: None,
+            currency: config.currency,
             expense_table_index: 0,
-            search_query: String::new(),
+            search_query: TextInput::default(),
+            search_filter: Query::default(),
+            search_error: None,
             filtered_indices: Vec::new(),
             show_recurring_only: false,
+            columns: config.columns.clone(),
+            sort: config.sort,
+            mouse_enabled: config.mouse,
+            monthly_cap: config.monthly_cap,
+            column_editor_index: 0,
+            selected_ids: HashSet::new(),
+            range_anchor: None,
+            bulk: BulkState::default(),
+            undo_stack: Vec::new(),
             selected_month: now.month(),
             selected_year: now.year(),
+            monthly_view: MonthlyView::default(),
+            overview_year: now.year(),
+            heatmap_day: utils::today(),
+            budget_index: 0,
+            goals: Vec::new(),
+            contributions: Vec::new(),
+            goals_error: None,
+            goal_index: 0,
+            goal_prompt: GoalPrompt::NewGoal,
+            goal_input: TextInput::default(),
+            loans: Vec::new(),
+            loans_error: None,
+            loan_index: 0,
+            loan_input: TextInput::default(),
+            subscription_index: 0,
+            subscription_input: TextInput::default(),
+            anomaly_index: 0,
             form: FormState::default(),
+            templates: Vec::new(),
+            template_index: 0,
+            quick_add: TextInput::default(),
+            theme,
+            theme_name: config.theme.clone(),
+            palette_query: TextInput::default(),
+            palette_index: 0,
+            keymap,
+            pending_keys: Vec::new(),
+            help_scroll: 0,
+            budget_alert: None,
+            budget_warnings: Vec::new(),
+            anomalies: Report::default(),
             status_message: None,
-        };
+        }
+    }
 
-        app.generate_recurring_expenses();
-        app.update_filtered_indices();
-        Ok(app)
+    /// Keymap contexts that are live in Normal mode on the current tab.
+    pub fn normal_contexts(&self) -> Vec<KeyContext> {
+        match self.active_tab {
+            Tab::Dashboard => vec![KeyContext::Global],
+            Tab::Expenses => vec![KeyContext::Global, KeyContext::Expenses],
+            Tab::Monthly => vec![KeyContext::Global, KeyContext::Monthly],
+            Tab::Yearly => vec![KeyContext::Global, KeyContext::Yearly],
+            Tab::Heatmap => vec![KeyContext::Global, KeyContext::Heatmap],
+            Tab::Budgets => vec![KeyContext::Global, KeyContext::Budgets],
+            Tab::Goals => vec![KeyContext::Global, KeyContext::Goals],
+            Tab::Loans => vec![KeyContext::Global, KeyContext::Loans],
+            Tab::Subscriptions => vec![KeyContext::Global, KeyContext::Subscriptions],
+        }
     }
 
     pub fn save(&self) -> Result<()> {
         storage::save_expenses(&self.expenses)?;
-        storage::save_budgets(&self.budgets)?;
+        if self.budgets_error.is_none() {
+            storage::save_budgets(&self.budgets)?;
+        }
         Ok(())
     }
 
+    pub fn save_config(&self) -> Result<()> {
+        storage::save_config(&storage::Config {
+            currency: self.currency,
+            columns: self.columns.clone(),
+            sort: self.sort,
+            mouse: self.mouse_enabled,
+            theme: self.theme_name.clone(),
+            monthly_cap: self.monthly_cap,
+        })
+    }
+
+    /// Re-parse the search bar. An invalid query keeps the last valid filter
+    /// so the table doesn't flicker while a term is half-typed.
+    pub fn update_search_query(&mut self) {
+        match Query::parse(self.search_query.as_str()) {
+            Ok(query) => {
+                self.search_filter = query;
+                self.search_error = None;
+            }
+            Err(e) => self.search_error = Some(e),
+        }
+        self.update_filtered_indices();
+    }
+
     pub fn update_filtered_indices(&mut self) {
-        let query = self.search_query.to_lowercase();
         self.filtered_indices = self
             .expenses
             .iter()
@@ -230,23 +817,31 @@ impl App {
                 if self.show_recurring_only && !e.is_recurring {
                     return false;
                 }
-                if query.is_empty() {
-                    return true;
-                }
-                e.description.to_lowercase().contains(&query)
-                    || e.category.to_string().to_lowercase().contains(&query)
+                self.search_filter.matches(e)
             })
             .map(|(i, _)| i)
             .collect();
 
-        // Sort by date descending
-        self.filtered_indices.sort_by(|a, b| {
-            self.expenses[*b].date.cmp(&self.expenses[*a].date)
-        });
+        let sort = self.sort;
+        self.filtered_indices
+            .sort_by(|a, b| sort.compare(&self.expenses[*a], &self.expenses[*b]));
 
         if self.expense_table_index >= self.filtered_indices.len() && !self.filtered_indices.is_empty() {
             self.expense_table_index = self.filtered_indices.len() - 1;
         }
+        self.budget_warnings = self.current_budget_warnings();
+        self.anomalies = anomaly::detect(&self.expenses, utils::today());
+    }
+
+    pub fn is_filtered(&self) -> bool {
+        !self.search_filter.is_empty() || self.show_recurring_only
+    }
+
+    pub fn filtered_total(&self) -> f64 {
+        self.filtered_indices
+            .iter()
+            .map(|&i| self.expenses[i].amount)
+            .sum()
     }
 
     pub fn selected_expense(&self) -> Option<&Expense> {
@@ -255,14 +850,40 @@ impl App {
             .map(|&i| &self.expenses[i])
     }
 
+    /// Select the expense with `id` in the table, clearing the search and
+    /// recurring filter if they hide it.
+    pub fn jump_to_expense(&mut self, id: u64) {
+        let position = |app: &App| {
+            app.filtered_indices
+                .iter()
+                .position(|&i| app.expenses[i].id == id)
+        };
+        if position(self).is_none() {
+            self.search_query.clear();
+            self.show_recurring_only = false;
+            self.update_search_query();
+        }
+        if let Some(index) = position(self) {
+            self.expense_table_index = index;
+            self.active_tab = Tab::Expenses;
+        }
+    }
+
     pub fn add_expense(&mut self, expense: Expense) {
+        self.push_undo("add expense");
         self.expenses.push(expense);
         self.update_filtered_indices();
         let _ = self.save();
     }
 
-    pub fn update_expense(&mut self, id: u64, updated: Expense) {
+    pub fn update_expense(&mut self, id: u64, mut updated: Expense) {
         if let Some(pos) = self.expenses.iter().position(|e| e.id == id) {
+            self.push_undo("edit expense");
+            // The form doesn't show a cancellation, so keep it while the
+            // expense stays recurring.
+            if updated.is_recurring {
+                updated.cancelled = self.expenses[pos].cancelled;
+            }
             self.expenses[pos] = updated;
             self.update_filtered_indices();
             let _ = self.save();
@@ -271,97 +892,353 @@ impl App {
 
     pub fn delete_selected_expense(&mut self) {
         if let Some(&real_index) = self.filtered_indices.get(self.expense_table_index) {
-            self.expenses.remove(real_index);
+            self.push_undo("delete expense");
+            let removed = self.expenses.remove(real_index);
+            self.selected_ids.remove(&removed.id);
             self.update_filtered_indices();
             let _ = self.save();
         }
     }
 
-    pub fn next_id(&self) -> u64 {
-        storage::next_id(&self.expenses)
+    /// The expense the `:` prompt would add, or why it can't.
+    pub fn quick_add_preview(&self) -> Result<Expense, String> {
+        quick_add::parse(self.quick_add.as_str(), self.next_id(), utils::today())
     }
 
-    pub fn export(&mut self) -> Result<String> {
-        let path = storage::export_expenses(&self.expenses)?;
-        self.status_message = Some(format!("Exported to {}", path));
-        Ok(path)
+    /// Rows offered by the command palette: the month the query names, if
+    /// any, then actions, best match first. Form and popup actions only
+    /// make sense while those are open, so they're left out.
+    pub fn palette_entries(&self) -> Vec<PaletteEntry> {
+        let query = self.palette_query.as_str();
+        let mut scored: Vec<(i64, usize, Action)> = Action::all()
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| !a.context().is_modal() && **a != Action::CommandPalette)
+            .filter_map(|(i, a)| {
+                let text = format!("{} {}", a.description(), a.name());
+                utils::fuzzy_score(query, &text).map(|score| (score, i, *a))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        utils::parse_month(query, utils::today())
+            .map(PaletteEntry::GoToMonth)
+            .into_iter()
+            .chain(scored.into_iter().map(|(_, _, a)| PaletteEntry::Action(a)))
+            .collect()
     }
 
-    pub fn import_from_csv(&mut self, path: &str) -> Result<usize> {
-        let count = storage::import_csv(path, &mut self.expenses)
-            .with_context(|| format!("Failed to import from {}", path))?;
-        self.update_filtered_indices();
-        self.save()?;
-        self.status_message = Some(format!("Imported {} expenses from {}", count, path));
-        Ok(count)
+    /// Save the selected expense as a template, replacing one with the same
+    /// name.
+    pub fn save_selected_as_template(&mut self) -> Result<String> {
+        let Some(template) = self.selected_expense().map(Template::from_expense) else {
+            return Ok("No expense selected".to_string());
+        };
+        self.save_template(template)
     }
 
-    pub fn generate_recurring_expenses(&mut self) {
-        let today = Local::now().date_naive();
-        let mut new_expenses: Vec<Expense> = Vec::new();
+    /// Add `template`, replacing one with the same name.
+    pub fn save_template(&mut self, template: Template) -> Result<String> {
+        let message = format!("Saved template '{}'", template.name);
+        match self.templates.iter().position(|t| t.name == template.name) {
+            Some(pos) => self.templates[pos] = template,
+            None => self.templates.push(template),
+        }
+        storage::save_templates(&self.templates)?;
+        Ok(message)
+    }
 
-        let recurring: Vec<Expense> = self
-            .expenses
-            .iter()
-            .filter(|e| e.is_recurring && e.recurrence.is_some())
-            .cloned()
-            .collect();
+    pub fn delete_template(&mut self, index: usize) -> Result<String> {
+        if index >= self.templates.len() {
+            return Ok("No template selected".to_string());
+        }
+        let removed = self.templates.remove(index);
+        self.template_index = self.template_index.min(self.templates.len().saturating_sub(1));
+        storage::save_templates(&self.templates)?;
+        Ok(format!("Deleted template '{}'", removed.name))
+    }
 
-        for template in &recurring {
-            let recurrence = template.recurrence.unwrap();
-            let last_date = self
-                .expenses
-                .iter()
-                .filter(|e| {
-                    e.description == template.description
-                        && e.category == template.category
-                        && e.amount == template.amount
-                })
-                .map(|e| e.date)
-                .max()
-                .unwrap_or(template.date);
+    fn push_undo(&mut self, label: &str) {
+        if self.undo_stack.len() == UNDO_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(UndoEntry {
+            label: label.to_string(),
+            expenses: self.expenses.clone(),
+        });
+    }
 
-            let mut next = recurrence.next_date(last_date);
-            let mut next_id = self.next_id() + new_expenses.len() as u64;
-            while next <= today {
-                new_expenses.push(Expense::new(
-                    next_id,
-                    template.amount,
-                    template.category.clone(),
-                    template.description.clone(),
-                    next,
-                    false,
-                    None,
-                ));
-                next_id += 1;
-                next = recurrence.next_date(next);
+    pub fn undo(&mut self) {
+        match self.undo_stack.pop() {
+            Some(entry) => {
+                self.expenses = entry.expenses;
+                let ids: HashSet<u64> = self.expenses.iter().map(|e| e.id).collect();
+                self.selected_ids.retain(|id| ids.contains(id));
+                self.update_filtered_indices();
+                let _ = self.save();
+                self.status_message = Some(format!("Undid {}", entry.label));
             }
+            None => self.status_message = Some("Nothing to undo".to_string()),
         }
+    }
 
-        if !new_expenses.is_empty() {
-            self.expenses.extend(new_expenses);
-            let _ = self.save();
+    pub fn toggle_mark_selected(&mut self) {
+        if let Some(id) = self.selected_expense().map(|e| e.id) {
+            if !self.selected_ids.remove(&id) {
+                self.selected_ids.insert(id);
+            }
         }
     }
 
-    pub fn expenses_for_month(&self, year: i32, month: u32) -> Vec<&Expense> {
-        self.expenses
+    /// First press anchors a range at the cursor, the second marks every row
+    /// between the anchor and the cursor.
+    pub fn toggle_range_select(&mut self) {
+        match self.range_anchor.take() {
+            Some(_) => {
+                let ids = self.pending_range_ids();
+                let count = ids.len();
+                self.selected_ids.extend(ids);
+                self.status_message = Some(format!("Marked {} expenses", count));
+            }
+            None => {
+                self.range_anchor = self.selected_expense().map(|e| e.id);
+                if self.range_anchor.is_some() {
+                    self.status_message =
+                        Some("Range started: move and press V again to mark".to_string());
+                }
+            }
+        }
+    }
+
+    /// Ids between the range anchor and the cursor, in table order.
+    pub fn pending_range_ids(&self) -> Vec<u64> {
+        let Some(anchor) = self.range_anchor else {
+            return Vec::new();
+        };
+        let Some(start) = self
+            .filtered_indices
             .iter()
-            .filter(|e| e.date.year() == year && e.date.month() == month)
+            .position(|&i| self.expenses[i].id == anchor)
+        else {
+            return Vec::new();
+        };
+        let (from, to) = if start <= self.expense_table_index {
+            (start, self.expense_table_index)
+        } else {
+            (self.expense_table_index, start)
+        };
+        self.filtered_indices
+            .get(from..=to)
+            .unwrap_or_default()
+            .iter()
+            .map(|&i| self.expenses[i].id)
             .collect()
     }
 
-    pub fn total_for_month(&self, year: i32, month: u32) -> f64 {
-        self.expenses_for_month(year, month)
+    /// Mark every filtered row, or clear them if they are all marked already.
+    pub fn toggle_select_all_filtered(&mut self) {
+        let ids: Vec<u64> = self
+            .filtered_indices
             .iter()
-            .map(|e| e.amount)
-            .sum()
+            .map(|&i| self.expenses[i].id)
+            .collect();
+        if ids.iter().all(|id| self.selected_ids.contains(id)) {
+            for id in &ids {
+                self.selected_ids.remove(id);
+            }
+        } else {
+            self.selected_ids.extend(ids);
+        }
     }
 
-    pub fn total_for_year(&self, year: i32) -> f64 {
+    pub fn clear_selection(&mut self) {
+        self.selected_ids.clear();
+        self.range_anchor = None;
+    }
+
+    pub fn marked_expenses(&self) -> Vec<&Expense> {
         self.expenses
             .iter()
-            .filter(|e| e.date.year() == year)
+            .filter(|e| self.selected_ids.contains(&e.id))
+            .collect()
+    }
+
+    /// Apply `action` to every marked expense as a single undoable step.
+    pub fn apply_bulk(&mut self, action: &BulkAction) -> Result<String> {
+        let count = self.selected_ids.len();
+        if count == 0 {
+            return Ok("No expenses selected".to_string());
+        }
+
+        if let BulkAction::Export = action {
+            let selected: Vec<Expense> = self.marked_expenses().into_iter().cloned().collect();
+            let path = storage::export_expenses(&selected)?;
+            return Ok(format!("Exported {} expenses to {}", count, path));
+        }
+
+        let ids = self.selected_ids.clone();
+        let marked = |e: &Expense| ids.contains(&e.id);
+
+        // Work out every shifted date first, so an overflow leaves all rows
+        // as they were.
+        let mut shifted: Vec<NaiveDate> = Vec::new();
+        if let BulkAction::ShiftDate(days) = action {
+            let step = chrono::Days::new(days.unsigned_abs());
+            for e in self.expenses.iter().filter(|e| marked(e)) {
+                let date = if *days < 0 {
+                    e.date.checked_sub_days(step)
+                } else {
+                    e.date.checked_add_days(step)
+                };
+                shifted.push(date.with_context(|| {
+                    format!("shifting {} by {} days is out of range", e.date, days)
+                })?);
+            }
+        }
+
+        self.push_undo(&format!("bulk {}", action.describe().to_lowercase()));
+
+        match action {
+            BulkAction::Delete => {
+                self.expenses.retain(|e| !marked(e));
+                self.selected_ids.clear();
+            }
+            BulkAction::SetCategory(category) => {
+                for e in self.expenses.iter_mut().filter(|e| marked(e)) {
+                    e.category = category.clone();
+                }
+            }
+            BulkAction::AddTag(tags) => {
+                for e in self.expenses.iter_mut().filter(|e| marked(e)) {
+                    for tag in tags {
+                        if !e.has_tag(tag) {
+                            e.tags.push(tag.clone());
+                        }
+                    }
+                }
+            }
+            BulkAction::ShiftDate(_) => {
+                for (e, date) in self.expenses.iter_mut().filter(|e| marked(e)).zip(shifted) {
+                    e.date = date;
+                }
+            }
+            BulkAction::ToggleRecurring => {
+                let all_recurring = self
+                    .expenses
+                    .iter()
+                    .filter(|e| marked(e))
+                    .all(|e| e.is_recurring);
+                for e in self.expenses.iter_mut().filter(|e| marked(e)) {
+                    e.is_recurring = !all_recurring;
+                    e.recurrence = if all_recurring {
+                        None
+                    } else {
+                        e.recurrence.or(Some(Recurrence::Monthly))
+                    };
+                }
+            }
+            BulkAction::Export => unreachable!(),
+        }
+
+        self.update_filtered_indices();
+        self.save()?;
+        Ok(format!("{}: {} expenses (u to undo)", action.describe(), count))
+    }
+
+    pub fn next_id(&self) -> u64 {
+        storage::next_id(&self.expenses)
+    }
+
+    pub fn export(&mut self) -> Result<String> {
+        let path = storage::export_expenses(&self.expenses)?;
+        self.status_message = Some(format!("Exported to {}", path));
+        Ok(path)
+    }
+
+    pub fn import_from_csv(&mut self, path: &str) -> Result<usize> {
+        let count = storage::import_csv(path, &mut self.expenses)
+            .with_context(|| format!("Failed to import from {}", path))?;
+        self.update_filtered_indices();
+        self.save()?;
+        self.status_message = Some(format!("Imported {} expenses from {}", count, path));
+        Ok(count)
+    }
+
+    pub fn generate_recurring_expenses(&mut self) {
+        let new_expenses = self.pending_recurring(Local::now().date_naive());
+        if !new_expenses.is_empty() {
+            self.expenses.extend(new_expenses);
+            let _ = self.save();
+        }
+    }
+
+    /// Occurrences of recurring expenses due up to `until` that haven't been
+    /// added yet, numbered from the next free id.
+    pub fn pending_recurring(&self, until: NaiveDate) -> Vec<Expense> {
+        let mut new_expenses: Vec<Expense> = Vec::new();
+
+        let recurring: Vec<Expense> = self
+            .expenses
+            .iter()
+            .filter(|e| e.is_recurring && e.recurrence.is_some())
+            .cloned()
+            .collect();
+
+        for template in &recurring {
+            let recurrence = template.recurrence.unwrap();
+            let last_date = self
+                .expenses
+                .iter()
+                .filter(|e| subscriptions::in_series(template, e))
+                .map(|e| e.date)
+                .max()
+                .unwrap_or(template.date);
+
+            let mut next = recurrence.next_date(last_date);
+            let mut next_id = self.next_id() + new_expenses.len() as u64;
+            while next <= until && template.cancelled.is_none_or(|from| next < from) {
+                new_expenses.push(Expense::new(
+                    next_id,
+                    template.amount,
+                    template.category.clone(),
+                    template.description.clone(),
+                    next,
+                    false,
+                    None,
+                )
+                .with_tags(template.tags.clone()));
+                next_id += 1;
+                next = recurrence.next_date(next);
+            }
+        }
+
+        new_expenses
+    }
+
+    /// Month-end forecast for the current month.
+    pub fn forecast(&self) -> Forecast {
+        let today = utils::today();
+        let upcoming = self.pending_recurring(forecast::month_end(today));
+        forecast::project(&self.expenses, &upcoming, today)
+    }
+
+    pub fn expenses_for_month(&self, year: i32, month: u32) -> Vec<&Expense> {
+        self.expenses
+            .iter()
+            .filter(|e| e.date.year() == year && e.date.month() == month)
+            .collect()
+    }
+
+    pub fn total_for_month(&self, year: i32, month: u32) -> f64 {
+        self.expenses_for_month(year, month)
+            .iter()
+            .map(|e| e.amount)
+            .sum()
+    }
+
+    pub fn total_for_year(&self, year: i32) -> f64 {
+        self.expenses
+            .iter()
+            .filter(|e| e.date.year() == year)
             .map(|e| e.amount)
             .sum()
     }
@@ -377,33 +1254,789 @@ impl App {
         result
     }
 
+    /// The month the Monthly tab compares the selected month with, if any.
+    pub fn comparison_month(&self) -> Option<(i32, u32)> {
+        match self.monthly_view {
+            MonthlyView::Breakdown => None,
+            MonthlyView::VsPreviousMonth if self.selected_month == 1 => {
+                Some((self.selected_year - 1, 12))
+            }
+            MonthlyView::VsPreviousMonth => Some((self.selected_year, self.selected_month - 1)),
+            MonthlyView::VsLastYear => Some((self.selected_year - 1, self.selected_month)),
+        }
+    }
+
+    /// Per-category spending in the selected month against `(year, month)`,
+    /// biggest change first.
+    pub fn category_changes(&self, year: i32, month: u32) -> Vec<CategoryChange> {
+        let previous = self.spending_by_category(year, month);
+        let mut changes: Vec<CategoryChange> = self
+            .spending_by_category(self.selected_year, self.selected_month)
+            .into_iter()
+            .map(|(category, current)| {
+                let previous = previous
+                    .iter()
+                    .find(|(c, _)| *c == category)
+                    .map_or(0.0, |(_, amount)| *amount);
+                CategoryChange {
+                    category,
+                    current,
+                    previous,
+                }
+            })
+            .collect();
+        for (category, amount) in previous {
+            if !changes.iter().any(|c| c.category == category) {
+                changes.push(CategoryChange {
+                    category,
+                    current: 0.0,
+                    previous: amount,
+                });
+            }
+        }
+        changes.sort_by(|a, b| {
+            b.delta()
+                .abs()
+                .partial_cmp(&a.delta().abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.category.cmp(&b.category))
+        });
+        changes
+    }
+
+    /// Totals for each month of `year`, January first.
+    pub fn monthly_totals(&self, year: i32) -> [f64; 12] {
+        let mut totals = [0.0; 12];
+        for e in self.expenses.iter().filter(|e| e.date.year() == year) {
+            totals[e.date.month0() as usize] += e.amount;
+        }
+        totals
+    }
+
+    /// Each category's totals per month of `year`, biggest yearly total first.
+    pub fn category_totals_by_month(&self, year: i32) -> Vec<(String, [f64; 12])> {
+        let mut map: std::collections::HashMap<String, [f64; 12]> =
+            std::collections::HashMap::new();
+        for e in self.expenses.iter().filter(|e| e.date.year() == year) {
+            map.entry(e.category.to_string()).or_insert([0.0; 12])[e.date.month0() as usize] +=
+                e.amount;
+        }
+        let mut result: Vec<(String, [f64; 12])> = map.into_iter().collect();
+        result.sort_by(|a, b| {
+            let (ta, tb) = (a.1.iter().sum::<f64>(), b.1.iter().sum::<f64>());
+            tb.partial_cmp(&ta)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        result
+    }
+
+    /// Total spent on each day of `year` that has any expenses.
+    pub fn daily_totals(&self, year: i32) -> std::collections::HashMap<NaiveDate, f64> {
+        let mut totals = std::collections::HashMap::new();
+        for e in self.expenses.iter().filter(|e| e.date.year() == year) {
+            *totals.entry(e.date).or_insert(0.0) += e.amount;
+        }
+        totals
+    }
+
+    /// Expenses dated `date`, biggest first.
+    pub fn expenses_on(&self, date: NaiveDate) -> Vec<&Expense> {
+        let mut expenses: Vec<&Expense> = self.expenses.iter().filter(|e| e.date == date).collect();
+        expenses.sort_by(|a, b| {
+            b.amount
+                .partial_cmp(&a.amount)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        expenses
+    }
+
+    pub fn move_heatmap_day(&mut self, days: i64) {
+        let moved = if days < 0 {
+            self.heatmap_day.checked_sub_days(Days::new(days.unsigned_abs()))
+        } else {
+            self.heatmap_day.checked_add_days(Days::new(days as u64))
+        };
+        if let Some(day) = moved {
+            self.heatmap_day = day;
+        }
+    }
+
+    pub fn move_budget_selection(&mut self, delta: i32) {
+        let last = self.budget_categories().len().saturating_sub(1);
+        self.budget_index = if delta < 0 {
+            self.budget_index.min(last).saturating_sub(delta.unsigned_abs() as usize)
+        } else {
+            (self.budget_index + delta as usize).min(last)
+        };
+    }
+
+    pub fn move_goal_selection(&mut self, delta: i32) {
+        let last = self.goals.len().saturating_sub(1);
+        self.goal_index = if delta < 0 {
+            self.goal_index.min(last).saturating_sub(delta.unsigned_abs() as usize)
+        } else {
+            (self.goal_index + delta as usize).min(last)
+        };
+    }
+
+    pub fn move_loan_selection(&mut self, delta: i32) {
+        let last = self.loans.len().saturating_sub(1);
+        self.loan_index = if delta < 0 {
+            self.loan_index.min(last).saturating_sub(delta.unsigned_abs() as usize)
+        } else {
+            (self.loan_index + delta as usize).min(last)
+        };
+    }
+
+    /// Move through the subscriptions and then the detected candidates.
+    pub fn move_subscription_selection(&mut self, delta: i32) {
+        let count = self.subscriptions().len() + self.subscription_candidates().len();
+        let last = count.saturating_sub(1);
+        self.subscription_index = if delta < 0 {
+            self.subscription_index.min(last).saturating_sub(delta.unsigned_abs() as usize)
+        } else {
+            (self.subscription_index + delta as usize).min(last)
+        };
+    }
+
+    /// Move to the same day in another year; 29 February becomes the 28th.
+    pub fn shift_heatmap_year(&mut self, years: i32) {
+        let months = Months::new(years.unsigned_abs() * 12);
+        let moved = if years < 0 {
+            self.heatmap_day.checked_sub_months(months)
+        } else {
+            self.heatmap_day.checked_add_months(months)
+        };
+        if let Some(day) = moved {
+            self.heatmap_day = day;
+        }
+    }
+
     pub fn daily_spending_last_30_days(&self) -> Vec<u64> {
         let today = Local::now().date_naive();
-        let mut daily = vec![0u64; 30];
-        for i in 0..30 {
-            let day = today - chrono::Duration::days(29 - i as i64);
-            let total: f64 = self
-                .expenses
-                .iter()
-                .filter(|e| e.date == day)
-                .map(|e| e.amount)
-                .sum();
-            daily[i] = total as u64;
+        (0..30)
+            .map(|i| {
+                let day = today - chrono::Duration::days(29 - i);
+                let total: f64 = self
+                    .expenses
+                    .iter()
+                    .filter(|e| e.date == day)
+                    .map(|e| e.amount)
+                    .sum();
+                total as u64
+            })
+            .collect()
+    }
+
+    /// Every category with a budget, in the order budgets.csv lists them.
+    pub fn budget_categories(&self) -> Vec<&Category> {
+        let mut categories: Vec<&Category> = Vec::new();
+        for budget in &self.budgets {
+            if !categories.contains(&&budget.category) {
+                categories.push(&budget.category);
+            }
         }
-        daily
+        categories
     }
 
-    pub fn budget_for_category(&self, category: &Category) -> Option<f64> {
-        self.budgets
+    /// A category's budget versions, oldest first.
+    pub fn budget_versions(&self, category: &Category) -> Vec<&Budget> {
+        let mut versions: Vec<&Budget> = self
+            .budgets
             .iter()
-            .find(|b| &b.category == category)
-            .map(|b| b.monthly_limit)
+            .filter(|b| &b.category == category)
+            .collect();
+        versions.sort_by_key(|b| b.effective_from);
+        versions
+    }
+
+    /// The latest version of a category's budget in force on `date`.
+    fn budget_version_at(&self, category: &Category, date: NaiveDate) -> Option<&Budget> {
+        self.budget_versions(category)
+            .into_iter()
+            .rev()
+            .find(|b| b.effective_from.is_none_or(|from| from <= date))
+    }
+
+    /// The budget version that judges the period holding `date`, with that
+    /// period's first and last day. A new version takes over from the first
+    /// period that starts once it's in force, so a mid-period change doesn't
+    /// rewrite the period it lands in; a category's first version also
+    /// covers the period it starts in.
+    pub fn budget_for(
+        &self,
+        category: &Category,
+        date: NaiveDate,
+    ) -> Option<(&Budget, (NaiveDate, NaiveDate))> {
+        let latest = self.budget_version_at(category, date)?;
+        let window = latest.window(date)?;
+        if let Some(earlier) = self.budget_version_at(category, window.0) {
+            if !std::ptr::eq(earlier, latest) {
+                if let Some(earlier_window) = earlier.window(date) {
+                    return Some((earlier, earlier_window));
+                }
+            }
+        }
+        Some((latest, window))
+    }
+
+    /// A category's envelope for the period holding `date`. `None` when no
+    /// budget applies on that day.
+    pub fn envelope(&self, category: &Category, date: NaiveDate) -> Option<Envelope> {
+        self.envelopes(category, date)
+            .pop()
+            .filter(|e| e.start <= date && date <= e.end)
+    }
+
+    /// Every budget period of a category up to the one holding `until`,
+    /// oldest first, starting from the first period with spending in the
+    /// category. Each period is judged by the version in force for it, and
+    /// passes on whatever that version's rollover allows.
+    pub fn envelopes(&self, category: &Category, until: NaiveDate) -> Vec<Envelope> {
+        let Some((_, (current_start, _))) = self.budget_for(category, until) else {
+            return Vec::new();
+        };
+        let mut amounts: Vec<(NaiveDate, f64)> = self
+            .expenses
+            .iter()
+            .filter(|e| &e.category == category)
+            .map(|e| (e.date, e.amount))
+            .collect();
+        amounts.sort_by_key(|(date, _)| *date);
+        let spent_between = |from: NaiveDate, to: NaiveDate| -> f64 {
+            let lo = amounts.partition_point(|(d, _)| *d < from);
+            let hi = amounts.partition_point(|(d, _)| *d <= to);
+            amounts[lo..hi].iter().fold(0.0, |sum, (_, a)| sum + a)
+        };
+        // Days a version or a custom period begins, to skip gaps with no
+        // budget in force.
+        let changes: Vec<NaiveDate> = self
+            .budget_versions(category)
+            .iter()
+            .flat_map(|b| b.effective_from.into_iter().chain(b.start))
+            .collect();
+
+        let mut envelopes: Vec<Envelope> = Vec::new();
+        let mut carried = 0.0;
+        let mut day = amounts
+            .first()
+            .map_or(current_start, |(d, _)| (*d).min(current_start));
+        while day <= until {
+            let Some((version, (mut from, to))) = self.budget_for(category, day) else {
+                carried = 0.0;
+                match changes.iter().filter(|d| **d > day).min() {
+                    Some(next) => day = *next,
+                    None => break,
+                }
+                continue;
+            };
+            // A version with a different period can start mid-way through
+            // the previous window; don't count those days twice.
+            if let Some(previous) = envelopes.last() {
+                from = from.max(previous.end + Days::new(1));
+            }
+            let spent = spent_between(from, to.min(until));
+            envelopes.push(Envelope {
+                start: from,
+                end: to,
+                limit: version.limit,
+                carried,
+                spent,
+            });
+            carried = version.carry(version.limit + carried - spent);
+            match to.succ_opt() {
+                Some(next) => day = next,
+                None => break,
+            }
+        }
+        envelopes
+    }
+
+    /// The day budgets are judged at for a month: today in the current
+    /// month, otherwise the month's last day.
+    pub fn budget_date(&self, year: i32, month: u32) -> NaiveDate {
+        let today = utils::today();
+        if (today.year(), today.month()) == (year, month) {
+            return today;
+        }
+        NaiveDate::from_ymd_opt(year, month, 1)
+            .map(crate::forecast::month_end)
+            .unwrap_or(today)
+    }
+
+    /// Money available across all budgets in a month. Monthly budgets count
+    /// with their rollover; other periods by their share of the month.
+    pub fn total_budget(&self, year: i32, month: u32) -> f64 {
+        let Some(month_start) = NaiveDate::from_ymd_opt(year, month, 1) else {
+            return 0.0;
+        };
+        let month_end = crate::forecast::month_end(month_start);
+        self.budget_categories()
+            .into_iter()
+            .filter_map(|category| {
+                let budget = match self.budget_for(category, month_end) {
+                    Some((budget, _)) => budget,
+                    None => self.budget_version_at(category, month_end)?,
+                };
+                match budget.period {
+                    Period::Monthly => self.envelope(category, month_end).map(|e| e.available()),
+                    _ => Some(budget.monthly_share(month_start)),
+                }
+            })
+            .sum()
+    }
+
+    /// How saving `amount` in `category` on `date` would move the budget of
+    /// the period it lands in. `replacing` is the expense being edited, so
+    /// its old amount isn't counted twice.
+    pub fn budget_impact(
+        &self,
+        category: &Category,
+        date: NaiveDate,
+        amount: f64,
+        replacing: Option<u64>,
+    ) -> Option<BudgetImpact> {
+        let (budget, (start, end)) = self.budget_for(category, date)?;
+        let today = utils::today();
+        // Everything spent in the period so far, even when the expense is
+        // back-dated into it.
+        let as_of = end.min(today).max(date);
+        let envelope = self.envelope(category, as_of)?;
+        let replaced = replacing
+            .and_then(|id| self.expenses.iter().find(|e| e.id == id))
+            .filter(|e| &e.category == category && e.date >= start && e.date <= as_of)
+            .map_or(0.0, |e| e.amount);
+        let before = envelope.spent - replaced;
+        let after = before + amount;
+        let crossed = budget
+            .alerts
+            .iter()
+            .copied()
+            .rfind(|t| envelope.percent_used(before) < *t && envelope.percent_used(after) >= *t);
+        let period = if (start..=end).contains(&today) || budget.period == Period::Custom {
+            budget.period_label()
+        } else {
+            format!(
+                "{} – {}",
+                start.format("%-d %b"),
+                end.format("%-d %b %Y")
+            )
+        };
+        Some(BudgetImpact {
+            category: category.clone(),
+            period,
+            envelope,
+            before,
+            after,
+            crossed,
+        })
+    }
+
+    /// Budgets whose current period is past their lowest alert threshold,
+    /// with the percentage used, most used first.
+    fn current_budget_warnings(&self) -> Vec<(String, f64)> {
+        let today = utils::today();
+        let mut warnings: Vec<(String, f64)> = self
+            .budget_categories()
+            .into_iter()
+            .filter_map(|category| {
+                let (budget, _) = self.budget_for(category, today)?;
+                let envelope = self.envelope(category, today)?;
+                let used = envelope.percent_used(envelope.spent);
+                budget
+                    .alerts
+                    .first()
+                    .is_some_and(|t| used >= *t)
+                    .then(|| (category.to_string(), used))
+            })
+            .collect();
+        warnings.sort_by(|a, b| b.1.total_cmp(&a.1));
+        warnings
+    }
+
+    /// The most a month may spend: the monthly cap when one is set,
+    /// otherwise the category budgets added up. Zero when there's neither.
+    pub fn spending_limit(&self, year: i32, month: u32) -> f64 {
+        self.monthly_cap.unwrap_or_else(|| self.total_budget(year, month))
+    }
+
+    /// Spending in a month on categories no budget covers, biggest first.
+    pub fn unbudgeted_spending(&self, year: i32, month: u32) -> Vec<(String, f64)> {
+        let as_of = self.budget_date(year, month);
+        let budgeted: Vec<String> = self
+            .budget_categories()
+            .into_iter()
+            .filter(|c| self.budget_for(c, as_of).is_some())
+            .map(|c| c.to_string())
+            .collect();
+        self.spending_by_category(year, month)
+            .into_iter()
+            .filter(|(name, _)| !budgeted.contains(name))
+            .collect()
+    }
+
+    pub fn selected_goal(&self) -> Option<&Goal> {
+        self.goals.get(self.goal_index.min(self.goals.len().saturating_sub(1)))
+    }
+
+    /// A goal's contributions, oldest first.
+    pub fn goal_contributions(&self, goal: &Goal) -> Vec<&Contribution> {
+        let mut contributions: Vec<&Contribution> = self
+            .contributions
+            .iter()
+            .filter(|c| c.goal == goal.name)
+            .collect();
+        contributions.sort_by_key(|c| c.date);
+        contributions
+    }
+
+    /// How far a goal has got by `as_of`. Without a start date, saving counts
+    /// as starting with the first contribution.
+    pub fn goal_progress(&self, goal: &Goal, as_of: NaiveDate) -> GoalProgress {
+        let contributions = self.goal_contributions(goal);
+        let saved = contributions
+            .iter()
+            .filter(|c| c.date <= as_of)
+            .fold(0.0, |total, c| total + c.amount);
+        let start = goal
+            .start
+            .or_else(|| contributions.first().map(|c| c.date))
+            .unwrap_or(as_of);
+        GoalProgress {
+            target: goal.target,
+            saved,
+            start,
+            target_date: goal.target_date,
+            as_of,
+        }
+    }
+
+    /// The goal or contribution the Goals tab prompt would add, described for
+    /// the preview line, or why it can't.
+    pub fn goal_prompt_preview(&self) -> Result<String, String> {
+        let today = utils::today();
+        match self.goal_prompt {
+            GoalPrompt::NewGoal => {
+                let goal = prompts::parse_goal(self.goal_input.as_str(), today)?;
+                let progress = self.goal_progress(&goal, today);
+                Ok(format!(
+                    "{}: {} by {} — {}/month",
+                    goal.name,
+                    self.fmt(goal.target),
+                    goal.target_date.format("%-d %b %Y"),
+                    self.fmt(progress.required_monthly())
+                ))
+            }
+            GoalPrompt::Contribute => {
+                let goal = self.selected_goal().ok_or("No goal selected")?;
+                let contribution =
+                    prompts::parse_contribution(self.goal_input.as_str(), &goal.name, today)?;
+                let verb = if contribution.amount < 0.0 { "Withdraw" } else { "Add" };
+                let mut text = format!(
+                    "{} {} on {}",
+                    verb,
+                    self.fmt(contribution.amount.abs()),
+                    contribution.date.format("%a %-d %b %Y")
+                );
+                if !contribution.note.is_empty() {
+                    text.push_str(&format!(" \"{}\"", contribution.note));
+                }
+                Ok(text)
+            }
+        }
+    }
+
+    /// Add what the Goals tab prompt holds and save it.
+    pub fn submit_goal_prompt(&mut self) -> Result<String, String> {
+        if let Some(error) = &self.goals_error {
+            return Err(format!("Goals aren't saved until this is fixed: {}", error));
+        }
+        let today = utils::today();
+        match self.goal_prompt {
+            GoalPrompt::NewGoal => {
+                let goal = prompts::parse_goal(self.goal_input.as_str(), today)?;
+                if self.goals.iter().any(|g| g.name.eq_ignore_ascii_case(&goal.name)) {
+                    return Err(format!("There's already a goal called '{}'", goal.name));
+                }
+                let message = format!("Added goal '{}'", goal.name);
+                self.goals.push(goal);
+                self.goal_index = self.goals.len() - 1;
+                storage::save_goals(&self.goals).map_err(|e| format!("{:#}", e))?;
+                Ok(message)
+            }
+            GoalPrompt::Contribute => {
+                let goal = self.selected_goal().ok_or("No goal selected")?;
+                let contribution =
+                    prompts::parse_contribution(self.goal_input.as_str(), &goal.name, today)?;
+                let message = if contribution.amount < 0.0 {
+                    format!(
+                        "Withdrew {} from '{}'",
+                        self.fmt(-contribution.amount),
+                        goal.name
+                    )
+                } else {
+                    format!("Added {} to '{}'", self.fmt(contribution.amount), goal.name)
+                };
+                self.contributions.push(contribution);
+                storage::save_contributions(&self.contributions)
+                    .map_err(|e| format!("{:#}", e))?;
+                Ok(message)
+            }
+        }
+    }
+
+    /// Expenses tagged as payments on a loan up to today, oldest first.
+    pub fn loan_payments(&self, loan: &Loan) -> Vec<&Expense> {
+        let today = utils::today();
+        let mut payments: Vec<&Expense> = self
+            .expenses
+            .iter()
+            .filter(|e| e.date <= today && e.tags.iter().any(|t| t.eq_ignore_ascii_case(&loan.tag)))
+            .collect();
+        payments.sort_by_key(|e| (e.date, e.id));
+        payments
+    }
+
+    pub fn loan_summary(&self, loan: &Loan) -> LoanSummary {
+        let payments: Vec<(NaiveDate, f64)> = self
+            .loan_payments(loan)
+            .into_iter()
+            .map(|e| (e.date, e.amount))
+            .collect();
+        loan.summarize(&payments)
+    }
+
+    /// The loan the Loans tab prompt would add, described for the preview
+    /// line, or why it can't.
+    pub fn loan_prompt_preview(&self) -> Result<String, String> {
+        let loan = prompts::parse_loan(self.loan_input.as_str(), utils::today())?;
+        let rate = if loan.rate > 0.0 {
+            format!("{}%", loan.rate)
+        } else {
+            "interest-free".to_string()
+        };
+        Ok(format!(
+            "{}: {} at {} over {} months — {}/month from {}, payments tagged #{}",
+            loan.name,
+            self.fmt(loan.principal),
+            rate,
+            loan.term_months,
+            self.fmt(loan.payment()),
+            loan.first_payment.format("%-d %b %Y"),
+            loan.tag
+        ))
+    }
+
+    /// Add the loan the Loans tab prompt holds and save it.
+    pub fn submit_loan_prompt(&mut self) -> Result<String, String> {
+        if let Some(error) = &self.loans_error {
+            return Err(format!("Loans aren't saved until this is fixed: {}", error));
+        }
+        let loan = prompts::parse_loan(self.loan_input.as_str(), utils::today())?;
+        if self.loans.iter().any(|l| l.name.eq_ignore_ascii_case(&loan.name)) {
+            return Err(format!("There's already a loan called '{}'", loan.name));
+        }
+        if let Some(other) = self.loans.iter().find(|l| l.tag.eq_ignore_ascii_case(&loan.tag)) {
+            return Err(format!("'{}' already uses #{}", other.name, loan.tag));
+        }
+        let message = format!(
+            "Added loan '{}'; tag its payments #{}",
+            loan.name, loan.tag
+        );
+        self.loans.push(loan);
+        self.loan_index = self.loans.len() - 1;
+        storage::save_loans(&self.loans).map_err(|e| format!("{:#}", e))?;
+        Ok(message)
+    }
+
+    pub fn subscriptions(&self) -> Vec<Subscription> {
+        subscriptions::subscriptions(&self.expenses, utils::today())
+    }
+
+    pub fn subscription_candidates(&self) -> Vec<Candidate> {
+        subscriptions::detect(&self.expenses, utils::today())
+    }
+
+    /// The selected subscription, if the selection isn't on a candidate.
+    pub fn selected_subscription(&self) -> Option<Subscription> {
+        self.subscriptions().into_iter().nth(self.subscription_index)
+    }
+
+    /// The selected candidate, if the selection is past the subscriptions.
+    pub fn selected_candidate(&self) -> Option<Candidate> {
+        let index = self.subscription_index.checked_sub(self.subscriptions().len())?;
+        self.subscription_candidates().into_iter().nth(index)
+    }
+
+    /// What cancelling the selected subscription from the date in the
+    /// prompt would do, or why it can't.
+    fn cancellation(&self) -> Result<(Subscription, NaiveDate, Vec<u64>), String> {
+        let subscription = self.selected_subscription().ok_or("No subscription selected")?;
+        let from = utils::parse_date(self.subscription_input.as_str(), utils::today())?;
+        let template = self
+            .expenses
+            .iter()
+            .find(|e| e.id == subscription.id)
+            .ok_or("No subscription selected")?;
+        let since = subscription.since().unwrap_or(template.date);
+        if from <= since {
+            return Err(format!(
+                "{} started on {}; delete it instead",
+                subscription.name,
+                since.format("%-d %b %Y")
+            ));
+        }
+        let removed = self
+            .expenses
+            .iter()
+            .filter(|e| e.date >= from && subscriptions::in_series(template, e))
+            .map(|e| e.id)
+            .collect();
+        Ok((subscription, from, removed))
+    }
+
+    pub fn cancel_preview(&self) -> Result<String, String> {
+        let (subscription, from, removed) = self.cancellation()?;
+        let mut text = format!(
+            "No {} charges from {}",
+            subscription.name,
+            from.format("%a %-d %b %Y")
+        );
+        if !removed.is_empty() {
+            text.push_str(&format!(
+                "; removes {} charge{} already recorded",
+                removed.len(),
+                if removed.len() == 1 { "" } else { "s" }
+            ));
+        }
+        Ok(text)
+    }
+
+    /// End the selected subscription from the date in the prompt, removing
+    /// charges already generated from then on.
+    pub fn cancel_subscription(&mut self) -> Result<String, String> {
+        let (subscription, from, removed) = self.cancellation()?;
+        let Some(template) = self.expenses.iter().find(|e| e.id == subscription.id).cloned() else {
+            return Err("No subscription selected".to_string());
+        };
+        self.push_undo("cancel subscription");
+        self.expenses.retain(|e| !removed.contains(&e.id));
+        // A price change from `from` on never takes effect, so its recurring
+        // entry goes too and the latest charge left ends the series instead.
+        let last = if removed.contains(&template.id) {
+            self.expenses
+                .iter_mut()
+                .filter(|e| subscriptions::in_series(&template, e))
+                .max_by_key(|e| (e.date, e.id))
+        } else {
+            self.expenses.iter_mut().find(|e| e.id == template.id)
+        };
+        if let Some(last) = last {
+            last.is_recurring = true;
+            last.recurrence = template.recurrence;
+            last.cancelled = Some(from);
+        }
+        self.update_filtered_indices();
+        self.save().map_err(|e| format!("{:#}", e))?;
+        Ok(format!(
+            "Cancelled {} from {}",
+            subscription.name,
+            from.format("%-d %b %Y")
+        ))
+    }
+
+    /// What changing the selected subscription's price to the amount in the
+    /// prompt would do: the subscription, the new price and its first charge.
+    fn price_change(&self) -> Result<(Subscription, f64, NaiveDate), String> {
+        let subscription = self.selected_subscription().ok_or("No subscription selected")?;
+        let next = subscription
+            .next_charge
+            .ok_or_else(|| format!("{} has no charges left", subscription.name))?;
+        let amount = utils::eval_amount(self.subscription_input.as_str())?;
+        let amount = (amount * 100.0).round() / 100.0;
+        if amount <= 0.0 {
+            return Err("Amount must be positive".to_string());
+        }
+        if (amount - subscription.amount).abs() < 0.005 {
+            return Err(format!("{} already costs {}", subscription.name, self.fmt(amount)));
+        }
+        Ok((subscription, amount, next))
+    }
+
+    pub fn price_change_preview(&self) -> Result<String, String> {
+        let (subscription, amount, from) = self.price_change()?;
+        let mut text = format!(
+            "{} → {} from {}",
+            self.fmt(subscription.amount),
+            self.fmt(amount),
+            from.format("%a %-d %b %Y")
+        );
+        if let Some(percent) = percent_change(amount, subscription.amount) {
+            text.push_str(&format!(" ({:+.0}%)", percent));
+        }
+        Ok(text)
+    }
+
+    /// Charge the amount in the prompt from the selected subscription's next
+    /// charge on. That charge becomes the recurring entry, so the charges
+    /// before it keep the price they were made at.
+    pub fn change_subscription_price(&mut self) -> Result<String, String> {
+        let (subscription, amount, from) = self.price_change()?;
+        let Some(pos) = self.expenses.iter().position(|e| e.id == subscription.id) else {
+            return Err("No subscription selected".to_string());
+        };
+        let id = self.next_id();
+        self.push_undo("change price");
+        let old = &mut self.expenses[pos];
+        if old.date >= from {
+            // Already changed once from this charge on.
+            old.amount = amount;
+        } else {
+            let mut template = old.clone();
+            old.is_recurring = false;
+            old.recurrence = None;
+            old.cancelled = None;
+            template.id = id;
+            template.amount = amount;
+            template.date = from;
+            self.expenses.push(template);
+        }
+        self.update_filtered_indices();
+        self.save().map_err(|e| format!("{:#}", e))?;
+        Ok(format!(
+            "{} costs {} from {}",
+            subscription.name,
+            self.fmt(amount),
+            from.format("%-d %b %Y")
+        ))
+    }
+
+    /// Turn the selected candidate into a recurring series, continuing from
+    /// its latest charge.
+    pub fn mark_candidate(&mut self) -> Result<String> {
+        let Some(candidate) = self.selected_candidate() else {
+            return Ok("Select a possible subscription to mark".to_string());
+        };
+        self.push_undo("mark subscription");
+        if let Some(latest) = self.expenses.iter_mut().find(|e| e.id == candidate.latest_id) {
+            latest.is_recurring = true;
+            latest.recurrence = Some(candidate.recurrence);
+        }
+        self.generate_recurring_expenses();
+        self.update_filtered_indices();
+        self.save()?;
+        Ok(format!(
+            "Marked {} as a {} subscription",
+            candidate.description,
+            candidate.recurrence.to_string().to_lowercase()
+        ))
     }
 
     pub fn cycle_currency_forward(&mut self) {
         let next_index = (self.currency.to_index() + 1) % Currency::count();
         self.currency = Currency::from_index(next_index);
-        let _ = storage::save_currency(&self.currency);
+        let _ = self.save_config();
         self.status_message = Some(format!("Currency: {}", self.currency.display_name()));
     }
 
@@ -415,10 +2048,118 @@ impl App {
             self.currency.to_index() - 1
         };
         self.currency = Currency::from_index(prev_index);
-        let _ = storage::save_currency(&self.currency);
+        let _ = self.save_config();
         self.status_message = Some(format!("Currency: {}", self.currency.display_name()));
     }
 
+    /// Sort by the next visible column, keeping the current direction.
+    pub fn cycle_sort_column(&mut self) {
+        let pos = self
+            .columns
+            .iter()
+            .position(|c| *c == self.sort.column)
+            .map(|p| (p + 1) % self.columns.len())
+            .unwrap_or(0);
+        self.sort.column = self.columns[pos];
+        self.apply_sort();
+    }
+
+    pub fn toggle_sort_direction(&mut self) {
+        self.sort.ascending = !self.sort.ascending;
+        self.apply_sort();
+    }
+
+    /// Sort by `column`, or flip the direction if it is already the sort column.
+    pub fn sort_by_column(&mut self, column: Column) {
+        if self.sort.column == column {
+            self.sort.ascending = !self.sort.ascending;
+        } else {
+            self.sort = SortOrder {
+                column,
+                ascending: column != Column::Date && column != Column::Amount,
+            };
+        }
+        self.apply_sort();
+    }
+
+    fn apply_sort(&mut self) {
+        self.update_filtered_indices();
+        let _ = self.save_config();
+        self.status_message = Some(format!(
+            "Sorted by {} {}",
+            self.sort.column,
+            self.sort.indicator()
+        ));
+    }
+
+    /// All columns in editor order: visible ones in display order, then hidden.
+    pub fn column_editor_entries(&self) -> Vec<(Column, bool)> {
+        let mut entries: Vec<(Column, bool)> = self.columns.iter().map(|c| (*c, true)).collect();
+        entries.extend(
+            Column::all()
+                .iter()
+                .filter(|c| !self.columns.contains(c))
+                .map(|c| (*c, false)),
+        );
+        entries
+    }
+
+    pub fn toggle_column_at(&mut self, index: usize) {
+        let Some((column, visible)) = self.column_editor_entries().get(index).copied() else {
+            return;
+        };
+        if visible {
+            if self.columns.len() == 1 {
+                self.status_message = Some("At least one column must stay visible".to_string());
+                return;
+            }
+            self.columns.retain(|c| *c != column);
+        } else {
+            self.columns.push(column);
+        }
+        self.column_editor_index = self
+            .column_editor_entries()
+            .iter()
+            .position(|(c, _)| *c == column)
+            .unwrap_or(0);
+        let _ = self.save_config();
+    }
+
+    /// Move the visible column at `index` one place left (`-1`) or right (`1`).
+    pub fn move_column_at(&mut self, index: usize, delta: isize) {
+        if index >= self.columns.len() {
+            return;
+        }
+        let target = index as isize + delta;
+        if target < 0 || target as usize >= self.columns.len() {
+            return;
+        }
+        self.columns.swap(index, target as usize);
+        self.column_editor_index = target as usize;
+        let _ = self.save_config();
+    }
+
+    /// Switch to the next built-in or user theme.
+    pub fn cycle_theme(&mut self) {
+        if no_color() {
+            self.status_message = Some("NO_COLOR is set; themes are disabled".to_string());
+            return;
+        }
+        let mut names: Vec<String> = BUILTIN_THEMES.iter().map(|n| n.to_string()).collect();
+        for name in storage::list_theme_files().unwrap_or_default() {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        let pos = names.iter().position(|n| *n == self.theme.name);
+        let next = &names[pos.map(|p| (p + 1) % names.len()).unwrap_or(0)];
+        let (theme, error) = load_theme(next);
+        self.theme = theme;
+        self.theme_name = self.theme.name.clone();
+        let _ = self.save_config();
+        self.status_message = Some(error.unwrap_or_else(|| format!("Theme: {}", self.theme.name)));
+    }
+
     pub fn fmt(&self, amount: f64) -> String {
         self.currency.format(amount)
     }
@@ -436,6 +2177,19 @@ impl App {
         }
     }
 
+    /// Show `month` in the Monthly tab.
+    pub fn go_to_month(&mut self, month: NaiveDate) {
+        self.selected_month = month.month();
+        self.selected_year = month.year();
+        self.active_tab = Tab::Monthly;
+    }
+
+    pub fn current_month(&mut self) {
+        let today = utils::today();
+        self.selected_month = today.month();
+        self.selected_year = today.year();
+    }
+
     pub fn next_month(&mut self) {
         if self.selected_month == 12 {
             self.selected_month = 1;
@@ -445,3 +2199,138 @@ impl App {
         }
     }
 }
+
+/// Load the user keymap, falling back to the defaults if it can't be used.
+/// The second value is a message describing why, for the status bar.
+fn load_keymap() -> (Keymap, Option<String>) {
+    match storage::load_keymap() {
+        Ok(None) => (Keymap::default(), None),
+        Ok(Some(content)) => match Keymap::from_config(&content) {
+            Ok(keymap) => (keymap, None),
+            Err(problems) => (
+                Keymap::default(),
+                Some(format!(
+                    "Keymap ignored ({} problem{}): {}",
+                    problems.len(),
+                    if problems.len() == 1 { "" } else { "s" },
+                    problems[0]
+                )),
+            ),
+        },
+        Err(e) => (Keymap::default(), Some(format!("Keymap ignored: {}", e))),
+    }
+}
+
+fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+}
+
+/// Resolve a theme name: `NO_COLOR` wins, then user theme files (which may
+/// shadow a built-in), then built-ins. Falls back to the dark theme with a
+/// message on any problem.
+fn load_theme(name: &str) -> (Theme, Option<String>) {
+    if no_color() {
+        return (Theme::monochrome(), None);
+    }
+    match storage::load_theme_file(name) {
+        Ok(Some(content)) => match Theme::from_config(name, &content) {
+            Ok(theme) => (theme, None),
+            Err(e) => (Theme::default(), Some(format!("Theme '{}' ignored: {}", name, e))),
+        },
+        Ok(None) => match Theme::builtin(name) {
+            Some(theme) => (theme, None),
+            None => (Theme::default(), Some(format!("Unknown theme '{}'", name))),
+        },
+        Err(e) => (Theme::default(), Some(format!("Theme '{}' ignored: {}", name, e))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Rollover;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn food(limit: f64, rollover: Rollover) -> Budget {
+        Budget {
+            rollover,
+            ..Budget::_new(Category::Food, limit)
+        }
+    }
+
+    fn app(budgets: Vec<Budget>, spending: &[(NaiveDate, f64)]) -> App {
+        let mut app = App::empty(&storage::Config::default(), Keymap::default(), Theme::default());
+        app.budgets = budgets;
+        app.expenses = spending
+            .iter()
+            .enumerate()
+            .map(|(i, (day, amount))| {
+                let id = i as u64 + 1;
+                Expense::new(id, *amount, Category::Food, String::new(), *day, false, None)
+            })
+            .collect();
+        app
+    }
+
+    fn carried(app: &App, until: NaiveDate) -> Vec<f64> {
+        app.envelopes(&Category::Food, until)
+            .iter()
+            .map(|e| e.carried)
+            .collect()
+    }
+
+    #[test]
+    fn surplus_rolls_over_but_overspending_does_not() {
+        let spending = [(date(2026, 1, 10), 60.0), (date(2026, 2, 10), 150.0)];
+        let app = app(vec![food(100.0, Rollover::Surplus)], &spending);
+        assert_eq!(carried(&app, date(2026, 3, 15)), [0.0, 40.0, 0.0]);
+    }
+
+    #[test]
+    fn deficit_rolls_over_but_savings_do_not() {
+        let spending = [(date(2026, 1, 10), 60.0), (date(2026, 2, 10), 130.0)];
+        let app = app(vec![food(100.0, Rollover::Deficit)], &spending);
+        assert_eq!(carried(&app, date(2026, 3, 15)), [0.0, 0.0, -30.0]);
+    }
+
+    #[test]
+    fn rollover_is_capped_both_ways() {
+        let budget = Budget {
+            rollover_cap: Some(25.0),
+            ..food(100.0, Rollover::Both)
+        };
+        let spending = [(date(2026, 1, 10), 20.0), (date(2026, 2, 10), 200.0)];
+        let app = app(vec![budget], &spending);
+        assert_eq!(carried(&app, date(2026, 3, 15)), [0.0, 25.0, -25.0]);
+    }
+
+    #[test]
+    fn mid_period_version_starts_after_the_previous_envelope() {
+        // A weekly version from Wednesday 11 March leaves March to the
+        // monthly one; its first week then starts on 1 April, not on the
+        // Monday before, so 31 March isn't counted twice.
+        let weekly = Budget {
+            effective_from: Some(date(2026, 3, 11)),
+            period: Period::Weekly,
+            ..food(30.0, Rollover::None)
+        };
+        let spending = [
+            (date(2026, 3, 5), 10.0),
+            (date(2026, 3, 31), 20.0),
+            (date(2026, 4, 1), 7.0),
+            (date(2026, 4, 2), 3.0),
+        ];
+        let app = app(vec![food(100.0, Rollover::None), weekly], &spending);
+        let envelopes = app.envelopes(&Category::Food, date(2026, 4, 3));
+
+        assert_eq!(envelopes.len(), 2);
+        let (march, week) = (envelopes[0], envelopes[1]);
+        assert_eq!((march.start, march.end), (date(2026, 3, 1), date(2026, 3, 31)));
+        assert_eq!((march.limit, march.spent), (100.0, 30.0));
+        assert_eq!((week.start, week.end), (date(2026, 4, 1), date(2026, 4, 5)));
+        assert_eq!((week.limit, week.spent), (30.0, 10.0));
+    }
+}
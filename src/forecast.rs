@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Months, NaiveDate};
+
+use crate::model::Expense;
+use crate::subscriptions;
+
+/// Months of history averaged for the usual spending pattern.
+const HISTORY_MONTHS: u32 = 3;
+
+/// One category's projected month-end spending.
+#[derive(Debug, Clone)]
+pub struct CategoryForecast {
+    pub category: String,
+    /// Spent so far this month.
+    pub spent: f64,
+    /// Recurring expenses still due this month.
+    pub upcoming: f64,
+    /// Projected month-end total.
+    pub projected: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Forecast {
+    /// Biggest projected total first.
+    pub categories: Vec<CategoryForecast>,
+    pub days_elapsed: u32,
+    pub days_in_month: u32,
+}
+
+impl Forecast {
+    pub fn projected(&self) -> f64 {
+        self.categories.iter().map(|c| c.projected).sum()
+    }
+
+    pub fn for_category(&self, category: &str) -> Option<&CategoryForecast> {
+        self.categories.iter().find(|c| c.category == category)
+    }
+}
+
+fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+pub fn month_end(date: NaiveDate) -> NaiveDate {
+    month_start(date)
+        .checked_add_months(Months::new(1))
+        .and_then(|d| d.pred_opt())
+        .unwrap_or(date)
+}
+
+/// Project month-end spending for the month holding `today`.
+///
+/// What's been spent so far stays as is. On top of it come the recurring
+/// expenses still due (`upcoming`, from `App::pending_recurring`) and, for
+/// each remaining day, a daily rate for everything else: this month's
+/// run-rate blended with the average of the previous months, leaning on the
+/// run-rate more as the month goes on.
+pub fn project(expenses: &[Expense], upcoming: &[Expense], today: NaiveDate) -> Forecast {
+    let start = month_start(today);
+    let end = month_end(today);
+    let days_in_month = end.day();
+    let days_elapsed = today.day();
+    let days_left = (days_in_month - days_elapsed) as f64;
+
+    // Recurring amounts are already counted through `upcoming`, so they are
+    // left out of the run-rate and the history.
+    let templates: Vec<&Expense> = expenses.iter().filter(|e| e.is_recurring).collect();
+    let is_recurring =
+        |e: &Expense| e.is_recurring || templates.iter().any(|t| subscriptions::in_series(t, e));
+
+    let mut categories: HashMap<String, CategoryForecast> = HashMap::new();
+
+    let mut variable_so_far: HashMap<String, f64> = HashMap::new();
+    for e in expenses.iter().filter(|e| e.date >= start && e.date <= today) {
+        entry(&mut categories, e.category.to_string()).spent += e.amount;
+        if !is_recurring(e) {
+            *variable_so_far.entry(e.category.to_string()).or_default() += e.amount;
+        }
+    }
+    // Entries already dated later this month count as still due.
+    let later = expenses.iter().filter(|e| e.date > today && e.date <= end);
+    for e in upcoming.iter().filter(|e| e.date >= start && e.date <= end).chain(later) {
+        entry(&mut categories, e.category.to_string()).upcoming += e.amount;
+    }
+
+    // Average daily spending per category over the previous months that
+    // have any expenses at all, so a new user's empty history doesn't drag
+    // the forecast down.
+    let mut history: HashMap<String, f64> = HashMap::new();
+    let mut history_days = 0;
+    for k in 1..=HISTORY_MONTHS {
+        let Some(from) = start.checked_sub_months(Months::new(k)) else {
+            break;
+        };
+        let to = month_end(from);
+        let in_month: Vec<&Expense> = expenses
+            .iter()
+            .filter(|e| e.date >= from && e.date <= to)
+            .collect();
+        if in_month.is_empty() {
+            continue;
+        }
+        history_days += to.day();
+        for e in in_month.into_iter().filter(|e| !is_recurring(e)) {
+            *history.entry(e.category.to_string()).or_default() += e.amount;
+        }
+    }
+    for category in history.keys() {
+        entry(&mut categories, category.clone());
+    }
+
+    let weight = if history_days == 0 {
+        1.0
+    } else {
+        days_elapsed as f64 / days_in_month as f64
+    };
+    let mut categories: Vec<CategoryForecast> = categories
+        .into_values()
+        .map(|mut c| {
+            let run_rate =
+                variable_so_far.get(&c.category).copied().unwrap_or(0.0) / days_elapsed as f64;
+            let usual = if history_days == 0 {
+                0.0
+            } else {
+                history.get(&c.category).copied().unwrap_or(0.0) / history_days as f64
+            };
+            let daily = weight * run_rate + (1.0 - weight) * usual;
+            c.projected = c.spent + c.upcoming + daily * days_left;
+            c
+        })
+        .collect();
+    categories.sort_by(|a, b| {
+        b.projected
+            .partial_cmp(&a.projected)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.category.cmp(&b.category))
+    });
+
+    Forecast {
+        categories,
+        days_elapsed,
+        days_in_month,
+    }
+}
+
+fn entry(
+    categories: &mut HashMap<String, CategoryForecast>,
+    category: String,
+) -> &mut CategoryForecast {
+    categories
+        .entry(category.clone())
+        .or_insert(CategoryForecast {
+            category,
+            spent: 0.0,
+            upcoming: 0.0,
+            projected: 0.0,
+        })
+}
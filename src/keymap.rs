@@ -0,0 +1,810 @@
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Where a binding is active. Global bindings apply in Normal mode on every
+/// tab, alongside the bindings of the active tab. The form and each popup
+/// have bindings of their own, active only while they are open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    Global,
+    Expenses,
+    Monthly,
+    Yearly,
+    Heatmap,
+    Budgets,
+    Goals,
+    Loans,
+    Subscriptions,
+    Form,
+    Calendar,
+    Columns,
+    Templates,
+    Review,
+    DayDetail,
+    Help,
+}
+
+impl KeyContext {
+    pub fn all() -> &'static [KeyContext] {
+        &[
+            KeyContext::Global,
+            KeyContext::Expenses,
+            KeyContext::Monthly,
+            KeyContext::Yearly,
+            KeyContext::Heatmap,
+            KeyContext::Budgets,
+            KeyContext::Goals,
+            KeyContext::Loans,
+            KeyContext::Subscriptions,
+            KeyContext::Form,
+            KeyContext::Calendar,
+            KeyContext::Columns,
+            KeyContext::Templates,
+            KeyContext::Review,
+            KeyContext::DayDetail,
+            KeyContext::Help,
+        ]
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            KeyContext::Global => "Global Keybindings",
+            KeyContext::Expenses => "Expenses Tab",
+            KeyContext::Monthly => "Monthly Tab",
+            KeyContext::Yearly => "Yearly Tab",
+            KeyContext::Heatmap => "Heatmap Tab",
+            KeyContext::Budgets => "Budgets Tab",
+            KeyContext::Goals => "Goals Tab",
+            KeyContext::Loans => "Loans Tab",
+            KeyContext::Subscriptions => "Subscriptions Tab",
+            KeyContext::Form => "Form",
+            KeyContext::Calendar => "Form Calendar",
+            KeyContext::Columns => "Column Editor",
+            KeyContext::Templates => "Template Picker",
+            KeyContext::Review => "Unusual Spending Review",
+            KeyContext::DayDetail => "Heatmap Day Details",
+            KeyContext::Help => "Help",
+        }
+    }
+
+    /// The form or a popup, whose bindings replace the Normal mode ones
+    /// while it is open.
+    pub fn is_modal(&self) -> bool {
+        matches!(
+            self,
+            KeyContext::Form
+                | KeyContext::Calendar
+                | KeyContext::Columns
+                | KeyContext::Templates
+                | KeyContext::Review
+                | KeyContext::DayDetail
+                | KeyContext::Help
+        )
+    }
+
+    fn overlaps(&self, other: KeyContext) -> bool {
+        match (*self, other) {
+            (a, b) if a == b => true,
+            (a, b) if a.is_modal() || b.is_modal() => false,
+            (KeyContext::Global, _) | (_, KeyContext::Global) => true,
+            _ => false,
+        }
+    }
+}
+
+/// A named, rebindable action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Help,
+    CommandPalette,
+    TabDashboard,
+    TabExpenses,
+    TabMonthly,
+    TabYearly,
+    TabHeatmap,
+    TabBudgets,
+    TabGoals,
+    TabLoans,
+    TabSubscriptions,
+    NextTab,
+    PrevTab,
+    AddExpense,
+    Templates,
+    QuickAdd,
+    CurrencyNext,
+    CurrencyPrev,
+    Export,
+    Undo,
+    CycleTheme,
+    MoveDown,
+    MoveUp,
+    MoveTop,
+    MoveBottom,
+    Search,
+    EditExpense,
+    DeleteExpense,
+    ToggleRecurringFilter,
+    CycleSort,
+    ReverseSort,
+    EditColumns,
+    ToggleMark,
+    RangeSelect,
+    SelectAll,
+    ClearSelection,
+    BulkActions,
+    DuplicateExpense,
+    SaveTemplate,
+    ReviewAnomalies,
+    PrevMonth,
+    NextMonth,
+    CurrentMonth,
+    MonthlyCompare,
+    PrevYear,
+    NextYear,
+    CurrentYear,
+    HeatmapPrevWeek,
+    HeatmapNextWeek,
+    HeatmapPrevDay,
+    HeatmapNextDay,
+    HeatmapPrevYear,
+    HeatmapNextYear,
+    HeatmapToday,
+    HeatmapShowDay,
+    BudgetsDown,
+    BudgetsUp,
+    GoalsDown,
+    GoalsUp,
+    GoalsNew,
+    GoalsContribute,
+    LoansDown,
+    LoansUp,
+    LoansNew,
+    SubscriptionsDown,
+    SubscriptionsUp,
+    SubscriptionsCancel,
+    SubscriptionsPrice,
+    SubscriptionsMark,
+    FormNextField,
+    FormPrevField,
+    FormSave,
+    FormCancel,
+    FormCalendar,
+    FormTemplate,
+    CalendarPrevDay,
+    CalendarNextDay,
+    CalendarPrevWeek,
+    CalendarNextWeek,
+    CalendarPrevMonth,
+    CalendarNextMonth,
+    CalendarToday,
+    CalendarPick,
+    CalendarClose,
+    ColumnsDown,
+    ColumnsUp,
+    ColumnsToggle,
+    ColumnsMoveDown,
+    ColumnsMoveUp,
+    ColumnsSort,
+    ColumnsClose,
+    TemplatesDown,
+    TemplatesUp,
+    TemplatesUse,
+    TemplatesEdit,
+    TemplatesDelete,
+    TemplatesClose,
+    ReviewDown,
+    ReviewUp,
+    ReviewShow,
+    ReviewClose,
+    DayPrev,
+    DayNext,
+    DayClose,
+    HelpDown,
+    HelpUp,
+    HelpClose,
+}
+
+struct ActionInfo {
+    name: &'static str,
+    context: KeyContext,
+    description: &'static str,
+    hint: &'static str,
+    defaults: &'static str,
+}
+
+impl Action {
+    pub fn all() -> &'static [Action] {
+        &[
+            Action::Quit,
+            Action::Help,
+            Action::CommandPalette,
+            Action::TabDashboard,
+            Action::TabExpenses,
+            Action::TabMonthly,
+            Action::TabYearly,
+            Action::TabHeatmap,
+            Action::TabBudgets,
+            Action::TabGoals,
+            Action::TabLoans,
+            Action::TabSubscriptions,
+            Action::NextTab,
+            Action::PrevTab,
+            Action::AddExpense,
+            Action::Templates,
+            Action::QuickAdd,
+            Action::CurrencyNext,
+            Action::CurrencyPrev,
+            Action::Export,
+            Action::Undo,
+            Action::CycleTheme,
+            Action::MoveDown,
+            Action::MoveUp,
+            Action::MoveTop,
+            Action::MoveBottom,
+            Action::Search,
+            Action::EditExpense,
+            Action::DeleteExpense,
+            Action::ToggleRecurringFilter,
+            Action::CycleSort,
+            Action::ReverseSort,
+            Action::EditColumns,
+            Action::ToggleMark,
+            Action::RangeSelect,
+            Action::SelectAll,
+            Action::ClearSelection,
+            Action::BulkActions,
+            Action::DuplicateExpense,
+            Action::SaveTemplate,
+            Action::ReviewAnomalies,
+            Action::PrevMonth,
+            Action::NextMonth,
+            Action::CurrentMonth,
+            Action::MonthlyCompare,
+            Action::PrevYear,
+            Action::NextYear,
+            Action::CurrentYear,
+            Action::HeatmapPrevWeek,
+            Action::HeatmapNextWeek,
+            Action::HeatmapPrevDay,
+            Action::HeatmapNextDay,
+            Action::HeatmapPrevYear,
+            Action::HeatmapNextYear,
+            Action::HeatmapToday,
+            Action::HeatmapShowDay,
+            Action::BudgetsDown,
+            Action::BudgetsUp,
+            Action::GoalsDown,
+            Action::GoalsUp,
+            Action::GoalsNew,
+            Action::GoalsContribute,
+            Action::LoansDown,
+            Action::LoansUp,
+            Action::LoansNew,
+            Action::SubscriptionsDown,
+            Action::SubscriptionsUp,
+            Action::SubscriptionsCancel,
+            Action::SubscriptionsPrice,
+            Action::SubscriptionsMark,
+            Action::FormNextField,
+            Action::FormPrevField,
+            Action::FormSave,
+            Action::FormCancel,
+            Action::FormCalendar,
+            Action::FormTemplate,
+            Action::CalendarPrevDay,
+            Action::CalendarNextDay,
+            Action::CalendarPrevWeek,
+            Action::CalendarNextWeek,
+            Action::CalendarPrevMonth,
+            Action::CalendarNextMonth,
+            Action::CalendarToday,
+            Action::CalendarPick,
+            Action::CalendarClose,
+            Action::ColumnsDown,
+            Action::ColumnsUp,
+            Action::ColumnsToggle,
+            Action::ColumnsMoveDown,
+            Action::ColumnsMoveUp,
+            Action::ColumnsSort,
+            Action::ColumnsClose,
+            Action::TemplatesDown,
+            Action::TemplatesUp,
+            Action::TemplatesUse,
+            Action::TemplatesEdit,
+            Action::TemplatesDelete,
+            Action::TemplatesClose,
+            Action::ReviewDown,
+            Action::ReviewUp,
+            Action::ReviewShow,
+            Action::ReviewClose,
+            Action::DayPrev,
+            Action::DayNext,
+            Action::DayClose,
+            Action::HelpDown,
+            Action::HelpUp,
+            Action::HelpClose,
+        ]
+    }
+
+    fn info(&self) -> ActionInfo {
+        use KeyContext::*;
+        let (name, context, description, hint, defaults) = match self {
+            Action::Quit => ("quit", Global, "Quit", "quit", "q, ctrl+c"),
+            Action::Help => ("help", Global, "Toggle this help", "help", "?"),
+            Action::CommandPalette => ("palette", Global, "Search and run any action", "commands", "ctrl+p"),
+            Action::TabDashboard => ("tab.dashboard", Global, "Dashboard tab", "dashboard", "1"),
+            Action::TabExpenses => ("tab.expenses", Global, "Expenses tab", "expenses", "2"),
+            Action::TabMonthly => ("tab.monthly", Global, "Monthly tab", "monthly", "3"),
+            Action::TabYearly => ("tab.yearly", Global, "Yearly tab", "yearly", "4"),
+            Action::TabHeatmap => ("tab.heatmap", Global, "Heatmap tab", "heatmap", "5"),
+            Action::TabBudgets => ("tab.budgets", Global, "Budgets tab", "budgets", "6"),
+            Action::TabGoals => ("tab.goals", Global, "Savings goals tab", "goals", "7"),
+            Action::TabLoans => ("tab.loans", Global, "Loans tab", "loans", "8"),
+            Action::TabSubscriptions => ("tab.subscriptions", Global, "Subscriptions tab", "subscriptions", "9"),
+            Action::NextTab => ("tab.next", Global, "Next tab", "next tab", "tab"),
+            Action::PrevTab => ("tab.prev", Global, "Previous tab", "prev tab", "shift+tab"),
+            Action::AddExpense => ("add", Global, "Add new expense", "add", "a"),
+            Action::Templates => ("templates", Global, "Quick-add from a template", "templates", "f"),
+            Action::QuickAdd => ("quickadd", Global, "Add from one line, e.g. 12.50 food lunch", "quick add", ":"),
+            Action::CurrencyNext => ("currency.next", Global, "Next currency", "currency", "c"),
+            Action::CurrencyPrev => ("currency.prev", Global, "Previous currency", "currency", "C"),
+            Action::Export => ("export", Global, "Export to CSV", "export", "x"),
+            Action::Undo => ("undo", Global, "Undo last change", "undo", "u"),
+            Action::CycleTheme => ("theme.next", Global, "Next color theme", "theme", "t"),
+            Action::MoveDown => ("expenses.down", Expenses, "Move down", "down", "j, down"),
+            Action::MoveUp => ("expenses.up", Expenses, "Move up", "up", "k, up"),
+            Action::MoveTop => ("expenses.top", Expenses, "Jump to first row", "top", "g g, home"),
+            Action::MoveBottom => ("expenses.bottom", Expenses, "Jump to last row", "bottom", "G, end"),
+            Action::Search => ("expenses.search", Expenses, "Search", "search", "/"),
+            Action::EditExpense => ("expenses.edit", Expenses, "Edit selected", "edit", "e"),
+            Action::DeleteExpense => ("expenses.delete", Expenses, "Delete selected", "delete", "d"),
+            Action::ToggleRecurringFilter => ("expenses.recurring", Expenses, "Toggle recurring filter", "recurring", "r"),
+            Action::CycleSort => ("expenses.sort", Expenses, "Cycle sort column", "sort", "s"),
+            Action::ReverseSort => ("expenses.reverse", Expenses, "Reverse sort direction", "reverse", "S"),
+            Action::EditColumns => ("expenses.columns", Expenses, "Show, hide and reorder columns", "columns", "v"),
+            Action::ToggleMark => ("expenses.mark", Expenses, "Mark / unmark row", "mark", "space"),
+            Action::RangeSelect => ("expenses.range", Expenses, "Start / finish range selection", "range", "V"),
+            Action::SelectAll => ("expenses.select_all", Expenses, "Mark all filtered rows", "all", "A"),
+            Action::ClearSelection => ("expenses.clear", Expenses, "Clear selection", "clear", "esc"),
+            Action::BulkActions => ("expenses.bulk", Expenses, "Bulk actions on marked rows", "bulk actions", "b"),
+            Action::DuplicateExpense => ("expenses.duplicate", Expenses, "Duplicate selected with today's date", "duplicate", "y"),
+            Action::SaveTemplate => ("expenses.template", Expenses, "Save selected as template", "save template", "T"),
+            Action::ReviewAnomalies => ("expenses.review", Expenses, "Review unusual spending", "unusual", "!"),
+            Action::PrevMonth => ("monthly.prev", Monthly, "Previous month", "prev", "left, h"),
+            Action::NextMonth => ("monthly.next", Monthly, "Next month", "next", "right, l"),
+            Action::CurrentMonth => ("monthly.current", Monthly, "Jump to current month", "this month", "home"),
+            Action::MonthlyCompare => ("monthly.compare", Monthly, "Compare with previous month / last year", "compare", "v"),
+            Action::PrevYear => ("yearly.prev", Yearly, "Previous year", "prev", "left, h"),
+            Action::NextYear => ("yearly.next", Yearly, "Next year", "next", "right, l"),
+            Action::CurrentYear => ("yearly.current", Yearly, "Jump to current year", "this year", "home"),
+            Action::HeatmapPrevWeek => ("heatmap.left", Heatmap, "Previous week", "week", "left, h"),
+            Action::HeatmapNextWeek => ("heatmap.right", Heatmap, "Next week", "week", "right, l"),
+            Action::HeatmapPrevDay => ("heatmap.up", Heatmap, "Previous day", "day", "up, k"),
+            Action::HeatmapNextDay => ("heatmap.down", Heatmap, "Next day", "day", "down, j"),
+            Action::HeatmapPrevYear => ("heatmap.prev_year", Heatmap, "Previous year", "year", "[, pageup"),
+            Action::HeatmapNextYear => ("heatmap.next_year", Heatmap, "Next year", "year", "], pagedown"),
+            Action::HeatmapToday => ("heatmap.today", Heatmap, "Jump to today", "today", "home"),
+            Action::HeatmapShowDay => ("heatmap.show", Heatmap, "Show the day's expenses", "day's expenses", "enter"),
+            Action::BudgetsDown => ("budgets.down", Budgets, "Next budget", "select", "down, j"),
+            Action::BudgetsUp => ("budgets.up", Budgets, "Previous budget", "select", "up, k"),
+            Action::GoalsDown => ("goals.down", Goals, "Next goal", "select", "down, j"),
+            Action::GoalsUp => ("goals.up", Goals, "Previous goal", "select", "up, k"),
+            Action::GoalsNew => ("goals.new", Goals, "New goal, e.g. Car 12000 @2027-12-31", "new goal", "n"),
+            Action::GoalsContribute => ("goals.contribute", Goals, "Add to or withdraw from the selected goal", "contribute", "enter, +"),
+            Action::LoansDown => ("loans.down", Loans, "Next loan", "select", "down, j"),
+            Action::LoansUp => ("loans.up", Loans, "Previous loan", "select", "up, k"),
+            Action::LoansNew => ("loans.new", Loans, "New loan, e.g. Car loan 18000 5.9% 60m", "new loan", "n"),
+            Action::SubscriptionsDown => ("subscriptions.down", Subscriptions, "Next subscription", "select", "down, j"),
+            Action::SubscriptionsUp => ("subscriptions.up", Subscriptions, "Previous subscription", "select", "up, k"),
+            Action::SubscriptionsCancel => ("subscriptions.cancel", Subscriptions, "Cancel the selected subscription from a date", "cancel", "d"),
+            Action::SubscriptionsPrice => ("subscriptions.price", Subscriptions, "Change the selected subscription's price from its next charge", "price", "p"),
+            Action::SubscriptionsMark => ("subscriptions.mark", Subscriptions, "Mark a detected repeating charge as a subscription", "mark", "m"),
+            Action::FormNextField => ("form.next", Form, "Next field", "next", "tab"),
+            Action::FormPrevField => ("form.prev", Form, "Previous field", "prev", "shift+tab"),
+            Action::FormSave => ("form.save", Form, "Save", "save", "enter"),
+            Action::FormCancel => ("form.cancel", Form, "Cancel", "cancel", "esc"),
+            Action::FormCalendar => ("form.calendar", Form, "Pick date from calendar", "calendar", "ctrl+d"),
+            Action::FormTemplate => ("form.template", Form, "Save the form as a template", "template", "ctrl+t"),
+            Action::CalendarPrevDay => ("calendar.prev_day", Calendar, "Previous day", "day", "left, h, -"),
+            Action::CalendarNextDay => ("calendar.next_day", Calendar, "Next day", "day", "right, l, +"),
+            Action::CalendarPrevWeek => ("calendar.prev_week", Calendar, "Previous week", "week", "up, k"),
+            Action::CalendarNextWeek => ("calendar.next_week", Calendar, "Next week", "week", "down, j"),
+            Action::CalendarPrevMonth => ("calendar.prev_month", Calendar, "Previous month", "month", "pageup, <"),
+            Action::CalendarNextMonth => ("calendar.next_month", Calendar, "Next month", "month", "pagedown, >"),
+            Action::CalendarToday => ("calendar.today", Calendar, "Jump to today", "today", "t, home"),
+            Action::CalendarPick => ("calendar.pick", Calendar, "Pick the highlighted day", "pick", "enter"),
+            Action::CalendarClose => ("calendar.close", Calendar, "Close without picking", "close", "esc"),
+            Action::ColumnsDown => ("columns.down", Columns, "Next column", "select", "down, j"),
+            Action::ColumnsUp => ("columns.up", Columns, "Previous column", "select", "up, k"),
+            Action::ColumnsToggle => ("columns.toggle", Columns, "Show / hide the selected column", "show/hide", "space"),
+            Action::ColumnsMoveDown => ("columns.move_down", Columns, "Move the selected column down", "move down", "J"),
+            Action::ColumnsMoveUp => ("columns.move_up", Columns, "Move the selected column up", "move up", "K"),
+            Action::ColumnsSort => ("columns.sort", Columns, "Sort by the selected column", "sort", "s"),
+            Action::ColumnsClose => ("columns.close", Columns, "Close", "close", "esc, enter, v"),
+            Action::TemplatesDown => ("templates.down", Templates, "Next template", "select", "down, j"),
+            Action::TemplatesUp => ("templates.up", Templates, "Previous template", "select", "up, k"),
+            Action::TemplatesUse => ("templates.use", Templates, "Add the selected template today", "add today", "enter"),
+            Action::TemplatesEdit => ("templates.edit", Templates, "Open the selected template in the add form", "edit first", "e"),
+            Action::TemplatesDelete => ("templates.delete", Templates, "Delete the selected template", "delete", "d"),
+            Action::TemplatesClose => ("templates.close", Templates, "Close", "close", "esc"),
+            Action::ReviewDown => ("review.down", Review, "Next flagged expense", "select", "down, j"),
+            Action::ReviewUp => ("review.up", Review, "Previous flagged expense", "select", "up, k"),
+            Action::ReviewShow => ("review.show", Review, "Show the expense in the table", "show in table", "enter"),
+            Action::ReviewClose => ("review.close", Review, "Close", "close", "esc, q"),
+            Action::DayPrev => ("day.prev", DayDetail, "Previous day", "day", "left, h, up, k"),
+            Action::DayNext => ("day.next", DayDetail, "Next day", "day", "right, l, down, j"),
+            Action::DayClose => ("day.close", DayDetail, "Close", "close", "esc, enter, q"),
+            Action::HelpDown => ("help.down", Help, "Scroll down", "scroll", "down, j"),
+            Action::HelpUp => ("help.up", Help, "Scroll up", "scroll", "up, k"),
+            Action::HelpClose => ("help.close", Help, "Close", "close", "esc"),
+        };
+        ActionInfo {
+            name,
+            context,
+            description,
+            hint,
+            defaults,
+        }
+    }
+
+    /// Name used in the keymap file, e.g. `expenses.delete`.
+    pub fn name(&self) -> &'static str {
+        self.info().name
+    }
+
+    pub fn context(&self) -> KeyContext {
+        self.info().context
+    }
+
+    pub fn description(&self) -> &'static str {
+        self.info().description
+    }
+
+    /// Short label for status bar hints.
+    pub fn hint(&self) -> &'static str {
+        self.info().hint
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().iter().find(|a| a.name() == name).copied()
+    }
+}
+
+/// A single key press with its modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    /// Normalize a key event: shift is already part of characters and
+    /// `BackTab`, so it is dropped there to make `G` and `shift+g` the same.
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        if matches!(code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self { code, modifiers }
+    }
+
+    fn parse(s: &str) -> Result<Self, String> {
+        let (mods, key) = match s.rfind('+') {
+            Some(i) if i + 1 < s.len() => (&s[..i], &s[i + 1..]),
+            _ => ("", s),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for m in mods.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match m.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                other => return Err(format!("unknown modifier '{}'", other)),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "comma" => KeyCode::Char(','),
+                "plus" => KeyCode::Char('+'),
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key '{}'", key)),
+                },
+            },
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+
+    /// Whether typing this chord into a text field would insert a character.
+    fn is_text(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_))
+            && !self
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Ins"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            _ => write!(f, "?"),
+        }
+    }
+}
+
+/// One or more chords pressed in order, e.g. `g g`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySequence(pub Vec<KeyChord>);
+
+impl KeySequence {
+    fn parse(s: &str) -> Result<Self, String> {
+        let chords = s
+            .split_whitespace()
+            .map(KeyChord::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if chords.is_empty() {
+            return Err("empty key sequence".to_string());
+        }
+        Ok(Self(chords))
+    }
+
+    fn starts_with(&self, prefix: &[KeyChord]) -> bool {
+        self.0.starts_with(prefix)
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.0.iter().map(|c| c.to_string()).collect();
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+/// Result of feeding the keys pressed so far to the keymap.
+pub enum Resolution {
+    Action(Action),
+    Pending,
+    NoMatch,
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(Action, Vec<KeySequence>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = Action::all()
+            .iter()
+            .map(|action| {
+                let keys = parse_key_list(action.info().defaults)
+                    .unwrap_or_else(|e| panic!("bad default keys for {}: {}", action.name(), e));
+                (*action, keys)
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Build the keymap from a `keymap` file. Each line is
+    /// `action = keys, keys`; listed actions replace their default keys.
+    /// Any error or conflict rejects the file so that a typo can never
+    /// leave an action unreachable; the problems are returned instead.
+    pub fn from_config(content: &str) -> Result<Self, Vec<String>> {
+        let mut keymap = Keymap::default();
+        let mut errors = Vec::new();
+
+        for (n, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((name, keys)) = line.split_once('=') else {
+                errors.push(format!("line {}: expected 'action = keys'", n + 1));
+                continue;
+            };
+            let name = name.trim();
+            let Some(action) = Action::from_name(name) else {
+                errors.push(format!("line {}: unknown action '{}'", n + 1, name));
+                continue;
+            };
+            match parse_key_list(keys) {
+                Ok(sequences) => keymap.set(action, sequences),
+                Err(e) => errors.push(format!("line {}: {}", n + 1, e)),
+            }
+        }
+
+        errors.extend(keymap.conflicts());
+        if errors.is_empty() {
+            Ok(keymap)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn set(&mut self, action: Action, keys: Vec<KeySequence>) {
+        if let Some(entry) = self.bindings.iter_mut().find(|(a, _)| *a == action) {
+            entry.1 = keys;
+        }
+    }
+
+    pub fn keys(&self, action: Action) -> &[KeySequence] {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// All keys for `action` for display, e.g. `j/↓`.
+    pub fn display(&self, action: Action) -> String {
+        let keys: Vec<String> = self.keys(action).iter().map(|k| k.to_string()).collect();
+        if keys.is_empty() {
+            "(unbound)".to_string()
+        } else {
+            keys.join("/")
+        }
+    }
+
+    /// The first key for `action`, for compact hints.
+    pub fn primary(&self, action: Action) -> String {
+        self.keys(action)
+            .first()
+            .map(|k| k.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    /// `key:hint` for the status bar and popup footers.
+    pub fn hint(&self, action: Action) -> String {
+        format!("{}:{}", self.primary(action), action.hint())
+    }
+
+    pub fn resolve(&self, pressed: &[KeyChord], contexts: &[KeyContext]) -> Resolution {
+        let mut pending = false;
+        for (action, sequences) in &self.bindings {
+            if !contexts.contains(&action.context()) {
+                continue;
+            }
+            for seq in sequences {
+                if seq.0 == pressed {
+                    return Resolution::Action(*action);
+                }
+                if seq.starts_with(pressed) {
+                    pending = true;
+                }
+            }
+        }
+        if pending {
+            Resolution::Pending
+        } else {
+            Resolution::NoMatch
+        }
+    }
+
+    /// Bindings that can never fire or would swallow typed text.
+    fn conflicts(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        for (i, (a, a_keys)) in self.bindings.iter().enumerate() {
+            for seq in a_keys {
+                if a.context() == KeyContext::Form && (seq.0.len() > 1 || seq.0[0].is_text()) {
+                    problems.push(format!(
+                        "{} = {}: form keys must be a single non-character key or use ctrl/alt",
+                        a.name(),
+                        seq
+                    ));
+                }
+            }
+
+            for (b, b_keys) in self.bindings.iter().skip(i + 1) {
+                if !a.context().overlaps(b.context()) {
+                    continue;
+                }
+                for x in a_keys {
+                    for y in b_keys {
+                        if x == y {
+                            problems.push(format!(
+                                "'{}' is bound to both {} and {}",
+                                x,
+                                a.name(),
+                                b.name()
+                            ));
+                        } else if y.starts_with(&x.0) || x.starts_with(&y.0) {
+                            let (short, long, sa, la) = if x.0.len() < y.0.len() {
+                                (x, y, a, b)
+                            } else {
+                                (y, x, b, a)
+                            };
+                            problems.push(format!(
+                                "'{}' ({}) blocks '{}' ({})",
+                                short,
+                                sa.name(),
+                                long,
+                                la.name()
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        problems
+    }
+}
+
+fn parse_key_list(s: &str) -> Result<Vec<KeySequence>, String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .map(KeySequence::parse)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_keymap_has_no_conflicts() {
+        assert_eq!(Keymap::default().conflicts(), Vec::<String>::new());
+    }
+}
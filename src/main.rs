@@ -1,22 +1,40 @@
+mod anomaly;
 mod app;
+mod forecast;
+mod keymap;
 mod model;
+mod prompts;
+mod query;
+mod quick_add;
 mod storage;
+mod subscriptions;
+mod text_input;
+mod theme;
 mod ui;
 mod utils;
 
 use anyhow::Result;
+use chrono::{Datelike, Days, Months};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste,
+        EnableMouseCapture, Event, KeyCode, KeyModifiers, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::CrosstermBackend, layout::Rect, Terminal};
 use std::env;
 use std::io;
 use std::time::Duration;
 
-use app::{App, FormField, FormState, InputMode, Tab};
+use app::{
+    App, BulkActionKind, BulkState, FormField, FormState, GoalPrompt, InputMode, PaletteEntry, Tab,
+};
+use keymap::{Action, KeyChord, KeyContext, Keymap, Resolution};
 use model::{Category, Recurrence};
+use text_input::TextInput;
 
 fn main() -> Result<()> {
     let args: Vec<String> = env::args().collect();
@@ -27,6 +45,16 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if args.iter().any(|a| a == "--check-keymap") {
+        check_keymap()?;
+        return Ok(());
+    }
+
+    // Handle `add <entry>` without launching the TUI
+    if args.get(1).map(String::as_str) == Some("add") {
+        return add_from_command_line(&args[2..]);
+    }
+
     // Handle --import <file>
     let import_path = parse_import_arg(&args);
     let import_only = args.iter().any(|a| a == "--import-only");
@@ -43,14 +71,18 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    let mut app = App::new()?;
+    let mouse = app.mouse_enabled && !args.iter().any(|a| a == "--no-mouse");
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
+    if mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new()?;
-
     if let Some(path) = import_path {
         match app.import_from_csv(&path) {
             Ok(count) => {
@@ -65,11 +97,10 @@ fn main() -> Result<()> {
     let res = run_app(&mut terminal, &mut app);
 
     disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    if mouse {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
+    execute!(terminal.backend_mut(), DisableBracketedPaste, LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
     if let Err(err) = res {
@@ -87,18 +118,52 @@ fn run_app(
         terminal.draw(|f| ui::draw(f, app))?;
 
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let Event::Paste(text) = &event {
+                handle_paste(app, text);
+            }
+            if let Event::Mouse(mouse) = event {
+                let size = terminal.size()?;
+                handle_mouse_input(app, mouse, Rect::new(0, 0, size.width, size.height));
+            }
+            if let Event::Key(key) = event {
                 // Clear status message on any keypress
                 app.status_message = None;
 
                 match app.input_mode {
                     InputMode::Normal => handle_normal_input(app, key.code, key.modifiers),
-                    InputMode::Search => handle_search_input(app, key.code),
+                    InputMode::Search => handle_search_input(app, key.code, key.modifiers),
                     InputMode::AddForm | InputMode::EditForm => {
                         handle_form_input(app, key.code, key.modifiers)
                     }
-                    InputMode::HelpPopup => handle_help_input(app, key.code),
+                    InputMode::HelpPopup => handle_help_input(app, key.code, key.modifiers),
                     InputMode::ConfirmDelete => handle_confirm_delete(app, key.code),
+                    InputMode::ColumnEditor => {
+                        handle_column_editor_input(app, key.code, key.modifiers)
+                    }
+                    InputMode::BulkMenu => handle_bulk_menu_input(app, key.code),
+                    InputMode::BulkInput => handle_bulk_input(app, key.code, key.modifiers),
+                    InputMode::ConfirmBulk => handle_confirm_bulk(app, key.code),
+                    InputMode::TemplatePicker => {
+                        handle_template_picker_input(app, key.code, key.modifiers)
+                    }
+                    InputMode::QuickAdd => handle_quick_add_input(app, key.code, key.modifiers),
+                    InputMode::CommandPalette => {
+                        handle_palette_input(app, key.code, key.modifiers)
+                    }
+                    InputMode::DayDetail => handle_day_detail_input(app, key.code, key.modifiers),
+                    InputMode::BudgetAlert => handle_budget_alert_input(app, key.code),
+                    InputMode::GoalPrompt => handle_goal_prompt_input(app, key.code, key.modifiers),
+                    InputMode::LoanPrompt => handle_loan_prompt_input(app, key.code, key.modifiers),
+                    InputMode::CancelSubscription => {
+                        handle_cancel_subscription_input(app, key.code, key.modifiers)
+                    }
+                    InputMode::SubscriptionPrice => {
+                        handle_subscription_price_input(app, key.code, key.modifiers)
+                    }
+                    InputMode::AnomalyReview => {
+                        handle_anomaly_review_input(app, key.code, key.modifiers)
+                    }
                 }
             }
         }
@@ -110,58 +175,100 @@ fn run_app(
 }
 
 fn handle_normal_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
-    match key {
-        KeyCode::Char('q') => app.running = false,
-        KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => app.running = false,
-        KeyCode::Char('?') => app.input_mode = InputMode::HelpPopup,
+    app.pending_keys.push(KeyChord::new(key, modifiers));
+    let contexts = app.normal_contexts();
+    match app.keymap.resolve(&app.pending_keys, &contexts) {
+        Resolution::Action(action) => {
+            app.pending_keys.clear();
+            run_action(app, action);
+        }
+        Resolution::Pending => {}
+        Resolution::NoMatch => {
+            // A broken-off sequence: try the last key on its own.
+            let retry = app.pending_keys.len() > 1;
+            app.pending_keys.clear();
+            if retry {
+                handle_normal_input(app, key, modifiers);
+            }
+        }
+    }
+}
+
+/// Perform a keymap action. Actions scoped to a tab are only resolved while
+/// that tab is active, so they don't re-check it here.
+fn run_action(app: &mut App, action: Action) {
+    match action {
+        Action::Quit => app.running = false,
+        Action::Help => {
+            app.help_scroll = 0;
+            app.input_mode = InputMode::HelpPopup;
+        }
 
         // Tab switching
-        KeyCode::Char('1') => app.active_tab = Tab::Dashboard,
-        KeyCode::Char('2') => app.active_tab = Tab::Expenses,
-        KeyCode::Char('3') => app.active_tab = Tab::Monthly,
-        KeyCode::Tab => {
-            let next = (app.active_tab.index() + 1) % 3;
+        Action::TabDashboard => app.active_tab = Tab::Dashboard,
+        Action::TabExpenses => app.active_tab = Tab::Expenses,
+        Action::TabMonthly => app.active_tab = Tab::Monthly,
+        Action::TabYearly => app.active_tab = Tab::Yearly,
+        Action::TabHeatmap => app.active_tab = Tab::Heatmap,
+        Action::TabBudgets => app.active_tab = Tab::Budgets,
+        Action::TabGoals => app.active_tab = Tab::Goals,
+        Action::TabLoans => app.active_tab = Tab::Loans,
+        Action::TabSubscriptions => app.active_tab = Tab::Subscriptions,
+        Action::NextTab => {
+            let next = (app.active_tab.index() + 1) % Tab::titles().len();
             app.active_tab = Tab::from_index(next);
         }
-        KeyCode::BackTab => {
+        Action::PrevTab => {
             let prev = if app.active_tab.index() == 0 {
-                2
+                Tab::titles().len() - 1
             } else {
                 app.active_tab.index() - 1
             };
             app.active_tab = Tab::from_index(prev);
         }
 
-        // Add expense
-        KeyCode::Char('a') => {
+        Action::AddExpense => {
             app.form = FormState::default();
             app.input_mode = InputMode::AddForm;
         }
-
-        // Cycle currency
-        KeyCode::Char('c') => {
-            app.cycle_currency_forward();
+        Action::CommandPalette => {
+            app.palette_query.clear();
+            app.palette_index = 0;
+            app.input_mode = InputMode::CommandPalette;
         }
-        KeyCode::Char('C') => {
-            app.cycle_currency_backward();
+        Action::QuickAdd => {
+            app.quick_add.clear();
+            app.input_mode = InputMode::QuickAdd;
         }
-
-        // Export
-        KeyCode::Char('x') => {
-            match app.export() {
-                Ok(_) => {}
-                Err(e) => app.status_message = Some(format!("Export failed: {}", e)),
+        Action::Templates => {
+            if app.templates.is_empty() {
+                app.status_message = Some(format!(
+                    "No templates yet: select an expense and press {} to save one",
+                    app.keymap.primary(Action::SaveTemplate)
+                ));
+            } else {
+                app.template_index = app.template_index.min(app.templates.len() - 1);
+                app.input_mode = InputMode::TemplatePicker;
+            }
+        }
+        Action::CurrencyNext => app.cycle_currency_forward(),
+        Action::CurrencyPrev => app.cycle_currency_backward(),
+        Action::Undo => app.undo(),
+        Action::CycleTheme => app.cycle_theme(),
+        Action::Export => {
+            if let Err(e) = app.export() {
+                app.status_message = Some(format!("Export failed: {}", e));
             }
         }
 
-        // Expenses tab specific
-        KeyCode::Char('j') | KeyCode::Down if app.active_tab == Tab::Expenses => {
+        // Expenses tab
+        Action::MoveDown => {
             if !app.filtered_indices.is_empty() {
                 app.expense_table_index =
                     (app.expense_table_index + 1) % app.filtered_indices.len();
             }
         }
-        KeyCode::Char('k') | KeyCode::Up if app.active_tab == Tab::Expenses => {
+        Action::MoveUp => {
             if !app.filtered_indices.is_empty() {
                 app.expense_table_index = if app.expense_table_index == 0 {
                     app.filtered_indices.len() - 1
@@ -170,38 +277,279 @@ fn handle_normal_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
                 };
             }
         }
-        KeyCode::Char('/') if app.active_tab == Tab::Expenses => {
-            app.input_mode = InputMode::Search;
+        Action::MoveTop => app.expense_table_index = 0,
+        Action::MoveBottom => {
+            app.expense_table_index = app.filtered_indices.len().saturating_sub(1);
         }
-        KeyCode::Char('e') if app.active_tab == Tab::Expenses => {
+        Action::Search => app.input_mode = InputMode::Search,
+        Action::EditExpense => {
             if let Some(expense) = app.selected_expense() {
                 app.form = FormState::from_expense(expense);
                 app.input_mode = InputMode::EditForm;
             }
         }
-        KeyCode::Char('d') if app.active_tab == Tab::Expenses => {
+        Action::DuplicateExpense => {
+            if let Some(expense) = app.selected_expense() {
+                app.form = FormState::duplicate(expense);
+                app.input_mode = InputMode::AddForm;
+            }
+        }
+        Action::SaveTemplate => {
+            app.status_message = Some(match app.save_selected_as_template() {
+                Ok(msg) => msg,
+                Err(e) => format!("Could not save template: {}", e),
+            });
+        }
+        Action::ReviewAnomalies => {
+            let report = &app.anomalies;
+            if report.flags.is_empty() && report.spikes.is_empty() {
+                app.status_message = Some("Nothing unusual found".to_string());
+            } else {
+                app.anomaly_index = 0;
+                app.input_mode = InputMode::AnomalyReview;
+            }
+        }
+        Action::DeleteExpense => {
             if app.selected_expense().is_some() {
                 app.input_mode = InputMode::ConfirmDelete;
             }
         }
-        KeyCode::Char('r') if app.active_tab == Tab::Expenses => {
+        Action::ToggleRecurringFilter => {
             app.show_recurring_only = !app.show_recurring_only;
             app.update_filtered_indices();
         }
+        Action::CycleSort => app.cycle_sort_column(),
+        Action::ReverseSort => app.toggle_sort_direction(),
+        Action::EditColumns => {
+            app.column_editor_index = 0;
+            app.input_mode = InputMode::ColumnEditor;
+        }
+        Action::ToggleMark => app.toggle_mark_selected(),
+        Action::RangeSelect => app.toggle_range_select(),
+        Action::SelectAll => app.toggle_select_all_filtered(),
+        Action::ClearSelection => app.clear_selection(),
+        Action::BulkActions => {
+            if app.selected_ids.is_empty() {
+                app.status_message = Some(format!(
+                    "Mark expenses with {}, {} or {} first",
+                    app.keymap.primary(Action::ToggleMark),
+                    app.keymap.primary(Action::RangeSelect),
+                    app.keymap.primary(Action::SelectAll)
+                ));
+            } else {
+                app.bulk = BulkState::default();
+                app.input_mode = InputMode::BulkMenu;
+            }
+        }
 
-        // Monthly tab specific
-        KeyCode::Left | KeyCode::Char('h') if app.active_tab == Tab::Monthly => {
-            app.prev_month();
+        // Monthly tab
+        Action::PrevMonth => app.prev_month(),
+        Action::NextMonth => app.next_month(),
+        Action::CurrentMonth => app.current_month(),
+        Action::MonthlyCompare => {
+            app.monthly_view = app.monthly_view.next();
+            app.status_message = Some(format!("Monthly view: {}", app.monthly_view.label()));
         }
-        KeyCode::Right | KeyCode::Char('l') if app.active_tab == Tab::Monthly => {
-            app.next_month();
+        Action::PrevYear => app.overview_year -= 1,
+        Action::NextYear => app.overview_year += 1,
+        Action::CurrentYear => app.overview_year = utils::today().year(),
+
+        // Heatmap tab
+        Action::HeatmapPrevWeek => app.move_heatmap_day(-7),
+        Action::HeatmapNextWeek => app.move_heatmap_day(7),
+        Action::HeatmapPrevDay => app.move_heatmap_day(-1),
+        Action::HeatmapNextDay => app.move_heatmap_day(1),
+        Action::HeatmapPrevYear => app.shift_heatmap_year(-1),
+        Action::HeatmapNextYear => app.shift_heatmap_year(1),
+        Action::HeatmapToday => app.heatmap_day = utils::today(),
+        Action::HeatmapShowDay => app.input_mode = InputMode::DayDetail,
+
+        // Budgets tab
+        Action::BudgetsDown => app.move_budget_selection(1),
+        Action::BudgetsUp => app.move_budget_selection(-1),
+
+        // Goals tab
+        Action::GoalsDown => app.move_goal_selection(1),
+        Action::GoalsUp => app.move_goal_selection(-1),
+        Action::GoalsNew => {
+            app.goal_input.clear();
+            app.goal_prompt = GoalPrompt::NewGoal;
+            app.input_mode = InputMode::GoalPrompt;
+        }
+        Action::GoalsContribute => {
+            if app.goals.is_empty() {
+                app.status_message = Some("No goals yet".to_string());
+            } else {
+                app.goal_input.clear();
+                app.goal_prompt = GoalPrompt::Contribute;
+                app.input_mode = InputMode::GoalPrompt;
+            }
         }
 
-        _ => {}
+        // Loans tab
+        Action::LoansDown => app.move_loan_selection(1),
+        Action::LoansUp => app.move_loan_selection(-1),
+        Action::LoansNew => {
+            app.loan_input.clear();
+            app.input_mode = InputMode::LoanPrompt;
+        }
+
+        // Subscriptions tab
+        Action::SubscriptionsDown => app.move_subscription_selection(1),
+        Action::SubscriptionsUp => app.move_subscription_selection(-1),
+        Action::SubscriptionsCancel => match app.selected_subscription() {
+            Some(subscription) if subscription.cancelled.is_none() => {
+                app.subscription_input = TextInput::new("today");
+                app.input_mode = InputMode::CancelSubscription;
+            }
+            Some(subscription) => {
+                app.status_message = Some(format!("{} is already cancelled", subscription.name));
+            }
+            None => app.status_message = Some("Select a subscription to cancel".to_string()),
+        },
+        Action::SubscriptionsPrice => match app.selected_subscription() {
+            Some(subscription) if subscription.next_charge.is_some() => {
+                app.subscription_input = TextInput::new(format!("{:.2}", subscription.amount));
+                app.input_mode = InputMode::SubscriptionPrice;
+            }
+            Some(subscription) => {
+                app.status_message = Some(format!("{} has no charges left", subscription.name));
+            }
+            None => app.status_message = Some("Select a subscription to change".to_string()),
+        },
+        Action::SubscriptionsMark => {
+            app.status_message = Some(match app.mark_candidate() {
+                Ok(msg) => msg,
+                Err(e) => format!("Could not save: {}", e),
+            });
+        }
+
+        // Form
+        Action::FormNextField => app.form.active_field = app.form.active_field.next(),
+        Action::FormPrevField => app.form.active_field = app.form.active_field.prev(),
+        Action::FormCancel => app.input_mode = InputMode::Normal,
+        Action::FormSave => save_form(app),
+        Action::FormCalendar => app.form.open_calendar(),
+        Action::FormTemplate => match app.form.to_template() {
+            Ok(template) => {
+                app.status_message = Some(match app.save_template(template) {
+                    Ok(msg) => msg,
+                    Err(e) => format!("Could not save template: {}", e),
+                });
+                app.input_mode = InputMode::Normal;
+            }
+            Err(e) => app.status_message = Some(e),
+        },
+
+        // Form calendar
+        Action::CalendarPrevDay => app.form.move_calendar(|d| d.pred_opt()),
+        Action::CalendarNextDay => app.form.move_calendar(|d| d.succ_opt()),
+        Action::CalendarPrevWeek => app.form.move_calendar(|d| d.checked_sub_days(Days::new(7))),
+        Action::CalendarNextWeek => app.form.move_calendar(|d| d.checked_add_days(Days::new(7))),
+        Action::CalendarPrevMonth => {
+            app.form.move_calendar(|d| d.checked_sub_months(Months::new(1)))
+        }
+        Action::CalendarNextMonth => {
+            app.form.move_calendar(|d| d.checked_add_months(Months::new(1)))
+        }
+        Action::CalendarToday => app.form.move_calendar(|_| Some(utils::today())),
+        Action::CalendarPick => app.form.pick_calendar_date(),
+        Action::CalendarClose => app.form.calendar = None,
+
+        // Column editor
+        Action::ColumnsDown | Action::ColumnsUp => {
+            let count = app.column_editor_entries().len();
+            let down = action == Action::ColumnsDown;
+            app.column_editor_index = step(app.column_editor_index, count, down);
+        }
+        Action::ColumnsToggle => app.toggle_column_at(app.column_editor_index),
+        Action::ColumnsMoveDown => app.move_column_at(app.column_editor_index, 1),
+        Action::ColumnsMoveUp => app.move_column_at(app.column_editor_index, -1),
+        Action::ColumnsSort => {
+            if let Some((column, _)) = app.column_editor_entries().get(app.column_editor_index) {
+                app.sort_by_column(*column);
+            }
+        }
+        Action::ColumnsClose => app.input_mode = InputMode::Normal,
+
+        // Template picker
+        Action::TemplatesDown | Action::TemplatesUp => {
+            let down = action == Action::TemplatesDown;
+            app.template_index = step(app.template_index, app.templates.len(), down);
+        }
+        Action::TemplatesUse => use_template(app, app.template_index, false),
+        Action::TemplatesEdit => use_template(app, app.template_index, true),
+        Action::TemplatesDelete => {
+            app.status_message = Some(match app.delete_template(app.template_index) {
+                Ok(msg) => msg,
+                Err(e) => format!("Could not delete template: {}", e),
+            });
+            if app.templates.is_empty() {
+                app.input_mode = InputMode::Normal;
+            }
+        }
+        Action::TemplatesClose => app.input_mode = InputMode::Normal,
+
+        // Unusual spending review
+        Action::ReviewDown | Action::ReviewUp => {
+            let count = app.anomalies.flagged_ids().len();
+            let down = action == Action::ReviewDown;
+            app.anomaly_index = step(app.anomaly_index, count, down);
+        }
+        Action::ReviewShow => {
+            let ids = app.anomalies.flagged_ids();
+            if let Some(&id) = ids.get(app.anomaly_index.min(ids.len().saturating_sub(1))) {
+                app.jump_to_expense(id);
+            }
+            app.input_mode = InputMode::Normal;
+        }
+        Action::ReviewClose => app.input_mode = InputMode::Normal,
+
+        // Heatmap day details
+        Action::DayPrev => app.move_heatmap_day(-1),
+        Action::DayNext => app.move_heatmap_day(1),
+        Action::DayClose => app.input_mode = InputMode::Normal,
+
+        // Help
+        Action::HelpDown => app.help_scroll = app.help_scroll.saturating_add(1),
+        Action::HelpUp => app.help_scroll = app.help_scroll.saturating_sub(1),
+        Action::HelpClose => app.input_mode = InputMode::Normal,
+    }
+}
+
+/// `index` moved one row down or up a list of `count` rows, wrapping around
+/// at either end.
+fn step(index: usize, count: usize, down: bool) -> usize {
+    if count == 0 {
+        return 0;
+    }
+    let index = index.min(count - 1);
+    if down {
+        (index + 1) % count
+    } else if index == 0 {
+        count - 1
+    } else {
+        index - 1
     }
 }
 
-fn handle_search_input(app: &mut App, key: KeyCode) {
+/// Run the action `key` is bound to in a popup's `context`, if any.
+fn run_popup_action(
+    app: &mut App,
+    key: KeyCode,
+    modifiers: KeyModifiers,
+    context: KeyContext,
+) -> bool {
+    match app.keymap.resolve(&[KeyChord::new(key, modifiers)], &[context]) {
+        Resolution::Action(action) => {
+            run_action(app, action);
+            true
+        }
+        _ => false,
+    }
+}
+
+fn handle_search_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
     match key {
         KeyCode::Esc => {
             app.input_mode = InputMode::Normal;
@@ -209,100 +557,214 @@ fn handle_search_input(app: &mut App, key: KeyCode) {
         KeyCode::Enter => {
             app.input_mode = InputMode::Normal;
         }
-        KeyCode::Backspace => {
-            app.search_query.pop();
-            app.update_filtered_indices();
-        }
-        KeyCode::Char(c) => {
-            app.search_query.push(c);
-            app.update_filtered_indices();
+        _ => {
+            if app.search_query.handle_key(key, modifiers, |_| true) {
+                app.update_search_query();
+            }
         }
-        _ => {}
     }
 }
 
-fn handle_form_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+fn handle_palette_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    let count = app.palette_entries().len();
+    let ctrl = modifiers.contains(KeyModifiers::CONTROL);
     match key {
-        KeyCode::Esc => {
-            app.input_mode = InputMode::Normal;
+        KeyCode::Esc => app.input_mode = InputMode::Normal,
+        KeyCode::Enter => {
+            match app.palette_entries().get(app.palette_index).copied() {
+                Some(PaletteEntry::Action(action)) => {
+                    app.input_mode = InputMode::Normal;
+                    run_palette_action(app, action);
+                }
+                Some(PaletteEntry::GoToMonth(month)) => {
+                    app.input_mode = InputMode::Normal;
+                    app.go_to_month(month);
+                }
+                None => {}
+            }
         }
-        KeyCode::Tab => {
-            app.form.active_field = app.form.active_field.next();
+        KeyCode::Down | KeyCode::Tab => {
+            app.palette_index = (app.palette_index + 1).min(count.saturating_sub(1));
         }
-        KeyCode::BackTab => {
-            app.form.active_field = app.form.active_field.prev();
+        KeyCode::Char('n') if ctrl => {
+            app.palette_index = (app.palette_index + 1).min(count.saturating_sub(1));
         }
-        KeyCode::Enter => {
-            let id = app.form.editing_id.unwrap_or_else(|| app.next_id());
-            if let Some(expense) = app.form.to_expense(id) {
-                if app.input_mode == InputMode::EditForm {
-                    if let Some(edit_id) = app.form.editing_id {
-                        app.update_expense(edit_id, expense);
-                        app.status_message = Some("Expense updated".to_string());
-                    }
-                } else {
-                    app.add_expense(expense);
-                    app.status_message = Some("Expense added".to_string());
-                }
-                app.input_mode = InputMode::Normal;
-            } else {
-                app.status_message = Some("Invalid form data. Check fields.".to_string());
+        KeyCode::Up | KeyCode::BackTab => app.palette_index = app.palette_index.saturating_sub(1),
+        KeyCode::Char('p') if ctrl => app.palette_index = app.palette_index.saturating_sub(1),
+        _ => {
+            if app.palette_query.handle_key(key, modifiers, |_| true) {
+                app.palette_index = 0;
             }
         }
-        _ => handle_field_input(app, key, modifiers),
     }
 }
 
-fn handle_field_input(app: &mut App, key: KeyCode, _modifiers: KeyModifiers) {
-    match app.form.active_field {
-        FormField::Amount => match key {
-            KeyCode::Char(c) if c.is_ascii_digit() || c == '.' => {
-                app.form.amount_input.push(c);
-            }
-            KeyCode::Backspace => {
-                app.form.amount_input.pop();
+/// Run an action picked in the palette, first switching to the tab it
+/// belongs to so its effect is visible.
+fn run_palette_action(app: &mut App, action: Action) {
+    match action.context() {
+        KeyContext::Expenses => app.active_tab = Tab::Expenses,
+        KeyContext::Monthly => app.active_tab = Tab::Monthly,
+        KeyContext::Yearly => app.active_tab = Tab::Yearly,
+        KeyContext::Heatmap => app.active_tab = Tab::Heatmap,
+        KeyContext::Budgets => app.active_tab = Tab::Budgets,
+        KeyContext::Goals => app.active_tab = Tab::Goals,
+        KeyContext::Loans => app.active_tab = Tab::Loans,
+        KeyContext::Subscriptions => app.active_tab = Tab::Subscriptions,
+        _ => {}
+    }
+    run_action(app, action);
+}
+
+fn handle_quick_add_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    match key {
+        KeyCode::Esc => app.input_mode = InputMode::Normal,
+        KeyCode::Enter => match app.quick_add_preview() {
+            Ok(expense) => {
+                let message = format!("Added {}", describe_expense(app, &expense));
+                app.add_expense(expense);
+                app.status_message = Some(message);
+                app.input_mode = InputMode::Normal;
             }
-            _ => {}
+            Err(e) => app.status_message = Some(e),
         },
-        FormField::Category => match key {
-            KeyCode::Left => {
-                let count = Category::all_display_names().len();
-                app.form.category_index = if app.form.category_index == 0 {
-                    count - 1
-                } else {
-                    app.form.category_index - 1
-                };
-            }
-            KeyCode::Right => {
-                let count = Category::all_display_names().len();
-                app.form.category_index = (app.form.category_index + 1) % count;
-            }
-            KeyCode::Char(c) if app.form.category_index == 9 => {
-                app.form.custom_category.push(c);
-            }
-            KeyCode::Backspace if app.form.category_index == 9 => {
-                app.form.custom_category.pop();
+        _ => {
+            app.quick_add.handle_key(key, modifiers, |_| true);
+        }
+    }
+}
+
+fn handle_goal_prompt_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    match key {
+        KeyCode::Esc => app.input_mode = InputMode::Normal,
+        KeyCode::Enter => match app.submit_goal_prompt() {
+            Ok(message) => {
+                app.status_message = Some(message);
+                app.input_mode = InputMode::Normal;
             }
-            _ => {}
+            Err(e) => app.status_message = Some(e),
         },
-        FormField::Description => match key {
-            KeyCode::Char(c) => {
-                app.form.description_input.push(c);
-            }
-            KeyCode::Backspace => {
-                app.form.description_input.pop();
+        _ => {
+            app.goal_input.handle_key(key, modifiers, |_| true);
+        }
+    }
+}
+
+fn handle_loan_prompt_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    match key {
+        KeyCode::Esc => app.input_mode = InputMode::Normal,
+        KeyCode::Enter => match app.submit_loan_prompt() {
+            Ok(message) => {
+                app.status_message = Some(message);
+                app.input_mode = InputMode::Normal;
             }
-            _ => {}
+            Err(e) => app.status_message = Some(e),
         },
-        FormField::Date => match key {
-            KeyCode::Char(c) if c.is_ascii_digit() || c == '-' => {
-                app.form.date_input.push(c);
+        _ => {
+            app.loan_input.handle_key(key, modifiers, |_| true);
+        }
+    }
+}
+
+fn handle_cancel_subscription_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    match key {
+        KeyCode::Esc => app.input_mode = InputMode::Normal,
+        KeyCode::Enter => match app.cancel_subscription() {
+            Ok(message) => {
+                app.status_message = Some(message);
+                app.input_mode = InputMode::Normal;
             }
-            KeyCode::Backspace => {
-                app.form.date_input.pop();
+            Err(e) => app.status_message = Some(e),
+        },
+        _ => {
+            app.subscription_input.handle_key(key, modifiers, |_| true);
+        }
+    }
+}
+
+fn handle_subscription_price_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    match key {
+        KeyCode::Esc => app.input_mode = InputMode::Normal,
+        KeyCode::Enter => match app.change_subscription_price() {
+            Ok(message) => {
+                app.status_message = Some(message);
+                app.input_mode = InputMode::Normal;
             }
-            _ => {}
+            Err(e) => app.status_message = Some(e),
         },
+        _ => {
+            app.subscription_input.handle_key(key, modifiers, |_| true);
+        }
+    }
+}
+
+/// `12.50 Food "lunch" on 2026-10-17 #work`, for confirmations.
+fn describe_expense(app: &App, expense: &model::Expense) -> String {
+    let mut text = format!("{} {}", app.fmt(expense.amount), expense.category);
+    if !expense.description.is_empty() {
+        text.push_str(&format!(" \"{}\"", expense.description));
+    }
+    text.push_str(&format!(" on {}", expense.date.format("%Y-%m-%d")));
+    for tag in &expense.tags {
+        text.push_str(&format!(" #{}", tag));
+    }
+    text
+}
+
+fn handle_form_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    if app.form.calendar.is_some() {
+        handle_calendar_input(app, key, modifiers);
+        return;
+    }
+    let chord = KeyChord::new(key, modifiers);
+    match app.keymap.resolve(&[chord], &[KeyContext::Form]) {
+        Resolution::Action(action) => run_action(app, action),
+        _ => handle_field_input(app, key, modifiers),
+    }
+}
+
+fn handle_calendar_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    run_popup_action(app, key, modifiers, KeyContext::Calendar);
+}
+
+fn save_form(app: &mut App) {
+    let id = app.form.editing_id.unwrap_or_else(|| app.next_id());
+    if let Some(expense) = app.form.to_expense(id) {
+        let editing = app.form.editing_id.filter(|_| app.input_mode == InputMode::EditForm);
+        let impact =
+            app.budget_impact(&expense.category, expense.date, expense.amount, editing);
+        if let Some(edit_id) = editing {
+            app.update_expense(edit_id, expense);
+            app.status_message = Some("Expense updated".to_string());
+        } else if app.input_mode != InputMode::EditForm {
+            app.add_expense(expense);
+            app.status_message = Some("Expense added".to_string());
+        }
+        app.input_mode = InputMode::Normal;
+        // Saving took the budget past an alert threshold.
+        if let Some(impact) = impact.filter(|i| i.crossed.is_some()) {
+            app.budget_alert = Some(impact);
+            app.input_mode = InputMode::BudgetAlert;
+        }
+    } else {
+        app.status_message = Some("Invalid form data. Check fields.".to_string());
+    }
+}
+
+fn handle_field_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    match app.form.active_field {
+        FormField::Category if key == KeyCode::Left => {
+            let count = Category::all_display_names().len();
+            app.form.category_index = if app.form.category_index == 0 {
+                count - 1
+            } else {
+                app.form.category_index - 1
+            };
+        }
+        FormField::Category if key == KeyCode::Right => {
+            let count = Category::all_display_names().len();
+            app.form.category_index = (app.form.category_index + 1) % count;
+        }
         FormField::Recurring => {
             if let KeyCode::Char(' ') = key {
                 app.form.is_recurring = !app.form.is_recurring;
@@ -328,18 +790,190 @@ fn handle_field_input(app: &mut App, key: KeyCode, _modifiers: KeyModifiers) {
                 }
             }
         }
+        _ => {
+            if let Some((input, accept)) = app.form.active_input() {
+                input.handle_key(key, modifiers, accept);
+            }
+        }
     }
 }
 
-fn handle_help_input(app: &mut App, key: KeyCode) {
-    match key {
-        KeyCode::Char('?') | KeyCode::Esc => {
-            app.input_mode = InputMode::Normal;
+/// Bracketed paste goes into whichever text input has focus, so pasted
+/// text is never interpreted as key bindings.
+fn handle_paste(app: &mut App, text: &str) {
+    match app.input_mode {
+        InputMode::Search => {
+            app.search_query.insert_str(text, |_| true);
+            app.update_search_query();
+        }
+        InputMode::CommandPalette => {
+            app.palette_query.insert_str(text, |_| true);
+            app.palette_index = 0;
+        }
+        InputMode::QuickAdd => app.quick_add.insert_str(text, |_| true),
+        InputMode::GoalPrompt => app.goal_input.insert_str(text, |_| true),
+        InputMode::LoanPrompt => app.loan_input.insert_str(text, |_| true),
+        InputMode::CancelSubscription | InputMode::SubscriptionPrice => {
+            app.subscription_input.insert_str(text, |_| true)
+        }
+        InputMode::AddForm | InputMode::EditForm if app.form.calendar.is_none() => {
+            if let Some((input, accept)) = app.form.active_input() {
+                input.insert_str(text, accept);
+            }
         }
+        InputMode::BulkInput
+            if app.bulk.kind != Some(BulkActionKind::SetCategory)
+                || app.bulk.category_index == 9 =>
+        {
+            app.bulk.input.insert_str(text, |_| true);
+        }
+        _ => {}
+    }
+}
+
+fn handle_mouse_input(app: &mut App, mouse: MouseEvent, area: Rect) {
+    let (col, row) = (mouse.column, mouse.row);
+    match mouse.kind {
+        MouseEventKind::ScrollDown => handle_scroll(app, 1),
+        MouseEventKind::ScrollUp => handle_scroll(app, -1),
+        MouseEventKind::Down(MouseButton::Left) => {
+            app.status_message = None;
+            handle_click(app, col, row, area);
+        }
+        _ => {}
+    }
+}
+
+fn handle_scroll(app: &mut App, delta: i32) {
+    let key = if delta > 0 { KeyCode::Down } else { KeyCode::Up };
+    let scroll = |down: Action, up: Action| if delta > 0 { down } else { up };
+    match app.input_mode {
+        InputMode::Normal => match app.active_tab {
+            Tab::Expenses => {
+                let last = app.filtered_indices.len().saturating_sub(1);
+                app.expense_table_index = if delta > 0 {
+                    (app.expense_table_index + 1).min(last)
+                } else {
+                    app.expense_table_index.saturating_sub(1)
+                };
+            }
+            Tab::Monthly if delta > 0 => app.next_month(),
+            Tab::Monthly => app.prev_month(),
+            Tab::Yearly => app.overview_year += delta,
+            Tab::Heatmap => app.move_heatmap_day(delta as i64 * 7),
+            Tab::Budgets => app.move_budget_selection(delta),
+            Tab::Goals => app.move_goal_selection(delta),
+            Tab::Loans => app.move_loan_selection(delta),
+            Tab::Subscriptions => app.move_subscription_selection(delta),
+            // The Dashboard fits on one screen; there's nothing to scroll.
+            Tab::Dashboard => {}
+        },
+        InputMode::AddForm | InputMode::EditForm if app.form.calendar.is_some() => {
+            run_action(app, scroll(Action::CalendarNextWeek, Action::CalendarPrevWeek));
+        }
+        InputMode::AddForm | InputMode::EditForm => {
+            app.form.active_field = if delta > 0 {
+                app.form.active_field.next()
+            } else {
+                app.form.active_field.prev()
+            };
+        }
+        InputMode::ColumnEditor => run_action(app, scroll(Action::ColumnsDown, Action::ColumnsUp)),
+        InputMode::BulkMenu => handle_bulk_menu_input(app, key),
+        InputMode::TemplatePicker => {
+            run_action(app, scroll(Action::TemplatesDown, Action::TemplatesUp))
+        }
+        InputMode::CommandPalette => handle_palette_input(app, key, KeyModifiers::NONE),
+        InputMode::HelpPopup => run_action(app, scroll(Action::HelpDown, Action::HelpUp)),
+        InputMode::DayDetail => run_action(app, scroll(Action::DayNext, Action::DayPrev)),
+        InputMode::AnomalyReview => run_action(app, scroll(Action::ReviewDown, Action::ReviewUp)),
         _ => {}
     }
 }
 
+fn handle_click(app: &mut App, col: u16, row: u16, area: Rect) {
+    match app.input_mode {
+        InputMode::Normal => {
+            if let Some(tab) = ui::tab_at(area, col, row) {
+                app.active_tab = tab;
+                return;
+            }
+            let content = ui::screen_layout(area)[1];
+            match app.active_tab {
+                Tab::Expenses => {
+                    if let Some(index) = ui::expenses::table_row_at(app, content, col, row) {
+                        app.expense_table_index = index;
+                    }
+                }
+                Tab::Monthly => match ui::monthly::month_arrow_at(app, content, col, row) {
+                    Some(delta) if delta < 0 => app.prev_month(),
+                    Some(_) => app.next_month(),
+                    None => {}
+                },
+                Tab::Yearly => {
+                    if let Some(delta) = ui::yearly::year_arrow_at(app, content, col, row) {
+                        app.overview_year += delta;
+                    }
+                }
+                Tab::Heatmap => {
+                    if let Some(delta) = ui::heatmap::year_arrow_at(app, content, col, row) {
+                        app.shift_heatmap_year(delta);
+                    } else if let Some(day) = ui::heatmap::day_at(app, content, col, row) {
+                        // A second click on the selected day opens it.
+                        if day == app.heatmap_day {
+                            app.input_mode = InputMode::DayDetail;
+                        }
+                        app.heatmap_day = day;
+                    }
+                }
+                Tab::Budgets => {
+                    if let Some(index) = ui::budgets::budget_row_at(app, content, col, row) {
+                        app.budget_index = index;
+                    }
+                }
+                Tab::Goals => {
+                    if let Some(index) = ui::goals::goal_row_at(app, content, col, row) {
+                        app.goal_index = index;
+                    }
+                }
+                Tab::Loans => {
+                    if let Some(index) = ui::loans::loan_row_at(app, content, col, row) {
+                        app.loan_index = index;
+                    }
+                }
+                Tab::Subscriptions => {
+                    if let Some(index) = ui::subscriptions::row_at(app, content, col, row) {
+                        app.subscription_index = index;
+                    }
+                }
+                Tab::Dashboard => {}
+            }
+        }
+        InputMode::AddForm | InputMode::EditForm if app.form.calendar.is_none() => {
+            let Some((field, arrow)) = ui::add_form::field_at(app, area, col, row) else {
+                return;
+            };
+            app.form.active_field = field;
+            if field == FormField::Recurring {
+                handle_field_input(app, KeyCode::Char(' '), KeyModifiers::NONE);
+            } else if let Some(delta) = arrow {
+                let key = if delta < 0 { KeyCode::Left } else { KeyCode::Right };
+                handle_field_input(app, key, KeyModifiers::NONE);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_help_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    let chord = KeyChord::new(key, modifiers);
+    if app.keymap.keys(Action::Help).iter().any(|k| k.0 == [chord]) {
+        app.input_mode = InputMode::Normal;
+    } else {
+        run_popup_action(app, key, modifiers, KeyContext::Help);
+    }
+}
+
 fn handle_confirm_delete(app: &mut App, key: KeyCode) {
     match key {
         KeyCode::Char('y') | KeyCode::Char('Y') => {
@@ -353,6 +987,184 @@ fn handle_confirm_delete(app: &mut App, key: KeyCode) {
     }
 }
 
+fn handle_column_editor_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    run_popup_action(app, key, modifiers, KeyContext::Columns);
+}
+
+fn handle_template_picker_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    let count = app.templates.len();
+    if count == 0 {
+        app.input_mode = InputMode::Normal;
+        return;
+    }
+    if run_popup_action(app, key, modifiers, KeyContext::Templates) {
+        return;
+    }
+    // The first nine templates are numbered in the picker.
+    if let KeyCode::Char(c @ '1'..='9') = key {
+        let index = c.to_digit(10).unwrap_or(0) as usize - 1;
+        if index < count {
+            app.template_index = index;
+            use_template(app, index, false);
+        }
+    }
+}
+
+/// Add the template as today's expense, or open it in the add form when it
+/// has no amount or `edit` is set.
+fn use_template(app: &mut App, index: usize, edit: bool) {
+    let Some(template) = app.templates.get(index).cloned() else {
+        return;
+    };
+    let id = app.next_id();
+    match template.to_expense(id, utils::today()) {
+        Some(expense) if !edit => {
+            let amount = app.fmt(expense.amount);
+            app.add_expense(expense);
+            app.status_message = Some(format!("Added {} ({})", template.name, amount));
+            app.input_mode = InputMode::Normal;
+        }
+        _ => {
+            app.form = FormState::from_template(&template);
+            app.input_mode = InputMode::AddForm;
+        }
+    }
+}
+
+fn handle_anomaly_review_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    run_popup_action(app, key, modifiers, KeyContext::Review);
+}
+
+fn handle_day_detail_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    run_popup_action(app, key, modifiers, KeyContext::DayDetail);
+}
+
+fn handle_budget_alert_input(app: &mut App, key: KeyCode) {
+    if let KeyCode::Esc | KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Char('q') = key {
+        app.budget_alert = None;
+        app.input_mode = InputMode::Normal;
+    }
+}
+
+fn handle_bulk_menu_input(app: &mut App, key: KeyCode) {
+    let kinds = BulkActionKind::all();
+    match key {
+        KeyCode::Esc => app.input_mode = InputMode::Normal,
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.bulk.menu_index = (app.bulk.menu_index + 1) % kinds.len();
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.bulk.menu_index = if app.bulk.menu_index == 0 {
+                kinds.len() - 1
+            } else {
+                app.bulk.menu_index - 1
+            };
+        }
+        KeyCode::Char(c) if c.is_ascii_digit() => {
+            let index = c.to_digit(10).unwrap_or(0) as usize;
+            if index >= 1 && index <= kinds.len() {
+                app.bulk.menu_index = index - 1;
+                choose_bulk_action(app);
+            }
+        }
+        KeyCode::Enter => choose_bulk_action(app),
+        _ => {}
+    }
+}
+
+fn choose_bulk_action(app: &mut App) {
+    let kind = BulkActionKind::all()[app.bulk.menu_index];
+    app.bulk.kind = Some(kind);
+    app.bulk.input.clear();
+    if kind.needs_input() {
+        app.input_mode = InputMode::BulkInput;
+    } else {
+        confirm_bulk_action(app);
+    }
+}
+
+fn confirm_bulk_action(app: &mut App) {
+    match app.bulk.build_action() {
+        Ok(action) => {
+            app.bulk.pending = Some(action);
+            app.input_mode = InputMode::ConfirmBulk;
+        }
+        Err(e) => app.status_message = Some(e),
+    }
+}
+
+fn handle_bulk_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    let choosing_category = app.bulk.kind == Some(BulkActionKind::SetCategory);
+    match key {
+        KeyCode::Esc => app.input_mode = InputMode::BulkMenu,
+        KeyCode::Enter => confirm_bulk_action(app),
+        KeyCode::Left if choosing_category => {
+            let count = Category::all_display_names().len();
+            app.bulk.category_index = if app.bulk.category_index == 0 {
+                count - 1
+            } else {
+                app.bulk.category_index - 1
+            };
+        }
+        KeyCode::Right if choosing_category => {
+            let count = Category::all_display_names().len();
+            app.bulk.category_index = (app.bulk.category_index + 1) % count;
+        }
+        _ if !choosing_category || app.bulk.category_index == 9 => {
+            app.bulk.input.handle_key(key, modifiers, |_| true);
+        }
+        _ => {}
+    }
+}
+
+fn handle_confirm_bulk(app: &mut App, key: KeyCode) {
+    if let KeyCode::Char('y') | KeyCode::Char('Y') = key {
+        if let Some(action) = app.bulk.pending.take() {
+            app.status_message = Some(match app.apply_bulk(&action) {
+                Ok(msg) => msg,
+                Err(e) => format!("Bulk action failed: {}", e),
+            });
+        }
+    }
+    app.input_mode = InputMode::Normal;
+}
+
+fn check_keymap() -> Result<()> {
+    match storage::load_keymap()? {
+        None => eprintln!("No keymap file found; using the default keybindings."),
+        Some(content) => match Keymap::from_config(&content) {
+            Ok(_) => eprintln!("Keymap OK"),
+            Err(problems) => {
+                for problem in problems {
+                    eprintln!("{}", problem);
+                }
+            }
+        },
+    }
+    Ok(())
+}
+
+fn add_from_command_line(words: &[String]) -> Result<()> {
+    let entry = words.join(" ");
+    if entry.trim().is_empty() {
+        eprintln!("Usage: cashflow add \"12.50 food lunch @yesterday #work\"");
+        std::process::exit(1);
+    }
+    let mut app = App::new()?;
+    let expense = match quick_add::parse(&entry, app.next_id(), utils::today()) {
+        Ok(expense) => expense,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let message = describe_expense(&app, &expense);
+    app.expenses.push(expense);
+    app.save()?;
+    eprintln!("Added {}", message);
+    Ok(())
+}
+
 fn parse_import_arg(args: &[String]) -> Option<String> {
     let mut iter = args.iter();
     while let Some(arg) = iter.next() {
@@ -371,10 +1183,14 @@ fn print_usage() {
     eprintln!("  cashflow --import <file>              Import CSV then launch TUI");
     eprintln!("  cashflow --import <file> --import-only  Import CSV without TUI");
     eprintln!("  cashflow -i <file>                    Short form of --import");
+    eprintln!("  cashflow add \"<entry>\"                Add one expense without the TUI");
+    eprintln!("  cashflow --no-mouse                   Launch without mouse capture");
+    eprintln!("  cashflow --check-keymap               Validate ~/.cashflow/keymap");
     eprintln!("  cashflow --help                       Show this help");
     eprintln!();
     eprintln!("CSV FORMAT:");
-    eprintln!("  id,amount,category,description,date,is_recurring,recurrence");
+    eprintln!("  id,amount,category,description,date,is_recurring,recurrence,tags,cancelled");
+    eprintln!("  (tags and cancelled are optional; tags are separated by ';')");
     eprintln!();
     eprintln!("CATEGORIES:");
     eprintln!("  Food, Transport, Rent, Utilities, Entertainment,");
@@ -386,4 +1202,5 @@ fn print_usage() {
     eprintln!("EXAMPLE:");
     eprintln!("  cashflow --import sample_data.csv");
     eprintln!("  cashflow -i sample_data.csv --import-only");
+    eprintln!("  cashflow add \"12.50 food lunch with team @yesterday #work\"");
 }
@@ -1,17 +1,322 @@
 use super::expense::Category;
-use serde::{Deserialize, Serialize};
+use chrono::{Datelike, Days, Months, NaiveDate};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// The window a budget's limit applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Period {
+    /// Monday to Sunday.
+    Weekly,
+    #[default]
+    Monthly,
+    Quarterly,
+    Yearly,
+    /// The budget's own `start`..=`end` dates.
+    Custom,
+}
+
+impl Period {
+    pub fn from_str_value(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "weekly" | "week" => Some(Period::Weekly),
+            "" | "monthly" | "month" => Some(Period::Monthly),
+            "quarterly" | "quarter" => Some(Period::Quarterly),
+            "yearly" | "year" | "annual" => Some(Period::Yearly),
+            "custom" => Some(Period::Custom),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Period::Weekly => write!(f, "weekly"),
+            Period::Monthly => write!(f, "monthly"),
+            Period::Quarterly => write!(f, "quarterly"),
+            Period::Yearly => write!(f, "yearly"),
+            Period::Custom => write!(f, "custom"),
+        }
+    }
+}
+
+impl Serialize for Period {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Period {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Period::from_str_value(&s)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown period: {}", s)))
+    }
+}
+
+/// Percentages of a budget that trigger an alert when not set in the file.
+pub const DEFAULT_ALERTS: [f64; 2] = [80.0, 100.0];
+
+fn default_alerts() -> Vec<f64> {
+    DEFAULT_ALERTS.to_vec()
+}
+
+/// Alert thresholds as `;`-separated percentages, e.g. `80;100`. Empty means
+/// the defaults; `none` turns alerts off.
+mod alerts_format {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(alerts: &[f64], serializer: S) -> Result<S::Ok, S::Error> {
+        if alerts.is_empty() {
+            return serializer.serialize_str("none");
+        }
+        let values: Vec<String> = alerts.iter().map(|a| a.to_string()).collect();
+        serializer.serialize_str(&values.join(";"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<f64>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let s = s.trim();
+        match s.to_lowercase().as_str() {
+            "" => return Ok(super::default_alerts()),
+            "none" | "off" | "no" => return Ok(Vec::new()),
+            _ => {}
+        }
+        let mut alerts = Vec::new();
+        for part in s.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+            let value = part
+                .trim_end_matches('%')
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite() && *v > 0.0)
+                .ok_or_else(|| serde::de::Error::custom(format!("invalid alert: {}", part)))?;
+            alerts.push(value);
+        }
+        alerts.sort_by(|a, b| a.total_cmp(b));
+        alerts.dedup();
+        Ok(alerts)
+    }
+}
+
+/// What an envelope budget carries into the next period.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Rollover {
+    /// Every period starts from the plain limit.
+    #[default]
+    None,
+    /// Unspent money is added to the next period.
+    Surplus,
+    /// Overspending is taken from the next period.
+    Deficit,
+    Both,
+}
+
+impl Rollover {
+    pub fn from_str_value(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "none" | "no" => Some(Rollover::None),
+            "surplus" => Some(Rollover::Surplus),
+            "deficit" => Some(Rollover::Deficit),
+            "both" | "yes" => Some(Rollover::Both),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Rollover {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rollover::None => write!(f, "none"),
+            Rollover::Surplus => write!(f, "surplus"),
+            Rollover::Deficit => write!(f, "deficit"),
+            Rollover::Both => write!(f, "both"),
+        }
+    }
+}
+
+impl Serialize for Rollover {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Rollover {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Rollover::from_str_value(&s)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown rollover: {}", s)))
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Budget {
     pub category: Category,
-    pub monthly_limit: f64,
+    /// When this version of the category's budget takes over from the one
+    /// before. Empty for a budget that has always applied.
+    #[serde(default)]
+    pub effective_from: Option<NaiveDate>,
+    /// The limit for each period. Older files call it `monthly_limit`.
+    #[serde(alias = "monthly_limit")]
+    pub limit: f64,
+    #[serde(default)]
+    pub period: Period,
+    /// First and last day of a custom period.
+    #[serde(default)]
+    pub start: Option<NaiveDate>,
+    #[serde(default)]
+    pub end: Option<NaiveDate>,
+    #[serde(default)]
+    pub rollover: Rollover,
+    /// The most that can be carried into a period, surplus or deficit.
+    #[serde(default)]
+    pub rollover_cap: Option<f64>,
+    /// Percentages of the available amount that trigger an alert, lowest
+    /// first.
+    #[serde(default = "default_alerts", with = "alerts_format")]
+    pub alerts: Vec<f64>,
 }
 
 impl Budget {
-    pub fn _new(category: Category, monthly_limit: f64) -> Self {
+    pub fn _new(category: Category, limit: f64) -> Self {
         Self {
             category,
-            monthly_limit,
+            effective_from: None,
+            limit,
+            period: Period::Monthly,
+            start: None,
+            end: None,
+            rollover: Rollover::None,
+            rollover_cap: None,
+            alerts: default_alerts(),
+        }
+    }
+
+    /// The first and last day of the period holding `date`. A custom budget
+    /// only has a window between its own dates.
+    pub fn window(&self, date: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        let start = match self.period {
+            Period::Weekly => {
+                date.checked_sub_days(Days::new(date.weekday().num_days_from_monday() as u64))?
+            }
+            Period::Monthly => date.with_day(1)?,
+            Period::Quarterly => {
+                NaiveDate::from_ymd_opt(date.year(), date.month0() / 3 * 3 + 1, 1)?
+            }
+            Period::Yearly => NaiveDate::from_ymd_opt(date.year(), 1, 1)?,
+            Period::Custom => {
+                let (start, end) = (self.start?, self.end?);
+                return (start..=end).contains(&date).then_some((start, end));
+            }
+        };
+        let next = match self.period {
+            Period::Weekly => start.checked_add_days(Days::new(7))?,
+            Period::Monthly => start.checked_add_months(Months::new(1))?,
+            Period::Quarterly => start.checked_add_months(Months::new(3))?,
+            _ => start.checked_add_months(Months::new(12))?,
+        };
+        Some((start, next.pred_opt()?))
+    }
+
+    /// How the period reads in a sentence, e.g. "this week".
+    pub fn period_label(&self) -> String {
+        match (self.period, self.start, self.end) {
+            (Period::Weekly, _, _) => "this week".to_string(),
+            (Period::Monthly, _, _) => "this month".to_string(),
+            (Period::Quarterly, _, _) => "this quarter".to_string(),
+            (Period::Yearly, _, _) => "this year".to_string(),
+            (Period::Custom, Some(start), Some(end)) => {
+                format!(
+                    "{} – {}",
+                    start.format("%-d %b %Y"),
+                    end.format("%-d %b %Y")
+                )
+            }
+            (Period::Custom, _, _) => "no dates set".to_string(),
         }
     }
+
+    /// The share of the limit that falls in the month starting `month_start`,
+    /// by days, so budgets of any period can be added up for a month.
+    pub fn monthly_share(&self, month_start: NaiveDate) -> f64 {
+        let Some(month_end) = month_start
+            .checked_add_months(Months::new(1))
+            .and_then(|d| d.pred_opt())
+        else {
+            return 0.0;
+        };
+        let days = |from: NaiveDate, to: NaiveDate| (to - from).num_days() as f64 + 1.0;
+        match self.period {
+            Period::Monthly => self.limit,
+            Period::Weekly => self.limit * days(month_start, month_end) / 7.0,
+            Period::Quarterly => self.limit / 3.0,
+            Period::Yearly => self.limit / 12.0,
+            Period::Custom => match (self.start, self.end) {
+                (Some(start), Some(end)) if start <= end => {
+                    let from = start.max(month_start);
+                    let to = end.min(month_end);
+                    if from > to {
+                        0.0
+                    } else {
+                        self.limit * days(from, to) / days(start, end)
+                    }
+                }
+                _ => 0.0,
+            },
+        }
+    }
+
+    /// What carries into the next period from one that ended `balance`
+    /// under (positive) or over (negative) its available amount. A custom
+    /// period is a one-off and carries nothing.
+    pub fn carry(&self, balance: f64) -> f64 {
+        if self.period == Period::Custom {
+            return 0.0;
+        }
+        let kept = match self.rollover {
+            Rollover::Surplus | Rollover::Both if balance > 0.0 => balance,
+            Rollover::Deficit | Rollover::Both if balance < 0.0 => balance,
+            _ => 0.0,
+        };
+        match self.rollover_cap {
+            Some(cap) => kept.clamp(-cap.abs(), cap.abs()),
+            None => kept,
+        }
+    }
+}
+
+/// A budget's money for one period: the limit plus whatever rolled over.
+#[derive(Debug, Clone, Copy)]
+pub struct Envelope {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub limit: f64,
+    /// Carried in from earlier periods; negative after overspending.
+    pub carried: f64,
+    /// Spent in this period so far.
+    pub spent: f64,
+}
+
+impl Envelope {
+    pub fn available(&self) -> f64 {
+        self.limit + self.carried
+    }
+
+    /// Percentage of the available amount that `spent` uses. Anything spent
+    /// with nothing available counts as over every threshold.
+    pub fn percent_used(&self, spent: f64) -> f64 {
+        let available = self.available();
+        if available > 0.0 {
+            spent / available * 100.0
+        } else if spent > 0.0 {
+            f64::INFINITY
+        } else {
+            0.0
+        }
+    }
+
+    pub fn remaining(&self) -> f64 {
+        self.available() - self.spent
+    }
 }
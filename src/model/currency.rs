@@ -1,8 +1,10 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub enum Currency {
+    #[default]
     USD,
     EUR,
     GBP,
@@ -133,8 +135,8 @@ impl Currency {
         }
     }
 
-    pub fn to_index(&self) -> usize {
-        Self::all().iter().position(|c| c == self).unwrap_or(0)
+    pub fn to_index(self) -> usize {
+        Self::all().iter().position(|c| *c == self).unwrap_or(0)
     }
 
     pub fn from_code(code: &str) -> Option<Self> {
@@ -146,12 +148,6 @@ impl Currency {
     }
 }
 
-impl Default for Currency {
-    fn default() -> Self {
-        Currency::USD
-    }
-}
-
 impl fmt::Display for Currency {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.code())
@@ -158,7 +158,7 @@ impl Recurrence {
         }
     }
 
-    pub fn to_index(&self) -> usize {
+    pub fn to_index(self) -> usize {
         match self {
             Recurrence::Daily => 0,
             Recurrence::Weekly => 1,
@@ -167,6 +167,16 @@ impl Recurrence {
         }
     }
 
+    /// How many times a year it happens.
+    pub fn per_year(&self) -> f64 {
+        match self {
+            Recurrence::Daily => 365.25,
+            Recurrence::Weekly => 365.25 / 7.0,
+            Recurrence::Monthly => 12.0,
+            Recurrence::Yearly => 1.0,
+        }
+    }
+
     pub fn next_date(&self, from: NaiveDate) -> NaiveDate {
         match self {
             Recurrence::Daily => from + chrono::Duration::days(1),
@@ -236,6 +246,12 @@ pub struct Expense {
     pub date: NaiveDate,
     pub is_recurring: bool,
     pub recurrence: Option<Recurrence>,
+    #[serde(default, with = "tags_format")]
+    pub tags: Vec<String>,
+    /// For a recurring expense, the day its series stops charging; nothing
+    /// is generated from then on.
+    #[serde(default)]
+    pub cancelled: Option<NaiveDate>,
 }
 
 impl Expense {
@@ -256,6 +272,43 @@ impl Expense {
             date,
             is_recurring,
             recurrence,
+            tags: Vec::new(),
+            cancelled: None,
+        }
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+}
+
+/// Split a user-entered tag list (`work, #travel; q3`) into clean tag names.
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for raw in input.split(|c: char| c == ',' || c == ';' || c.is_whitespace()) {
+        let tag = raw.trim().trim_start_matches('#');
+        if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
         }
     }
+    tags
+}
+
+/// Tags are stored in a single CSV column, separated by `;`.
+pub(super) mod tags_format {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(tags: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&tags.join(";"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(super::parse_tags(&s))
+    }
 }
@@ -0,0 +1,107 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Average days in a month, for turning the time left into months.
+const DAYS_PER_MONTH: f64 = 365.25 / 12.0;
+
+/// Something being saved for, e.g. a car or an emergency fund.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Goal {
+    pub name: String,
+    pub target: f64,
+    pub target_date: NaiveDate,
+    /// When saving started, the baseline for on-track checks. Empty means
+    /// the first contribution.
+    #[serde(default)]
+    pub start: Option<NaiveDate>,
+}
+
+/// Money put towards a goal; negative for a withdrawal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contribution {
+    pub goal: String,
+    pub date: NaiveDate,
+    pub amount: f64,
+    #[serde(default)]
+    pub note: String,
+}
+
+/// Where a goal stands on a given day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GoalStatus {
+    Reached,
+    OnTrack,
+    /// Saved less than an even pace from the start would have by now.
+    Behind,
+    /// The target date has passed without reaching the target.
+    Overdue,
+}
+
+impl GoalStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GoalStatus::Reached => "reached",
+            GoalStatus::OnTrack => "on track",
+            GoalStatus::Behind => "off track",
+            GoalStatus::Overdue => "overdue",
+        }
+    }
+}
+
+/// A goal's contributions totalled up as of one day.
+#[derive(Debug, Clone, Copy)]
+pub struct GoalProgress {
+    pub target: f64,
+    pub saved: f64,
+    pub start: NaiveDate,
+    pub target_date: NaiveDate,
+    pub as_of: NaiveDate,
+}
+
+impl GoalProgress {
+    pub fn remaining(&self) -> f64 {
+        (self.target - self.saved).max(0.0)
+    }
+
+    /// Saved as a fraction of the target, 0 to 1.
+    pub fn ratio(&self) -> f64 {
+        if self.target > 0.0 {
+            (self.saved / self.target).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// Months until the target date, counting part months; 0 once it's past.
+    pub fn months_left(&self) -> f64 {
+        ((self.target_date - self.as_of).num_days().max(0) as f64) / DAYS_PER_MONTH
+    }
+
+    /// What has to go in each month from now to reach the target on time.
+    /// With less than a month left, the whole remainder is due.
+    pub fn required_monthly(&self) -> f64 {
+        self.remaining() / self.months_left().max(1.0)
+    }
+
+    /// What an even pace from `start` to the target date has saved by now.
+    pub fn expected(&self) -> f64 {
+        let total = (self.target_date - self.start).num_days();
+        if total <= 0 {
+            return self.target;
+        }
+        let elapsed = (self.as_of - self.start).num_days().clamp(0, total);
+        self.target * elapsed as f64 / total as f64
+    }
+
+    pub fn status(&self) -> GoalStatus {
+        if self.saved >= self.target - 0.005 {
+            GoalStatus::Reached
+        } else if self.as_of > self.target_date {
+            GoalStatus::Overdue
+        } else if self.saved >= self.expected() - 0.005 {
+            GoalStatus::OnTrack
+        } else {
+            GoalStatus::Behind
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use chrono::{Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Longest schedule worked out when projecting a payoff, in months.
+const MAX_PAYMENTS: u32 = 1200;
+
+/// Money owed and paid back monthly: a car loan, or an IOU at 0%.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Loan {
+    pub name: String,
+    /// Expenses with this tag are payments on the loan.
+    pub tag: String,
+    pub principal: f64,
+    /// Yearly interest rate in percent, e.g. `5.9`.
+    #[serde(default)]
+    pub rate: f64,
+    pub term_months: u32,
+    pub first_payment: NaiveDate,
+}
+
+/// One monthly payment, split into the interest it covers and the
+/// principal it pays off.
+#[derive(Debug, Clone, Copy)]
+pub struct Installment {
+    /// 1 for the first payment.
+    pub number: u32,
+    pub date: NaiveDate,
+    pub payment: f64,
+    pub interest: f64,
+    pub principal: f64,
+    /// What's still owed after this payment.
+    pub balance: f64,
+}
+
+/// A loan's actual payments and where they leave it.
+#[derive(Debug, Clone)]
+pub struct LoanSummary {
+    pub payments: Vec<Installment>,
+    pub balance: f64,
+    /// When the next scheduled payment is due, unless paid off.
+    pub next_due: Option<NaiveDate>,
+    /// When the last payment falls if the scheduled payment keeps being
+    /// made; `None` once paid off or if it doesn't cover the interest.
+    pub payoff: Option<NaiveDate>,
+}
+
+impl LoanSummary {
+    pub fn paid_off(&self) -> bool {
+        self.balance < 0.005
+    }
+
+    pub fn interest_paid(&self) -> f64 {
+        self.payments
+            .iter()
+            .fold(0.0, |total, p| total + p.interest)
+    }
+
+    pub fn principal_paid(&self) -> f64 {
+        self.payments
+            .iter()
+            .fold(0.0, |total, p| total + p.principal)
+    }
+}
+
+impl Loan {
+    fn monthly_rate(&self) -> f64 {
+        self.rate / 1200.0
+    }
+
+    /// The fixed monthly payment that clears the loan over its term.
+    pub fn payment(&self) -> f64 {
+        let n = self.term_months.max(1) as f64;
+        let r = self.monthly_rate();
+        if r.abs() < f64::EPSILON {
+            self.principal / n
+        } else {
+            self.principal * r / (1.0 - (1.0 + r).powf(-n))
+        }
+    }
+
+    /// When payment `number` (from 1) is due, a month after the one before.
+    pub fn due_date(&self, number: u32) -> Option<NaiveDate> {
+        self.first_payment
+            .checked_add_months(Months::new(number.saturating_sub(1)))
+    }
+
+    /// The payment that takes `balance` down by one month: a month's
+    /// interest first, the rest off the principal, and never more than
+    /// what's owed.
+    fn installment(&self, number: u32, date: NaiveDate, payment: f64, balance: f64) -> Installment {
+        let interest = balance * self.monthly_rate();
+        let payment = payment.min(balance + interest);
+        let principal = payment - interest;
+        Installment {
+            number,
+            date,
+            payment,
+            interest,
+            principal,
+            balance: balance - principal,
+        }
+    }
+
+    /// The amortization schedule when every payment is made on time.
+    pub fn schedule(&self) -> Vec<Installment> {
+        let payment = self.payment();
+        let mut balance = self.principal;
+        let mut schedule = Vec::new();
+        for number in 1..=self.term_months {
+            let Some(date) = self.due_date(number) else {
+                break;
+            };
+            // The last payment clears whatever rounding left behind.
+            let amount = if number == self.term_months {
+                f64::INFINITY
+            } else {
+                payment
+            };
+            let installment = self.installment(number, date, amount, balance);
+            balance = installment.balance;
+            schedule.push(installment);
+        }
+        schedule
+    }
+
+    /// Split actual payments, oldest first, into interest and principal,
+    /// and project the payoff from what's left.
+    pub fn summarize(&self, payments: &[(NaiveDate, f64)]) -> LoanSummary {
+        let mut balance = self.principal;
+        let mut made = Vec::new();
+        for (i, &(date, amount)) in payments.iter().enumerate() {
+            if balance < 0.005 {
+                break;
+            }
+            let installment = self.installment(i as u32 + 1, date, amount, balance);
+            balance = installment.balance;
+            made.push(installment);
+        }
+        let count = made.len() as u32;
+        let paid_off = balance < 0.005;
+        let next_due = if paid_off {
+            None
+        } else {
+            self.due_date(count + 1)
+        };
+        let payoff = if paid_off {
+            None
+        } else {
+            self.payoff_after(balance, count)
+        };
+        LoanSummary {
+            payments: made,
+            balance: balance.max(0.0),
+            next_due,
+            payoff,
+        }
+    }
+
+    /// The due date of the payment that clears `balance`, paying the
+    /// scheduled amount from payment `made + 1` on.
+    fn payoff_after(&self, mut balance: f64, made: u32) -> Option<NaiveDate> {
+        let payment = self.payment();
+        if payment <= balance * self.monthly_rate() {
+            return None;
+        }
+        let mut number = made;
+        while balance >= 0.005 {
+            number += 1;
+            if number - made > MAX_PAYMENTS {
+                return None;
+            }
+            balance = self
+                .installment(number, self.first_payment, payment, balance)
+                .balance;
+        }
+        self.due_date(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loan(principal: f64, rate: f64, term_months: u32) -> Loan {
+        Loan {
+            name: "Test".to_string(),
+            tag: "test".to_string(),
+            principal,
+            rate,
+            term_months,
+            first_payment: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn schedule_amortizes_at_six_percent() {
+        let loan = loan(10_000.0, 6.0, 12);
+        assert_close(loan.payment(), 860.66);
+
+        let schedule = loan.schedule();
+        assert_eq!(schedule.len(), 12);
+        let first = schedule[0];
+        assert_close(first.interest, 50.0);
+        assert_close(first.principal, 810.66);
+        assert_close(first.balance, 9_189.34);
+        let last = schedule[11];
+        assert_eq!(last.date, NaiveDate::from_ymd_opt(2026, 12, 15).unwrap());
+        assert_close(last.balance, 0.0);
+        let interest = schedule.iter().fold(0.0, |t, p| t + p.interest);
+        assert_close(interest, 327.97);
+
+        let paid: Vec<(NaiveDate, f64)> = schedule[..3]
+            .iter()
+            .map(|p| (p.date, p.payment))
+            .collect();
+        let summary = loan.summarize(&paid);
+        assert_close(summary.balance, schedule[2].balance);
+        assert_eq!(summary.next_due, loan.due_date(4));
+        assert_eq!(summary.payoff, loan.due_date(12));
+    }
+
+    #[test]
+    fn zero_rate_splits_the_principal_evenly() {
+        let loan = loan(1_200.0, 0.0, 12);
+        assert_close(loan.payment(), 100.0);
+
+        let schedule = loan.schedule();
+        assert_eq!(schedule.len(), 12);
+        assert!(schedule.iter().all(|p| p.interest == 0.0));
+        assert_close(schedule[11].balance, 0.0);
+
+        let summary = loan.summarize(&[(loan.first_payment, 1_200.0)]);
+        assert!(summary.paid_off());
+        assert_eq!((summary.next_due, summary.payoff), (None, None));
+    }
+
+    #[test]
+    fn no_payoff_when_the_payment_does_not_cover_the_interest() {
+        // Over 100 years at 2% a month the payment barely beats the
+        // interest, so one short payment leaves it behind for good.
+        let loan = loan(10_000.0, 24.0, 1200);
+        let summary = loan.summarize(&[(loan.first_payment, 100.0)]);
+        assert!(summary.balance > loan.principal);
+        assert!(loan.payment() <= summary.balance * loan.monthly_rate());
+        assert_eq!(summary.payoff, None);
+        assert_eq!(summary.next_due, loan.due_date(2));
+    }
+}
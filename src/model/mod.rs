@@ -1,7 +1,15 @@
 pub mod budget;
 pub mod currency;
 pub mod expense;
+pub mod goal;
+pub mod loan;
+pub mod table;
+pub mod template;
 
-pub use budget::Budget;
+pub use budget::{Budget, Envelope, Period, Rollover};
 pub use currency::Currency;
-pub use expense::{Category, Expense, Recurrence};
+pub use expense::{parse_tags, Category, Expense, Recurrence};
+pub use goal::{Contribution, Goal, GoalProgress, GoalStatus};
+pub use loan::{Loan, LoanSummary};
+pub use table::{Column, SortOrder};
+pub use template::Template;
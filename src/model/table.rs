@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::expense::Expense;
+
+/// A column of the expense table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Id,
+    Date,
+    Amount,
+    Category,
+    Description,
+    Tags,
+    Recurring,
+}
+
+impl Column {
+    pub fn all() -> &'static [Column] {
+        &[
+            Column::Id,
+            Column::Date,
+            Column::Amount,
+            Column::Category,
+            Column::Description,
+            Column::Tags,
+            Column::Recurring,
+        ]
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Column::Id => "ID",
+            Column::Date => "Date",
+            Column::Amount => "Amount",
+            Column::Category => "Category",
+            Column::Description => "Description",
+            Column::Tags => "Tags",
+            Column::Recurring => "Recurring",
+        }
+    }
+
+    /// Name used in the config file.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Column::Id => "id",
+            Column::Date => "date",
+            Column::Amount => "amount",
+            Column::Category => "category",
+            Column::Description => "description",
+            Column::Tags => "tags",
+            Column::Recurring => "recurring",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .find(|c| c.key().eq_ignore_ascii_case(key.trim()))
+            .copied()
+    }
+
+    pub fn compare(&self, a: &Expense, b: &Expense) -> Ordering {
+        match self {
+            Column::Id => a.id.cmp(&b.id),
+            Column::Date => a.date.cmp(&b.date),
+            Column::Amount => a.amount.partial_cmp(&b.amount).unwrap_or(Ordering::Equal),
+            Column::Category => a
+                .category
+                .to_string()
+                .to_lowercase()
+                .cmp(&b.category.to_string().to_lowercase()),
+            Column::Description => a
+                .description
+                .to_lowercase()
+                .cmp(&b.description.to_lowercase()),
+            Column::Tags => a
+                .tags
+                .join(" ")
+                .to_lowercase()
+                .cmp(&b.tags.join(" ").to_lowercase()),
+            Column::Recurring => (a.is_recurring, a.recurrence.map(|r| r.to_index()))
+                .cmp(&(b.is_recurring, b.recurrence.map(|r| r.to_index()))),
+        }
+    }
+
+    pub fn default_layout() -> Vec<Column> {
+        Self::all().to_vec()
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.title())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortOrder {
+    pub column: Column,
+    pub ascending: bool,
+}
+
+impl SortOrder {
+    /// Sort by `self`, falling back to newest first so ties stay stable.
+    pub fn compare(&self, a: &Expense, b: &Expense) -> Ordering {
+        let primary = self.column.compare(a, b);
+        let primary = if self.ascending {
+            primary
+        } else {
+            primary.reverse()
+        };
+        primary
+            .then_with(|| b.date.cmp(&a.date))
+            .then_with(|| b.id.cmp(&a.id))
+    }
+
+    pub fn indicator(&self) -> &'static str {
+        if self.ascending {
+            "▲"
+        } else {
+            "▼"
+        }
+    }
+
+    /// Parse the config form, e.g. `amount desc`.
+    pub fn from_config(value: &str) -> Option<Self> {
+        let mut parts = value.split_whitespace();
+        let column = Column::from_key(parts.next()?)?;
+        let ascending = match parts.next() {
+            None | Some("asc") => true,
+            Some("desc") => false,
+            Some(_) => return None,
+        };
+        Some(Self { column, ascending })
+    }
+
+    pub fn to_config(self) -> String {
+        format!(
+            "{} {}",
+            self.column.key(),
+            if self.ascending { "asc" } else { "desc" }
+        )
+    }
+}
+
+impl Default for SortOrder {
+    fn default() -> Self {
+        Self {
+            column: Column::Date,
+            ascending: false,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use super::expense::{Category, Expense};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// A saved expense used for quick entry. Without an amount, picking it opens
+/// the add form at the amount field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Template {
+    pub name: String,
+    pub amount: Option<f64>,
+    pub category: Category,
+    pub description: String,
+    #[serde(default, with = "super::expense::tags_format")]
+    pub tags: Vec<String>,
+}
+
+impl Template {
+    pub fn from_expense(expense: &Expense) -> Self {
+        let name = if expense.description.is_empty() {
+            expense.category.to_string()
+        } else {
+            expense.description.clone()
+        };
+        Self {
+            name,
+            amount: Some(expense.amount),
+            category: expense.category.clone(),
+            description: expense.description.clone(),
+            tags: expense.tags.clone(),
+        }
+    }
+
+    /// A one-off expense on `date`, if the template has an amount.
+    pub fn to_expense(&self, id: u64, date: NaiveDate) -> Option<Expense> {
+        let amount = self.amount?;
+        Some(
+            Expense::new(
+                id,
+                amount,
+                self.category.clone(),
+                self.description.clone(),
+                date,
+                false,
+                None,
+            )
+            .with_tags(self.tags.clone()),
+        )
+    }
+}
@@ -0,0 +1,174 @@
+use chrono::{Months, NaiveDate};
+
+use crate::model::{Contribution, Goal, Loan};
+use crate::utils;
+
+/// Parse a new savings goal such as `Car 12000 @2027-12-31`: the first
+/// number is the target, `@date` the target date and the other words the
+/// name. Saving starts `today`.
+pub fn parse_goal(input: &str, today: NaiveDate) -> Result<Goal, String> {
+    let mut target = None;
+    let mut date = None;
+    let mut name = Vec::new();
+
+    for word in input.split_whitespace() {
+        if let Some(when) = word.strip_prefix('@') {
+            if date.is_some() {
+                return Err("Only one @date is allowed".to_string());
+            }
+            date = Some(utils::parse_date(&when.replace('_', " "), today)?);
+        } else if target.is_none() && utils::starts_like_amount(word) {
+            target = Some(utils::eval_amount(word)?);
+        } else {
+            name.push(word);
+        }
+    }
+
+    if name.is_empty() {
+        return Err("Name the goal, e.g. Car 12000 @2027-12-31".to_string());
+    }
+    let target = target.ok_or("Add a target amount, e.g. Car 12000 @2027-12-31")?;
+    let target = (target * 100.0).round() / 100.0;
+    if target <= 0.0 {
+        return Err("Target must be positive".to_string());
+    }
+    let target_date = date.ok_or("Add a target date, e.g. @2027-12-31 or @+18m")?;
+    if target_date <= today {
+        return Err("Target date must be in the future".to_string());
+    }
+
+    Ok(Goal {
+        name: name.join(" "),
+        target,
+        target_date,
+        start: Some(today),
+    })
+}
+
+/// Parse a contribution to `goal` such as `250 @-3d bonus`: an amount
+/// (negative for a withdrawal), an optional `@date` and an optional note.
+pub fn parse_contribution(
+    input: &str,
+    goal: &str,
+    today: NaiveDate,
+) -> Result<Contribution, String> {
+    let mut amount = None;
+    let mut date = None;
+    let mut note = Vec::new();
+
+    for word in input.split_whitespace() {
+        if let Some(when) = word.strip_prefix('@') {
+            if date.is_some() {
+                return Err("Only one @date is allowed".to_string());
+            }
+            date = Some(utils::parse_date(&when.replace('_', " "), today)?);
+        } else if amount.is_none() && (utils::starts_like_amount(word) || word.starts_with('-')) {
+            amount = Some(utils::eval_amount(word)?);
+        } else {
+            note.push(word);
+        }
+    }
+
+    let amount = amount.ok_or("Start with an amount, e.g. 250 or -100 for a withdrawal")?;
+    let amount = (amount * 100.0).round() / 100.0;
+    if amount == 0.0 {
+        return Err("Amount can't be zero".to_string());
+    }
+
+    Ok(Contribution {
+        goal: goal.to_string(),
+        date: date.unwrap_or(today),
+        amount,
+        note: note.join(" "),
+    })
+}
+
+/// Parse a new loan such as `Car loan 18000 5.9% 60m @2026-11-15 #carloan`:
+/// the first number is the principal, `5.9%` the yearly rate (default 0),
+/// `60m` or `5y` the term, `@date` the first payment (default a month from
+/// `today`) and `#tag` the tag that marks payments (default the name in
+/// lowercase without spaces). The other words are the name.
+pub fn parse_loan(input: &str, today: NaiveDate) -> Result<Loan, String> {
+    let mut principal = None;
+    let mut rate = None;
+    let mut term = None;
+    let mut date = None;
+    let mut tag = None;
+    let mut name = Vec::new();
+
+    for word in input.split_whitespace() {
+        if let Some(when) = word.strip_prefix('@') {
+            if date.is_some() {
+                return Err("Only one @date is allowed".to_string());
+            }
+            date = Some(utils::parse_date(&when.replace('_', " "), today)?);
+        } else if let Some(t) = word.strip_prefix('#').filter(|t| !t.is_empty()) {
+            tag = Some(t.to_string());
+        } else if let Some(percent) = word.strip_suffix('%') {
+            let value = utils::eval_amount(percent)?;
+            if value < 0.0 {
+                return Err("Rate can't be negative".to_string());
+            }
+            rate = Some(value);
+        } else if let Some(months) = term_months(word) {
+            term = Some(months?);
+        } else if principal.is_none() && utils::starts_like_amount(word) {
+            principal = Some(utils::eval_amount(word)?);
+        } else {
+            name.push(word);
+        }
+    }
+
+    if name.is_empty() {
+        return Err("Name the loan, e.g. Car loan 18000 5.9% 60m".to_string());
+    }
+    let principal = principal.ok_or("Add the amount borrowed, e.g. Car loan 18000 5.9% 60m")?;
+    let principal = (principal * 100.0).round() / 100.0;
+    if principal <= 0.0 {
+        return Err("Amount borrowed must be positive".to_string());
+    }
+    let term_months = term.ok_or("Add a term, e.g. 60m or 5y")?;
+    let first_payment = match date {
+        Some(date) => date,
+        None => today
+            .checked_add_months(Months::new(1))
+            .ok_or("Date is out of range")?,
+    };
+    let name = name.join(" ");
+    let tag = tag.unwrap_or_else(|| {
+        name.chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+            .to_lowercase()
+    });
+
+    Ok(Loan {
+        name,
+        tag,
+        principal,
+        rate: rate.unwrap_or(0.0),
+        term_months,
+        first_payment,
+    })
+}
+
+/// A term such as `60m`, `60mo` or `5y` in months; `None` if the word isn't
+/// one.
+fn term_months(word: &str) -> Option<Result<u32, String>> {
+    let lower = word.to_lowercase();
+    let (number, per) = if let Some(n) = lower.strip_suffix("mo").or(lower.strip_suffix('m')) {
+        (n.to_string(), 1)
+    } else if let Some(n) = lower.strip_suffix('y') {
+        (n.to_string(), 12)
+    } else {
+        return None;
+    };
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let months = number.parse::<u32>().ok().and_then(|n| n.checked_mul(per));
+    Some(match months {
+        Some(months) if (1..=1200).contains(&months) => Ok(months),
+        _ => Err("Term must be between 1 month and 100 years".to_string()),
+    })
+}
@@ -0,0 +1,327 @@
+use std::ops::Bound;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::model::Expense;
+
+/// A parsed search query for the Expenses tab.
+///
+/// Terms are separated by whitespace and must all match. Supported terms:
+///
+/// - `lunch`, `"coffee beans"` -- text in the description or category
+/// - `cat:food`, `desc:rent`, `tag:work` -- field filters
+/// - `amount>20`, `amount<=5.5`, `amount:10..50` -- amount comparisons and ranges
+/// - `date:2026-09`, `date:2026-09..2026-10`, `date>=2026-09-15` -- dates as
+///   `YYYY`, `YYYY-MM` or `YYYY-MM-DD`, ranges may be open (`2026-09..`)
+/// - `recurring:yes` / `recurring:no`
+///
+/// Any term can be negated with a leading `-`, e.g. `-"coffee"` or `-tag:work`.
+#[derive(Debug, Clone, Default)]
+pub struct Query {
+    terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone)]
+struct Term {
+    negated: bool,
+    filter: Filter,
+}
+
+#[derive(Debug, Clone)]
+enum Filter {
+    Text(String),
+    Description(String),
+    Category(String),
+    Tag(String),
+    Amount(Bound<f64>, Bound<f64>),
+    Date(Bound<NaiveDate>, Bound<NaiveDate>),
+    Recurring(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Colon,
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Op::Colon => ":",
+            Op::Eq => "=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+        }
+    }
+}
+
+const FIELDS: &str = "cat, desc, tag, amount, date, recurring";
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let terms = tokenize(input)?
+            .iter()
+            .map(|token| parse_term(token))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { terms })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn matches(&self, expense: &Expense) -> bool {
+        self.terms
+            .iter()
+            .all(|term| term.filter.matches(expense) != term.negated)
+    }
+}
+
+impl Filter {
+    fn matches(&self, expense: &Expense) -> bool {
+        match self {
+            Filter::Text(text) => {
+                contains(&expense.description, text)
+                    || contains(&expense.category.to_string(), text)
+            }
+            Filter::Description(text) => contains(&expense.description, text),
+            Filter::Category(text) => contains(&expense.category.to_string(), text),
+            Filter::Tag(tag) => expense.has_tag(tag),
+            Filter::Amount(lower, upper) => in_bounds(&expense.amount, lower, upper),
+            Filter::Date(lower, upper) => in_bounds(&expense.date, lower, upper),
+            Filter::Recurring(value) => expense.is_recurring == *value,
+        }
+    }
+}
+
+fn contains(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(needle)
+}
+
+fn in_bounds<T: PartialOrd>(value: &T, lower: &Bound<T>, upper: &Bound<T>) -> bool {
+    let above = match lower {
+        Bound::Included(l) => value >= l,
+        Bound::Excluded(l) => value > l,
+        Bound::Unbounded => true,
+    };
+    let below = match upper {
+        Bound::Included(u) => value <= u,
+        Bound::Excluded(u) => value < u,
+        Bound::Unbounded => true,
+    };
+    above && below
+}
+
+/// A whitespace-separated piece of the query. Quotes are kept so that the
+/// term parser can tell `"cat:food"` (plain text) from `cat:food`.
+fn tokenize(input: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err("Unterminated quote".to_string());
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+fn unquote(s: &str) -> String {
+    s.replace('"', "")
+}
+
+fn parse_term(token: &str) -> Result<Term, String> {
+    let (negated, body) = match token.strip_prefix('-') {
+        Some(rest) if !rest.is_empty() => (true, rest),
+        _ => (false, token),
+    };
+
+    let filter = match split_field(body) {
+        Some((field, op, value)) => parse_filter(field, op, value)?,
+        None => {
+            let text = unquote(body).to_lowercase();
+            if text.is_empty() {
+                return Err("Empty search term".to_string());
+            }
+            Filter::Text(text)
+        }
+    };
+
+    Ok(Term { negated, filter })
+}
+
+/// Split `field<op>value` where `field` is a bare word. Returns `None` for
+/// plain text, including anything that starts with a quote.
+fn split_field(body: &str) -> Option<(&str, Op, &str)> {
+    let end = body.find(|c: char| !c.is_ascii_alphabetic())?;
+    if end == 0 {
+        return None;
+    }
+    let (field, rest) = body.split_at(end);
+    let (op, len) = if rest.starts_with("<=") {
+        (Op::Le, 2)
+    } else if rest.starts_with(">=") {
+        (Op::Ge, 2)
+    } else if rest.starts_with('<') {
+        (Op::Lt, 1)
+    } else if rest.starts_with('>') {
+        (Op::Gt, 1)
+    } else if rest.starts_with('=') {
+        (Op::Eq, 1)
+    } else if rest.starts_with(':') {
+        (Op::Colon, 1)
+    } else {
+        return None;
+    };
+    Some((field, op, &rest[len..]))
+}
+
+fn parse_filter(field: &str, op: Op, value: &str) -> Result<Filter, String> {
+    let field = field.to_lowercase();
+    let value = unquote(value);
+    if value.is_empty() {
+        return Err(format!("{}{} needs a value", field, op.symbol()));
+    }
+
+    match field.as_str() {
+        "cat" | "category" => {
+            text_op(&field, op)?;
+            Ok(Filter::Category(value.to_lowercase()))
+        }
+        "desc" | "description" => {
+            text_op(&field, op)?;
+            Ok(Filter::Description(value.to_lowercase()))
+        }
+        "tag" => {
+            text_op(&field, op)?;
+            Ok(Filter::Tag(value.trim_start_matches('#').to_string()))
+        }
+        "recurring" => {
+            text_op(&field, op)?;
+            match value.to_lowercase().as_str() {
+                "yes" | "y" | "true" => Ok(Filter::Recurring(true)),
+                "no" | "n" | "false" => Ok(Filter::Recurring(false)),
+                _ => Err(format!("recurring: expected yes or no, got '{}'", value)),
+            }
+        }
+        "amount" | "amt" => {
+            let (lower, upper) = parse_range(op, &value, |s, _| {
+                s.parse::<f64>()
+                    .map_err(|_| format!("amount: '{}' is not a number", s))
+            })?;
+            Ok(Filter::Amount(lower, upper))
+        }
+        "date" => {
+            let (lower, upper) = parse_range(op, &value, parse_date_bound)?;
+            Ok(Filter::Date(lower, upper))
+        }
+        _ => Err(format!(
+            "Unknown field '{}' (use {}, or quote the text)",
+            field, FIELDS
+        )),
+    }
+}
+
+fn text_op(field: &str, op: Op) -> Result<(), String> {
+    if op == Op::Colon || op == Op::Eq {
+        Ok(())
+    } else {
+        Err(format!("{} only supports ':' (got '{}')", field, op.symbol()))
+    }
+}
+
+/// Parse the value of an ordered field. `parse` receives the text and whether
+/// it is the upper end of the range, so partial dates can expand to the
+/// first or last day of their period.
+fn parse_range<T: Copy>(
+    op: Op,
+    value: &str,
+    parse: impl Fn(&str, bool) -> Result<T, String>,
+) -> Result<(Bound<T>, Bound<T>), String> {
+    match op {
+        Op::Colon | Op::Eq => {
+            if let Some((from, to)) = value.split_once("..") {
+                let lower = if from.is_empty() {
+                    Bound::Unbounded
+                } else {
+                    Bound::Included(parse(from, false)?)
+                };
+                let upper = if to.is_empty() {
+                    Bound::Unbounded
+                } else {
+                    Bound::Included(parse(to, true)?)
+                };
+                Ok((lower, upper))
+            } else {
+                Ok((
+                    Bound::Included(parse(value, false)?),
+                    Bound::Included(parse(value, true)?),
+                ))
+            }
+        }
+        Op::Lt => Ok((Bound::Unbounded, Bound::Excluded(parse(value, false)?))),
+        Op::Le => Ok((Bound::Unbounded, Bound::Included(parse(value, true)?))),
+        Op::Gt => Ok((Bound::Excluded(parse(value, true)?), Bound::Unbounded)),
+        Op::Ge => Ok((Bound::Included(parse(value, false)?), Bound::Unbounded)),
+    }
+}
+
+/// Parse `YYYY`, `YYYY-MM` or `YYYY-MM-DD`, resolving partial dates to the
+/// start or end of the period.
+fn parse_date_bound(s: &str, end: bool) -> Result<NaiveDate, String> {
+    let invalid = || {
+        format!(
+            "date: '{}' is not a valid date (use YYYY, YYYY-MM or YYYY-MM-DD)",
+            s
+        )
+    };
+    let parts: Vec<&str> = s.split('-').collect();
+    let year: i32 = parts[0].parse().map_err(|_| invalid())?;
+
+    let date = match parts.len() {
+        1 => {
+            if end {
+                NaiveDate::from_ymd_opt(year, 12, 31)
+            } else {
+                NaiveDate::from_ymd_opt(year, 1, 1)
+            }
+        }
+        2 => {
+            let month: u32 = parts[1].parse().map_err(|_| invalid())?;
+            let first = NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(invalid)?;
+            if end {
+                let (y, m) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+                NaiveDate::from_ymd_opt(y, m, 1).and_then(|d| d.pred_opt())
+            } else {
+                Some(first)
+            }
+        }
+        3 => NaiveDate::parse_from_str(s, "%Y-%m-%d").ok(),
+        _ => None,
+    };
+
+    date.filter(|d| d.year() == year).ok_or_else(invalid)
+}
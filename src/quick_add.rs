@@ -0,0 +1,69 @@
+use chrono::NaiveDate;
+
+use crate::model::{Category, Expense};
+use crate::utils;
+
+/// Parse a one-line entry such as `12.50 food lunch with team @yesterday #work`
+/// into an expense. Used by the `:` prompt and `cashflow add`.
+///
+/// - the first word that is a number or arithmetic (`45.80/3`) is the amount
+/// - the first other word naming a category (`food`) is the category, or use
+///   `cat:Name` for any category; anything else is `Other`
+/// - `@date` takes anything the date field accepts (`@-3d`, `@mon`), with `_`
+///   for spaces (`@last_fri`); the default is today
+/// - `#tag` adds a tag
+/// - the remaining words are the description
+pub fn parse(input: &str, id: u64, today: NaiveDate) -> Result<Expense, String> {
+    let mut amount = None;
+    let mut category = None;
+    let mut date = None;
+    let mut tags = Vec::new();
+    let mut description = Vec::new();
+
+    for word in input.split_whitespace() {
+        if let Some(when) = word.strip_prefix('@') {
+            if date.is_some() {
+                return Err("Only one @date is allowed".to_string());
+            }
+            date = Some(utils::parse_date(&when.replace('_', " "), today)?);
+        } else if let Some(tag) = word.strip_prefix('#') {
+            if !tag.is_empty() && !tags.iter().any(|t: &String| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.to_string());
+            }
+        } else if let Some(name) = word.strip_prefix("cat:") {
+            category = Some(category_named(name).unwrap_or(Category::Other(name.to_string())));
+        } else if amount.is_none() && utils::starts_like_amount(word) {
+            amount = Some(utils::eval_amount(word)?);
+        } else if let (None, Some(named)) = (&category, category_named(word)) {
+            category = Some(named);
+        } else {
+            description.push(word);
+        }
+    }
+
+    let amount = amount.ok_or("Start with an amount, e.g. 12.50 food lunch")?;
+    let amount = (amount * 100.0).round() / 100.0;
+    if amount <= 0.0 {
+        return Err("Amount must be positive".to_string());
+    }
+
+    Ok(Expense::new(
+        id,
+        amount,
+        category.unwrap_or(Category::Other(String::new())),
+        description.join(" "),
+        date.unwrap_or(today),
+        false,
+        None,
+    )
+    .with_tags(tags))
+}
+
+/// A built-in category by name, ignoring case.
+fn category_named(name: &str) -> Option<Category> {
+    Category::all_display_names()
+        .iter()
+        .take(9)
+        .position(|n| n.eq_ignore_ascii_case(name))
+        .map(|i| Category::from_index(i, None))
+}
@@ -0,0 +1,187 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+use super::csv_store::data_dir;
+use crate::model::{Column, Currency, SortOrder};
+
+/// User preferences stored in `~/.cashflow/config` as `key = value` lines.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub currency: Currency,
+    pub columns: Vec<Column>,
+    pub sort: SortOrder,
+    pub mouse: bool,
+    pub theme: String,
+    /// Most the whole ledger may spend in a month, across all categories.
+    pub monthly_cap: Option<f64>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            currency: Currency::default(),
+            columns: Column::default_layout(),
+            sort: SortOrder::default(),
+            mouse: true,
+            theme: "dark".to_string(),
+            monthly_cap: None,
+        }
+    }
+}
+
+impl Config {
+    fn parse(content: &str) -> Self {
+        let mut config = Config::default();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                // Older versions stored only the currency code.
+                if let Some(currency) = Currency::from_code(line) {
+                    config.currency = currency;
+                }
+                continue;
+            };
+
+            let value = value.trim();
+            match key.trim() {
+                "currency" => {
+                    config.currency = Currency::from_code(value).unwrap_or_default();
+                }
+                "columns" => {
+                    let mut columns: Vec<Column> = Vec::new();
+                    for column in value.split(',').filter_map(Column::from_key) {
+                        if !columns.contains(&column) {
+                            columns.push(column);
+                        }
+                    }
+                    if !columns.is_empty() {
+                        config.columns = columns;
+                    }
+                }
+                "sort" => {
+                    if let Some(sort) = SortOrder::from_config(value) {
+                        config.sort = sort;
+                    }
+                }
+                "theme" if !value.is_empty() => config.theme = value.to_string(),
+                "mouse" => {
+                    if let Some(mouse) = parse_bool(value) {
+                        config.mouse = mouse;
+                    }
+                }
+                "monthly_cap" => {
+                    config.monthly_cap = value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|cap| cap.is_finite() && *cap > 0.0);
+                }
+                _ => {}
+            }
+        }
+
+        config
+    }
+
+    fn render(&self) -> String {
+        let columns: Vec<&str> = self.columns.iter().map(|c| c.key()).collect();
+        let mut content = format!(
+            "currency = {}\ncolumns = {}\nsort = {}\nmouse = {}\ntheme = {}\n",
+            self.currency.code(),
+            columns.join(","),
+            self.sort.to_config(),
+            self.mouse,
+            self.theme
+        );
+        if let Some(cap) = self.monthly_cap {
+            content.push_str(&format!("monthly_cap = {}\n", cap));
+        }
+        content
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "on" => Some(true),
+        "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+fn config_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("config"))
+}
+
+pub fn load_config() -> Result<Config> {
+    let path = config_path()?;
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Could not read config at {}", path.display()))?;
+    Ok(Config::parse(&content))
+}
+
+pub fn save_config(config: &Config) -> Result<()> {
+    let path = config_path()?;
+    fs::write(&path, config.render())
+        .with_context(|| format!("Could not write config to {}", path.display()))?;
+    Ok(())
+}
+
+fn keymap_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("keymap"))
+}
+
+/// Contents of `~/.cashflow/keymap`, if the user has created one.
+pub fn load_keymap() -> Result<Option<String>> {
+    let path = keymap_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Could not read keymap at {}", path.display()))?;
+    Ok(Some(content))
+}
+
+fn themes_dir() -> Result<PathBuf> {
+    Ok(data_dir()?.join("themes"))
+}
+
+/// Contents of `~/.cashflow/themes/<name>.theme`, if it exists.
+pub fn load_theme_file(name: &str) -> Result<Option<String>> {
+    let path = themes_dir()?.join(format!("{}.theme", name));
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Could not read theme at {}", path.display()))?;
+    Ok(Some(content))
+}
+
+/// Names of the user theme files, sorted.
+pub fn list_theme_files() -> Result<Vec<String>> {
+    let dir = themes_dir()?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut names: Vec<String> = fs::read_dir(&dir)
+        .with_context(|| format!("Could not read {}", dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? == "theme" {
+                Some(path.file_stem()?.to_string_lossy().into_owned())
+            } else {
+                None
+            }
+        })
+        .collect();
+    names.sort();
+    Ok(names)
+}
@@ -3,9 +3,9 @@ use chrono::Local;
 use std::fs;
 use std::path::PathBuf;
 
-use crate::model::{Budget, Currency, Expense};
+use crate::model::{Budget, Contribution, Expense, Goal, Loan, Template};
 
-fn data_dir() -> Result<PathBuf> {
+pub(super) fn data_dir() -> Result<PathBuf> {
     let home = dirs::home_dir().context("Could not determine home directory")?;
     let dir = home.join(".cashflow");
     if !dir.exists() {
@@ -22,6 +22,22 @@ fn budgets_path() -> Result<PathBuf> {
     Ok(data_dir()?.join("budgets.csv"))
 }
 
+fn templates_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("templates.csv"))
+}
+
+fn goals_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("goals.csv"))
+}
+
+fn contributions_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("contributions.csv"))
+}
+
+fn loans_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("loans.csv"))
+}
+
 pub fn load_expenses() -> Result<Vec<Expense>> {
     let path = expenses_path()?;
     if !path.exists() {
@@ -88,6 +104,135 @@ pub fn save_budgets(budgets: &[Budget]) -> Result<()> {
     Ok(())
 }
 
+pub fn load_templates() -> Result<Vec<Template>> {
+    let path = templates_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut reader = csv::Reader::from_path(&path)
+        .with_context(|| format!("Could not open {}", path.display()))?;
+
+    let mut templates = Vec::new();
+    for result in reader.deserialize() {
+        let template: Template = result.context("Could not parse template record")?;
+        templates.push(template);
+    }
+
+    Ok(templates)
+}
+
+pub fn save_templates(templates: &[Template]) -> Result<()> {
+    let path = templates_path()?;
+    let mut writer = csv::Writer::from_path(&path)
+        .with_context(|| format!("Could not write to {}", path.display()))?;
+
+    for template in templates {
+        writer
+            .serialize(template)
+            .context("Could not serialize template")?;
+    }
+
+    writer.flush().context("Could not flush CSV writer")?;
+    Ok(())
+}
+
+pub fn load_goals() -> Result<Vec<Goal>> {
+    let path = goals_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut reader = csv::Reader::from_path(&path)
+        .with_context(|| format!("Could not open {}", path.display()))?;
+
+    let mut goals = Vec::new();
+    for result in reader.deserialize() {
+        let goal: Goal = result.context("Could not parse goal record")?;
+        goals.push(goal);
+    }
+
+    Ok(goals)
+}
+
+pub fn save_goals(goals: &[Goal]) -> Result<()> {
+    let path = goals_path()?;
+    let mut writer = csv::Writer::from_path(&path)
+        .with_context(|| format!("Could not write to {}", path.display()))?;
+
+    for goal in goals {
+        writer.serialize(goal).context("Could not serialize goal")?;
+    }
+
+    writer.flush().context("Could not flush CSV writer")?;
+    Ok(())
+}
+
+pub fn load_contributions() -> Result<Vec<Contribution>> {
+    let path = contributions_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut reader = csv::Reader::from_path(&path)
+        .with_context(|| format!("Could not open {}", path.display()))?;
+
+    let mut contributions = Vec::new();
+    for result in reader.deserialize() {
+        let contribution: Contribution =
+            result.context("Could not parse contribution record")?;
+        contributions.push(contribution);
+    }
+
+    Ok(contributions)
+}
+
+pub fn save_contributions(contributions: &[Contribution]) -> Result<()> {
+    let path = contributions_path()?;
+    let mut writer = csv::Writer::from_path(&path)
+        .with_context(|| format!("Could not write to {}", path.display()))?;
+
+    for contribution in contributions {
+        writer
+            .serialize(contribution)
+            .context("Could not serialize contribution")?;
+    }
+
+    writer.flush().context("Could not flush CSV writer")?;
+    Ok(())
+}
+
+pub fn load_loans() -> Result<Vec<Loan>> {
+    let path = loans_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut reader = csv::Reader::from_path(&path)
+        .with_context(|| format!("Could not open {}", path.display()))?;
+
+    let mut loans = Vec::new();
+    for result in reader.deserialize() {
+        let loan: Loan = result.context("Could not parse loan record")?;
+        loans.push(loan);
+    }
+
+    Ok(loans)
+}
+
+pub fn save_loans(loans: &[Loan]) -> Result<()> {
+    let path = loans_path()?;
+    let mut writer = csv::Writer::from_path(&path)
+        .with_context(|| format!("Could not write to {}", path.display()))?;
+
+    for loan in loans {
+        writer.serialize(loan).context("Could not serialize loan")?;
+    }
+
+    writer.flush().context("Could not flush CSV writer")?;
+    Ok(())
+}
+
 pub fn export_expenses(expenses: &[Expense]) -> Result<String> {
     let dir = data_dir()?;
     let timestamp = Local::now().format("%Y%m%d_%H%M%S");
@@ -111,13 +256,12 @@ pub fn import_csv(path: &str, existing: &mut Vec<Expense>) -> Result<usize> {
     let mut reader = csv::Reader::from_path(path)
         .with_context(|| format!("Could not open import file: {}", path))?;
 
-    let mut next = next_id(existing);
+    let first = next_id(existing);
     let mut count = 0;
 
-    for result in reader.deserialize() {
+    for (offset, result) in reader.deserialize().enumerate() {
         let mut expense: Expense = result.context("Could not parse import record")?;
-        expense.id = next;
-        next += 1;
+        expense.id = first + offset as u64;
         existing.push(expense);
         count += 1;
     }
@@ -128,25 +272,3 @@ pub fn import_csv(path: &str, existing: &mut Vec<Expense>) -> Result<usize> {
 pub fn next_id(expenses: &[Expense]) -> u64 {
     expenses.iter().map(|e| e.id).max().unwrap_or(0) + 1
 }
-
-fn config_path() -> Result<PathBuf> {
-    Ok(data_dir()?.join("config"))
-}
-
-pub fn load_currency() -> Result<Currency> {
-    let path = config_path()?;
-    if !path.exists() {
-        return Ok(Currency::default());
-    }
-    let content = fs::read_to_string(&path)
-        .with_context(|| format!("Could not read config at {}", path.display()))?;
-    let code = content.trim();
-    Ok(Currency::from_code(code).unwrap_or_default())
-}
-
-pub fn save_currency(currency: &Currency) -> Result<()> {
-    let path = config_path()?;
-    fs::write(&path, currency.code())
-        .with_context(|| format!("Could not write config to {}", path.display()))?;
-    Ok(())
-}
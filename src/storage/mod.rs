@@ -1,3 +1,5 @@
+pub mod config_store;
 pub mod csv_store;
 
+pub use config_store::*;
 pub use csv_store::*;
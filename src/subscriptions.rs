@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use crate::model::{Category, Expense, Recurrence};
+
+/// Fewest charges that count as a repeating pattern.
+const MIN_CHARGES: usize = 3;
+
+/// The most a detected series' price may vary, as highest over lowest.
+const MAX_PRICE_SPREAD: f64 = 1.5;
+
+/// A recurring series: the recurring expense that defines it and every
+/// charge it has made.
+#[derive(Debug, Clone)]
+pub struct Subscription {
+    /// Id of the recurring expense.
+    pub id: u64,
+    pub name: String,
+    pub category: Category,
+    pub recurrence: Recurrence,
+    /// The price of new charges, which may not have taken effect yet.
+    pub amount: f64,
+    /// Every charge, oldest first, including one already recorded for a
+    /// price change ahead.
+    pub charges: Vec<(NaiveDate, f64)>,
+    pub cancelled: Option<NaiveDate>,
+    /// The next charge due after today, unless the series is cancelled by
+    /// then.
+    pub next_charge: Option<NaiveDate>,
+}
+
+impl Subscription {
+    pub fn monthly_cost(&self) -> f64 {
+        self.yearly_cost() / 12.0
+    }
+
+    pub fn yearly_cost(&self) -> f64 {
+        self.amount * self.recurrence.per_year()
+    }
+
+    /// Still charging after `today`.
+    pub fn is_active(&self, today: NaiveDate) -> bool {
+        self.cancelled.is_none_or(|from| from > today)
+    }
+
+    pub fn since(&self) -> Option<NaiveDate> {
+        self.charges.first().map(|(date, _)| *date)
+    }
+
+    /// Each price the series has charged with the day it started, oldest
+    /// first.
+    pub fn price_changes(&self) -> Vec<(NaiveDate, f64)> {
+        let mut changes: Vec<(NaiveDate, f64)> = Vec::new();
+        for &(date, amount) in &self.charges {
+            if changes
+                .last()
+                .is_none_or(|(_, price)| (price - amount).abs() >= 0.005)
+            {
+                changes.push((date, amount));
+            }
+        }
+        changes
+    }
+}
+
+/// Charges that repeat on a schedule but aren't marked recurring, e.g. from
+/// imported bank data.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub description: String,
+    pub category: Category,
+    pub recurrence: Recurrence,
+    /// Every charge, oldest first.
+    pub charges: Vec<(NaiveDate, f64)>,
+    /// Id of the latest charge, which becomes the recurring expense when
+    /// the series is marked.
+    pub latest_id: u64,
+}
+
+impl Candidate {
+    pub fn amount(&self) -> f64 {
+        self.charges
+            .last()
+            .map(|(_, amount)| *amount)
+            .unwrap_or(0.0)
+    }
+
+    pub fn monthly_cost(&self) -> f64 {
+        self.amount() * self.recurrence.per_year() / 12.0
+    }
+}
+
+/// Whether `expense` is a charge of the series `template` defines. The
+/// amount isn't compared, so a series keeps its history across price
+/// changes.
+pub fn in_series(template: &Expense, expense: &Expense) -> bool {
+    expense.description == template.description && expense.category == template.category
+}
+
+/// Every recurring series, biggest monthly cost first.
+pub fn subscriptions(expenses: &[Expense], today: NaiveDate) -> Vec<Subscription> {
+    let mut subscriptions: Vec<Subscription> = expenses
+        .iter()
+        .filter(|e| e.is_recurring)
+        .filter_map(|template| {
+            let recurrence = template.recurrence?;
+            let mut charges: Vec<(NaiveDate, f64)> = expenses
+                .iter()
+                .filter(|e| in_series(template, e))
+                .map(|e| (e.date, e.amount))
+                .collect();
+            charges.sort_by_key(|(date, _)| *date);
+            let last = charges
+                .last()
+                .map(|(date, _)| *date)
+                .unwrap_or(template.date);
+            let next = charges
+                .iter()
+                .map(|(date, _)| *date)
+                .find(|date| *date > today)
+                .unwrap_or_else(|| recurrence.next_date(last));
+            let name = if template.description.is_empty() {
+                template.category.to_string()
+            } else {
+                template.description.clone()
+            };
+            Some(Subscription {
+                id: template.id,
+                name,
+                category: template.category.clone(),
+                recurrence,
+                amount: template.amount,
+                charges,
+                cancelled: template.cancelled,
+                next_charge: template
+                    .cancelled
+                    .is_none_or(|from| next < from)
+                    .then_some(next),
+            })
+        })
+        .collect();
+    subscriptions.sort_by(|a, b| {
+        b.monthly_cost()
+            .total_cmp(&a.monthly_cost())
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    subscriptions
+}
+
+/// Unmarked expenses with the same description and category, charged at
+/// least three times at a similar price and a week, a month or a year
+/// apart, that haven't stopped well before `today`. Most recent first.
+pub fn detect(expenses: &[Expense], today: NaiveDate) -> Vec<Candidate> {
+    let templates: Vec<&Expense> = expenses.iter().filter(|e| e.is_recurring).collect();
+    let mut groups: HashMap<(String, String), Vec<&Expense>> = HashMap::new();
+    for e in expenses {
+        if e.description.trim().is_empty() || templates.iter().any(|t| in_series(t, e)) {
+            continue;
+        }
+        let key = (e.description.trim().to_lowercase(), e.category.to_string());
+        groups.entry(key).or_default().push(e);
+    }
+
+    let mut candidates: Vec<Candidate> = groups
+        .into_values()
+        .filter(|group| group.len() >= MIN_CHARGES)
+        .filter_map(|mut group| {
+            group.sort_by_key(|e| (e.date, e.id));
+            let gaps: Vec<i64> = group
+                .windows(2)
+                .map(|w| (w[1].date - w[0].date).num_days())
+                .collect();
+            let recurrence = [Recurrence::Weekly, Recurrence::Monthly, Recurrence::Yearly]
+                .into_iter()
+                .find(|r| gaps.iter().all(|gap| fits(*r, *gap)))?;
+            let (low, high) = group.iter().fold((f64::MAX, 0.0_f64), |(low, high), e| {
+                (low.min(e.amount), high.max(e.amount))
+            });
+            if low <= 0.0 || high / low > MAX_PRICE_SPREAD {
+                return None;
+            }
+            let latest = group[group.len() - 1];
+            // Ended long ago: the next two charges would have been due.
+            let next = recurrence.next_date(recurrence.next_date(latest.date));
+            if next < today {
+                return None;
+            }
+            Some(Candidate {
+                description: latest.description.clone(),
+                category: latest.category.clone(),
+                recurrence,
+                charges: group.iter().map(|e| (e.date, e.amount)).collect(),
+                latest_id: latest.id,
+            })
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.charges
+            .last()
+            .map(|c| c.0)
+            .cmp(&a.charges.last().map(|c| c.0))
+            .then_with(|| a.description.cmp(&b.description))
+    });
+    candidates
+}
+
+/// Whether two charges `gap` days apart fit the schedule, with some slack
+/// for months of different lengths and charges taken a few days late.
+fn fits(recurrence: Recurrence, gap: i64) -> bool {
+    match recurrence {
+        Recurrence::Daily => gap == 1,
+        Recurrence::Weekly => (6..=8).contains(&gap),
+        Recurrence::Monthly => (26..=35).contains(&gap),
+        Recurrence::Yearly => (355..=375).contains(&gap),
+    }
+}
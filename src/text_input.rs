@@ -0,0 +1,185 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use unicode_width::UnicodeWidthChar;
+
+/// Decides which typed or pasted characters an input accepts.
+pub type CharFilter = fn(char) -> bool;
+
+/// A single line of editable text with a cursor.
+///
+/// The cursor is a char index, so it always sits on a character boundary.
+/// Supported keys: `←`/`→`, `Home`/`End` (or `Ctrl+A`/`Ctrl+E`),
+/// `Ctrl+←`/`Ctrl+→` (or `Alt+B`/`Alt+F`) by word, `Backspace`/`Del`,
+/// `Ctrl+W` or `Alt+Backspace` to delete the previous word, `Ctrl+U` to
+/// delete to the start and `Ctrl+K` to delete to the end.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextInput {
+    value: String,
+    cursor: usize,
+}
+
+impl TextInput {
+    pub fn new(value: impl Into<String>) -> Self {
+        let value = value.into();
+        let cursor = value.chars().count();
+        Self { value, cursor }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn len(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(char_index)
+            .map(|(i, _)| i)
+            .unwrap_or(self.value.len())
+    }
+
+    pub fn insert(&mut self, c: char) {
+        let at = self.byte_index(self.cursor);
+        self.value.insert(at, c);
+        self.cursor += 1;
+    }
+
+    /// Insert pasted text, dropping line breaks and anything `accept` rejects.
+    pub fn insert_str(&mut self, text: &str, accept: impl Fn(char) -> bool) {
+        for c in text.chars() {
+            if c == '\n' || c == '\r' {
+                continue;
+            }
+            let c = if c == '\t' { ' ' } else { c };
+            if accept(c) {
+                self.insert(c);
+            }
+        }
+    }
+
+    /// Remove the chars in `from..to` and leave the cursor at `from`.
+    fn delete_range(&mut self, from: usize, to: usize) {
+        let (start, end) = (self.byte_index(from), self.byte_index(to));
+        self.value.replace_range(start..end, "");
+        self.cursor = from;
+    }
+
+    fn word_start_before(&self) -> usize {
+        let chars: Vec<char> = self.value.chars().collect();
+        let mut i = self.cursor;
+        while i > 0 && chars[i - 1].is_whitespace() {
+            i -= 1;
+        }
+        while i > 0 && !chars[i - 1].is_whitespace() {
+            i -= 1;
+        }
+        i
+    }
+
+    fn word_end_after(&self) -> usize {
+        let chars: Vec<char> = self.value.chars().collect();
+        let mut i = self.cursor;
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+        while i < chars.len() && !chars[i].is_whitespace() {
+            i += 1;
+        }
+        i
+    }
+
+    /// Apply an editing key. Characters are only inserted if `accept` allows
+    /// them. Returns `false` for keys the input doesn't use, so the caller
+    /// can handle them.
+    pub fn handle_key(
+        &mut self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+        accept: impl Fn(char) -> bool,
+    ) -> bool {
+        let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+        let alt = modifiers.contains(KeyModifiers::ALT);
+
+        match code {
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.len(),
+            KeyCode::Char('w') if ctrl => self.delete_range(self.word_start_before(), self.cursor),
+            KeyCode::Char('u') if ctrl => self.delete_range(0, self.cursor),
+            KeyCode::Char('k') if ctrl => {
+                let end = self.len();
+                let cursor = self.cursor;
+                self.delete_range(cursor, end);
+            }
+            KeyCode::Char('b') if alt => self.cursor = self.word_start_before(),
+            KeyCode::Char('f') if alt => self.cursor = self.word_end_after(),
+            KeyCode::Char(_) if ctrl || alt => return false,
+            KeyCode::Char(c) => {
+                if accept(c) {
+                    self.insert(c);
+                }
+            }
+            KeyCode::Backspace if ctrl || alt => {
+                self.delete_range(self.word_start_before(), self.cursor)
+            }
+            KeyCode::Backspace => {
+                if self.cursor > 0 {
+                    self.delete_range(self.cursor - 1, self.cursor);
+                }
+            }
+            KeyCode::Delete => {
+                if self.cursor < self.len() {
+                    self.delete_range(self.cursor, self.cursor + 1);
+                }
+            }
+            KeyCode::Left if ctrl || alt => self.cursor = self.word_start_before(),
+            KeyCode::Right if ctrl || alt => self.cursor = self.word_end_after(),
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.len()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.len(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// The part of the text that fits in `width` columns, scrolled so the
+    /// cursor stays visible, and the cursor's column within it.
+    pub fn view(&self, width: u16) -> (String, u16) {
+        let width = width as usize;
+        let chars: Vec<(char, usize)> = self
+            .value
+            .chars()
+            .map(|c| (c, c.width().unwrap_or(0)))
+            .collect();
+
+        // Drop chars from the front until the cursor (plus one cell for it)
+        // fits.
+        let mut start = 0;
+        let mut cursor_col: usize = chars[..self.cursor].iter().map(|(_, w)| w).sum();
+        while start < self.cursor && cursor_col + 1 > width {
+            cursor_col -= chars[start].1;
+            start += 1;
+        }
+
+        let mut used = 0;
+        let mut visible = String::new();
+        for (c, w) in &chars[start..] {
+            if used + w > width {
+                break;
+            }
+            used += w;
+            visible.push(*c);
+        }
+        (visible, cursor_col as u16)
+    }
+}
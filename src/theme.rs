@@ -0,0 +1,399 @@
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::model::Category;
+
+/// Semantic colors used by every view. Built-in themes are defined below;
+/// user themes live in `~/.cashflow/themes/<name>.theme` and start from a
+/// built-in `base`, overriding individual keys.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub name: String,
+    pub text: Color,
+    pub muted: Color,
+    pub border: Color,
+    pub border_muted: Color,
+    pub accent: Color,
+    pub heading: Color,
+    pub positive: Color,
+    pub negative: Color,
+    pub tag: Color,
+    pub selection_bg: Color,
+    pub marked_bg: Color,
+    pub range_bg: Color,
+    /// Category colors, indexed by `Category::to_index`.
+    pub palette: Vec<Color>,
+    /// Heatmap shades from light to heavy spending.
+    pub heatmap: Vec<Color>,
+    pub category_colors: Vec<(Category, Color)>,
+    monochrome: bool,
+}
+
+pub const BUILTIN_THEMES: &[&str] = &["dark", "light", "high-contrast", "colorblind"];
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            text: Color::White,
+            muted: Color::DarkGray,
+            border: Color::Cyan,
+            border_muted: Color::DarkGray,
+            accent: Color::Yellow,
+            heading: Color::Cyan,
+            positive: Color::Green,
+            negative: Color::Red,
+            tag: Color::Magenta,
+            selection_bg: Color::DarkGray,
+            marked_bg: Color::Blue,
+            range_bg: Color::Indexed(17),
+            palette: vec![
+                Color::Green,
+                Color::Yellow,
+                Color::Blue,
+                Color::Red,
+                Color::Magenta,
+                Color::Cyan,
+                Color::LightGreen,
+                Color::LightYellow,
+                Color::LightBlue,
+                Color::LightRed,
+            ],
+            heatmap: vec![
+                Color::Indexed(22),
+                Color::Indexed(28),
+                Color::Indexed(34),
+                Color::Indexed(46),
+            ],
+            category_colors: Vec::new(),
+            monochrome: false,
+        }
+    }
+
+    /// For terminals with a light background: no white or yellow text.
+    pub fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            text: Color::Black,
+            muted: Color::DarkGray,
+            border: Color::Blue,
+            border_muted: Color::Gray,
+            accent: Color::Magenta,
+            heading: Color::Blue,
+            positive: Color::Green,
+            negative: Color::Red,
+            tag: Color::Magenta,
+            selection_bg: Color::Gray,
+            marked_bg: Color::LightCyan,
+            range_bg: Color::LightBlue,
+            palette: vec![
+                Color::Green,
+                Color::Blue,
+                Color::Magenta,
+                Color::Red,
+                Color::Cyan,
+                Color::Indexed(130),
+                Color::Indexed(22),
+                Color::Indexed(54),
+                Color::Indexed(24),
+                Color::Indexed(88),
+            ],
+            heatmap: vec![
+                Color::Indexed(151),
+                Color::Indexed(114),
+                Color::Indexed(34),
+                Color::Indexed(22),
+            ],
+            category_colors: Vec::new(),
+            monochrome: false,
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast".to_string(),
+            text: Color::White,
+            muted: Color::Gray,
+            border: Color::White,
+            border_muted: Color::Gray,
+            accent: Color::LightYellow,
+            heading: Color::LightCyan,
+            positive: Color::LightGreen,
+            negative: Color::LightRed,
+            tag: Color::LightMagenta,
+            selection_bg: Color::Blue,
+            marked_bg: Color::Magenta,
+            range_bg: Color::Indexed(54),
+            palette: vec![
+                Color::LightGreen,
+                Color::LightYellow,
+                Color::LightBlue,
+                Color::LightRed,
+                Color::LightMagenta,
+                Color::LightCyan,
+                Color::White,
+                Color::Indexed(214),
+                Color::Indexed(123),
+                Color::Indexed(218),
+            ],
+            heatmap: vec![
+                Color::Indexed(28),
+                Color::Indexed(34),
+                Color::Indexed(40),
+                Color::Indexed(46),
+            ],
+            category_colors: Vec::new(),
+            monochrome: false,
+        }
+    }
+
+    /// Okabe-Ito palette, distinguishable with the common forms of color
+    /// blindness. Good/bad use blue/orange instead of green/red.
+    pub fn colorblind() -> Self {
+        Self {
+            name: "colorblind".to_string(),
+            text: Color::White,
+            muted: Color::DarkGray,
+            border: Color::Rgb(86, 180, 233),
+            border_muted: Color::DarkGray,
+            accent: Color::Rgb(240, 228, 66),
+            heading: Color::Rgb(86, 180, 233),
+            positive: Color::Rgb(86, 180, 233),
+            negative: Color::Rgb(230, 159, 0),
+            tag: Color::Rgb(204, 121, 167),
+            selection_bg: Color::DarkGray,
+            marked_bg: Color::Rgb(0, 114, 178),
+            range_bg: Color::Indexed(17),
+            palette: vec![
+                Color::Rgb(230, 159, 0),
+                Color::Rgb(86, 180, 233),
+                Color::Rgb(0, 158, 115),
+                Color::Rgb(240, 228, 66),
+                Color::Rgb(0, 114, 178),
+                Color::Rgb(213, 94, 0),
+                Color::Rgb(204, 121, 167),
+                Color::Rgb(153, 153, 153),
+                Color::Rgb(255, 255, 255),
+                Color::Rgb(128, 96, 0),
+            ],
+            heatmap: vec![
+                Color::Rgb(0, 60, 100),
+                Color::Rgb(0, 114, 178),
+                Color::Rgb(86, 180, 233),
+                Color::Rgb(200, 232, 250),
+            ],
+            category_colors: Vec::new(),
+            monochrome: false,
+        }
+    }
+
+    /// Used when `NO_COLOR` is set: terminal default colors only, with
+    /// selection shown through text attributes.
+    pub fn monochrome() -> Self {
+        Self {
+            name: "no-color".to_string(),
+            text: Color::Reset,
+            muted: Color::Reset,
+            border: Color::Reset,
+            border_muted: Color::Reset,
+            accent: Color::Reset,
+            heading: Color::Reset,
+            positive: Color::Reset,
+            negative: Color::Reset,
+            tag: Color::Reset,
+            selection_bg: Color::Reset,
+            marked_bg: Color::Reset,
+            range_bg: Color::Reset,
+            palette: vec![Color::Reset],
+            heatmap: vec![Color::Reset; 4],
+            category_colors: Vec::new(),
+            monochrome: true,
+        }
+    }
+
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "colorblind" => Some(Self::colorblind()),
+            _ => None,
+        }
+    }
+
+    /// Parse a theme file. Unknown keys and bad colors are reported so a
+    /// typo doesn't silently fall back to the base color.
+    pub fn from_config(name: &str, content: &str) -> Result<Self, String> {
+        let mut theme = Self::dark();
+        let mut customized = false;
+
+        for (n, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(format!("line {}: expected 'key = color'", n + 1));
+            };
+            let (key, value) = (key.trim(), value.trim());
+            let err = |e: String| format!("line {}: {}", n + 1, e);
+
+            if key == "base" {
+                // A base replaces every color, so one set before it would be lost.
+                if customized {
+                    return Err(err("'base' must be the first key".to_string()));
+                }
+                theme = Self::builtin(value)
+                    .ok_or_else(|| err(format!("unknown base theme '{}'", value)))?;
+                customized = true;
+                continue;
+            }
+            customized = true;
+            if key == "palette" || key == "heatmap" {
+                let colors = value
+                    .split(',')
+                    .map(parse_color)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(err)?;
+                if colors.is_empty() {
+                    return Err(err(format!("{} needs at least one color", key)));
+                }
+                if key == "palette" {
+                    theme.palette = colors;
+                } else {
+                    theme.heatmap = colors;
+                }
+                continue;
+            }
+            if let Some(category) = key.strip_prefix("category.") {
+                let color = parse_color(value).map_err(err)?;
+                theme
+                    .category_colors
+                    .push((Category::from_str_value(category), color));
+                continue;
+            }
+
+            let color = parse_color(value).map_err(err)?;
+            let slot = match key {
+                "text" => &mut theme.text,
+                "muted" => &mut theme.muted,
+                "border" => &mut theme.border,
+                "border_muted" => &mut theme.border_muted,
+                "accent" => &mut theme.accent,
+                "heading" => &mut theme.heading,
+                "positive" => &mut theme.positive,
+                "negative" => &mut theme.negative,
+                "tag" => &mut theme.tag,
+                "selection_bg" => &mut theme.selection_bg,
+                "marked_bg" => &mut theme.marked_bg,
+                "range_bg" => &mut theme.range_bg,
+                _ => return Err(err(format!("unknown key '{}'", key))),
+            };
+            *slot = color;
+        }
+
+        theme.name = name.to_string();
+        Ok(theme)
+    }
+
+    /// The same category always gets the same color, whatever its rank.
+    pub fn category_color(&self, category: &Category) -> Color {
+        if let Some((_, color)) = self.category_colors.iter().find(|(c, _)| c == category) {
+            return *color;
+        }
+        let index = match category {
+            // Spread custom categories over the palette by name.
+            Category::Other(name) if !name.is_empty() => {
+                9 + name.bytes().map(|b| b as usize).sum::<usize>()
+            }
+            _ => category.to_index(),
+        };
+        self.palette[index % self.palette.len()]
+    }
+
+    pub fn category_color_by_name(&self, name: &str) -> Color {
+        self.category_color(&Category::from_str_value(name))
+    }
+
+    /// Glyph and style for a heatmap cell. Level 0 is a day without
+    /// spending; levels 1 to `heatmap.len()` go from light to heavy.
+    pub fn heat_cell(&self, level: usize) -> (&'static str, Style) {
+        if level == 0 {
+            return ("·", Style::default().fg(self.muted));
+        }
+        if self.monochrome {
+            const SHADES: [&str; 4] = ["░", "▒", "▓", "█"];
+            return (SHADES[(level - 1).min(SHADES.len() - 1)], Style::default());
+        }
+        let color = self.heatmap[(level - 1).min(self.heatmap.len() - 1)];
+        ("■", Style::default().fg(color))
+    }
+
+    pub fn selected_row(&self) -> Style {
+        if self.monochrome {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+                .bg(self.selection_bg)
+                .add_modifier(Modifier::BOLD)
+        }
+    }
+
+    pub fn marked_row(&self) -> Style {
+        if self.monochrome {
+            Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        } else {
+            Style::default().bg(self.marked_bg)
+        }
+    }
+
+    pub fn range_row(&self) -> Style {
+        if self.monochrome {
+            Style::default().add_modifier(Modifier::UNDERLINED)
+        } else {
+            Style::default().bg(self.range_bg)
+        }
+    }
+}
+
+/// `red`, `light_blue`, `#1e90ff`, or a 0-255 palette index.
+fn parse_color(s: &str) -> Result<Color, String> {
+    let s = s.trim();
+    if let Some(hex) = s.strip_prefix('#') {
+        if hex.len() == 6 {
+            if let Ok(rgb) = u32::from_str_radix(hex, 16) {
+                return Ok(Color::Rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8));
+            }
+        }
+        return Err(format!("invalid hex color '{}'", s));
+    }
+    if let Ok(index) = s.parse::<u8>() {
+        return Ok(Color::Indexed(index));
+    }
+    let color = match s.to_lowercase().replace(['-', ' '], "_").as_str() {
+        "reset" | "default" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "dark_gray" | "dark_grey" => Color::DarkGray,
+        "light_red" => Color::LightRed,
+        "light_green" => Color::LightGreen,
+        "light_yellow" => Color::LightYellow,
+        "light_blue" => Color::LightBlue,
+        "light_magenta" => Color::LightMagenta,
+        "light_cyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => return Err(format!("unknown color '{}'", s)),
+    };
+    Ok(color)
+}
@@ -1,20 +1,29 @@
+use std::rc::Rc;
+
+use chrono::{Datelike, Days, NaiveDate};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Margin, Position, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 
+use unicode_width::UnicodeWidthStr;
+
 use crate::app::{App, FormField, FormState, InputMode};
+use crate::keymap::Action;
 use crate::model::{Category, Recurrence};
+use crate::text_input::TextInput;
+use crate::theme::Theme;
+use crate::utils;
 
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
     if app.input_mode != InputMode::AddForm && app.input_mode != InputMode::EditForm {
         return;
     }
 
-    let popup_area = centered_rect(60, 70, area);
+    let popup_area = popup_area(area);
     f.render_widget(Clear, popup_area);
 
     let title = if app.input_mode == InputMode::EditForm {
@@ -25,47 +34,135 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
 
     let block = Block::default()
         .title(title)
-        .title_bottom(Line::from(" Tab:next  Shift+Tab:prev  Enter:save  Esc:cancel ").centered())
+        .title_bottom(Line::from(form_hints(app)).centered())
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
+        .border_style(Style::default().fg(app.theme.accent));
 
-    let inner = block.inner(popup_area);
     f.render_widget(block, popup_area);
+    let fields = field_layout(popup_area);
+    let theme = &app.theme;
 
-    let fields = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Min(0),
-        ])
-        .split(inner);
+    let amount_label = if app.form.active_field == FormField::Amount {
+        "Amount (math like 45.80/3 or 12+4.5 works)"
+    } else {
+        "Amount"
+    };
+    render_field(f, theme, amount_label, &app.form.amount_input, app.form.active_field == FormField::Amount, fields[0]);
+    render_amount_preview(f, app, fields[0]);
+    render_category_field(f, theme, &app.form, fields[1]);
+    render_budget_preview(f, app, fields[1]);
+    render_field(f, theme, "Description", &app.form.description_input, app.form.active_field == FormField::Description, fields[2]);
+    render_field(f, theme, "Tags (comma separated)", &app.form.tags_input, app.form.active_field == FormField::Tags, fields[3]);
+    let date_label = if app.form.active_field == FormField::Date {
+        format!(
+            "Date (today, -3d, last fri… {} calendar)",
+            app.keymap.primary(Action::FormCalendar)
+        )
+    } else {
+        "Date".to_string()
+    };
+    render_field(f, theme, &date_label, &app.form.date_input, app.form.active_field == FormField::Date, fields[4]);
+    render_date_preview(f, theme, &app.form, fields[4]);
+    render_toggle_field(f, theme, "Recurring", app.form.is_recurring, app.form.active_field == FormField::Recurring, fields[5]);
+    render_recurrence_field(f, theme, &app.form, fields[6]);
+
+    render_validation(f, theme, &app.form, fields[7]);
 
-    render_field(f, "Amount", &app.form.amount_input, app.form.active_field == FormField::Amount, fields[0]);
-    render_category_field(f, &app.form, fields[1]);
-    render_field(f, "Description", &app.form.description_input, app.form.active_field == FormField::Description, fields[2]);
-    render_field(f, "Date (YYYY-MM-DD)", &app.form.date_input, app.form.active_field == FormField::Date, fields[3]);
-    render_toggle_field(f, "Recurring", app.form.is_recurring, app.form.active_field == FormField::Recurring, fields[4]);
-    render_recurrence_field(f, &app.form, fields[5]);
+    if let Some(date) = app.form.calendar {
+        render_calendar(f, app, date, fields[4], popup_area);
+    }
+}
+
+fn form_hints(app: &App) -> String {
+    super::hints(
+        app,
+        &[
+            Action::FormNextField,
+            Action::FormPrevField,
+            Action::FormSave,
+            Action::FormTemplate,
+            Action::FormCancel,
+        ],
+    )
+}
+
+/// Fields in the order they are laid out in the popup.
+const FIELD_ORDER: [FormField; 7] = [
+    FormField::Amount,
+    FormField::Category,
+    FormField::Description,
+    FormField::Tags,
+    FormField::Date,
+    FormField::Recurring,
+    FormField::RecurrenceType,
+];
+
+fn popup_area(area: Rect) -> Rect {
+    centered_rect(60, 80, area)
+}
 
-    render_validation(f, &app.form, fields[6]);
+/// One 3-line row per field inside the popup border, then the validation area.
+fn field_layout(popup_area: Rect) -> Rc<[Rect]> {
+    let mut constraints = vec![Constraint::Length(3); FIELD_ORDER.len()];
+    constraints.push(Constraint::Min(0));
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(popup_area.inner(Margin::new(1, 1)))
 }
 
-fn render_field(f: &mut Frame, label: &str, value: &str, active: bool, area: Rect) {
+/// The field under (`col`, `row`) and, for option fields, `-1`/`1` when the
+/// click landed on the `<` or `>` arrow.
+pub fn field_at(app: &App, area: Rect, col: u16, row: u16) -> Option<(FormField, Option<i32>)> {
+    let fields = field_layout(popup_area(area));
+    let pos = Position::new(col, row);
+    let (i, rect) = FIELD_ORDER
+        .iter()
+        .zip(fields.iter())
+        .enumerate()
+        .find(|(_, (_, rect))| rect.contains(pos))
+        .map(|(i, (_, rect))| (i, *rect))?;
+    let field = FIELD_ORDER[i];
+
+    let selected = match field {
+        FormField::Category => Category::all_display_names()
+            .get(app.form.category_index)
+            .copied(),
+        FormField::RecurrenceType if app.form.is_recurring => Recurrence::all_display_names()
+            .get(app.form.recurrence_index)
+            .copied(),
+        _ => None,
+    };
+    // Option fields render as `< Name >` starting just inside the border.
+    let arrow = selected.and_then(|name| {
+        let start = rect.x + 1;
+        let right = start + 2 + name.width() as u16;
+        if row != rect.y + 1 {
+            None
+        } else if col >= start && col < start + 2 {
+            Some(-1)
+        } else if col >= right && col < right + 2 {
+            Some(1)
+        } else {
+            None
+        }
+    });
+    Some((field, arrow))
+}
+
+fn render_field(f: &mut Frame, theme: &Theme, label: &str, value: &TextInput, active: bool, area: Rect) {
     let style = if active {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(theme.accent)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(theme.border_muted)
     };
 
     let display = if active {
-        format!("{}_", value)
+        let (visible, cursor) = value.view(area.width.saturating_sub(2));
+        f.set_cursor_position((area.x + 1 + cursor, area.y + 1));
+        visible
     } else {
-        value.to_string()
+        value.as_str().to_string()
     };
 
     let paragraph = Paragraph::new(display).block(
@@ -78,12 +175,12 @@ fn render_field(f: &mut Frame, label: &str, value: &str, active: bool, area: Rec
     f.render_widget(paragraph, area);
 }
 
-fn render_category_field(f: &mut Frame, form: &FormState, area: Rect) {
+fn render_category_field(f: &mut Frame, theme: &Theme, form: &FormState, area: Rect) {
     let active = form.active_field == FormField::Category;
     let style = if active {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(theme.accent)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(theme.border_muted)
     };
 
     let names = Category::all_display_names();
@@ -91,24 +188,35 @@ fn render_category_field(f: &mut Frame, form: &FormState, area: Rect) {
 
     let display = if active {
         let mut parts = Vec::new();
-        parts.push(Span::styled("< ", Style::default().fg(Color::Yellow)));
+        parts.push(Span::styled("< ", Style::default().fg(theme.accent)));
         parts.push(Span::styled(
             selected.to_string(),
             Style::default()
-                .fg(Color::White)
+                .fg(theme.text)
                 .add_modifier(Modifier::BOLD),
         ));
-        parts.push(Span::styled(" >", Style::default().fg(Color::Yellow)));
+        parts.push(Span::styled(" >", Style::default().fg(theme.accent)));
         if form.category_index == 9 {
-            parts.push(Span::raw(format!(" ({})", form.custom_category)));
+            // `< Name > (` comes before the custom name.
+            let prefix = 2 + selected.width() as u16 + 4;
+            let width = area.width.saturating_sub(2 + prefix + 1);
+            let (visible, cursor) = form.custom_category.view(width);
+            f.set_cursor_position((area.x + 1 + prefix + cursor, area.y + 1));
+            parts.push(Span::raw(format!(" ({})", visible)));
         }
         Line::from(parts)
     } else {
-        let mut text = selected.to_string();
+        // Arrows stay visible, dimmed, so they can be clicked straight away.
+        let arrow = Style::default().fg(theme.border_muted);
+        let mut parts = vec![
+            Span::styled("< ", arrow),
+            Span::raw(selected.to_string()),
+            Span::styled(" >", arrow),
+        ];
         if form.category_index == 9 && !form.custom_category.is_empty() {
-            text = format!("Other({})", form.custom_category);
+            parts.push(Span::raw(format!(" ({})", form.custom_category.as_str())));
         }
-        Line::from(text)
+        Line::from(parts)
     };
 
     let hint = if active {
@@ -127,17 +235,249 @@ fn render_category_field(f: &mut Frame, form: &FormState, area: Rect) {
     f.render_widget(paragraph, area);
 }
 
-fn render_toggle_field(f: &mut Frame, label: &str, value: bool, active: bool, area: Rect) {
+/// The result of an amount expression, right-aligned inside the field. Plain
+/// numbers get no preview.
+fn render_amount_preview(f: &mut Frame, app: &App, area: Rect) {
+    let input = app.form.amount_input.as_str().trim();
+    if input.parse::<f64>().is_ok() {
+        return;
+    }
+    let Ok(value) = app.form.amount() else {
+        return;
+    };
+    let text = format!("= {}", app.fmt(value));
+    render_preview(f, text, Style::default().fg(app.theme.positive), input, area);
+}
+
+/// Right-align `text` on the field's input line if it fits after `input`.
+fn render_preview(f: &mut Frame, text: String, style: Style, input: &str, area: Rect) {
+    let width = text.width() as u16;
+    let inner = area.width.saturating_sub(2);
+    if input.width() as u16 + width + 2 > inner {
+        return;
+    }
+    let preview = Rect::new(area.x + 1 + inner - width, area.y + 1, width, 1);
+    f.render_widget(
+        Paragraph::new(Span::styled(text, style)),
+        preview,
+    );
+}
+
+/// What's left of the selected category's budget, and after this expense
+/// once an amount is entered.
+fn render_budget_preview(f: &mut Frame, app: &App, area: Rect) {
+    if app.budgets.is_empty() {
+        return;
+    }
+    let form = &app.form;
+    let category = form.category();
+    let shown = match &category {
+        Category::Other(name) if form.category_index == 9 && !name.is_empty() => {
+            format!("< Other > ({})", name)
+        }
+        category => format!("< {} >", category),
+    };
+    let date = form.date().unwrap_or_else(|_| utils::today());
+    let amount = form.amount().ok().filter(|a| *a > 0.0);
+    let editing = form
+        .editing_id
+        .filter(|_| app.input_mode == InputMode::EditForm);
+    let Some(impact) = app.budget_impact(&category, date, amount.unwrap_or(0.0), editing) else {
+        let text = "no budget".to_string();
+        render_preview(f, text, Style::default().fg(app.theme.muted), &shown, area);
+        return;
+    };
+
+    let (text, style) = match amount {
+        None => (
+            format!(
+                "{} of {} left {}",
+                app.fmt_compact(impact.left_before()),
+                app.fmt_compact(impact.envelope.available()),
+                impact.period
+            ),
+            Style::default().fg(if impact.left_before() < 0.0 {
+                app.theme.negative
+            } else {
+                app.theme.positive
+            }),
+        ),
+        Some(_) => {
+            let color = if impact.left_after() < 0.0 {
+                app.theme.negative
+            } else if impact.crossed.is_some() {
+                app.theme.accent
+            } else {
+                app.theme.positive
+            };
+            (
+                format!(
+                    "{} left {} → {} after",
+                    app.fmt_compact(impact.left_before()),
+                    impact.period,
+                    app.fmt_compact(impact.left_after())
+                ),
+                Style::default().fg(color),
+            )
+        }
+    };
+    render_preview(f, text, style, &shown, area);
+}
+
+/// Shown after saving takes a budget past one of its alert thresholds.
+pub fn render_budget_alert(f: &mut Frame, app: &App, area: Rect) {
+    let Some(impact) = &app.budget_alert else {
+        return;
+    };
+    let popup_area = super::centered_rect(50, 30, area);
+    f.render_widget(Clear, popup_area);
+
+    let theme = &app.theme;
+    let over = impact.left_after() < 0.0;
+    let color = if over { theme.negative } else { theme.accent };
+    let threshold = impact.crossed.unwrap_or(100.0);
+    let headline = format!(
+        "{} has reached {:.0}% of its budget {}",
+        impact.category, threshold, impact.period
+    );
+    let left = if over {
+        format!("{} over", app.fmt(-impact.left_after()))
+    } else {
+        format!("{} left", app.fmt(impact.left_after()))
+    };
+    let text = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            headline,
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ))
+        .centered(),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "Spent {} of {} ({:.0}%), {}",
+                app.fmt(impact.after),
+                app.fmt(impact.envelope.available()),
+                impact.percent_after().min(999.0),
+                left
+            ),
+            Style::default().fg(theme.text),
+        ))
+        .centered(),
+        Line::from(Span::styled(
+            format!("{} before this expense", app.fmt(impact.before)),
+            Style::default().fg(theme.muted),
+        ))
+        .centered(),
+    ];
+
+    let popup = Paragraph::new(text).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .title(" Budget Alert ")
+            .title_bottom(Line::from(" Enter/Esc:close ").centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color)),
+    );
+    f.render_widget(popup, popup_area);
+}
+
+/// The resolved date, right-aligned inside the field, so relative entries
+/// like `last fri` can be checked before saving.
+fn render_date_preview(f: &mut Frame, theme: &Theme, form: &FormState, area: Rect) {
+    let Ok(date) = form.date() else {
+        return;
+    };
+    let text = format!("= {}", date.format("%a %-d %b %Y"));
+    render_preview(f, text, Style::default().fg(theme.positive), form.date_input.as_str(), area);
+}
+
+/// Month grid for the date picker, opened just below the Date field.
+fn render_calendar(f: &mut Frame, app: &App, date: NaiveDate, field: Rect, popup_area: Rect) {
+    let theme = &app.theme;
+    let (width, height) = (24, 13);
+    let x = (field.x + 2).min(popup_area.right().saturating_sub(width));
+    let y = if field.bottom() + height <= popup_area.bottom() {
+        field.bottom()
+    } else {
+        field.y.saturating_sub(height)
+    };
+    let area = Rect::new(x, y, width, height).intersection(f.area());
+    f.render_widget(Clear, area);
+
+    let today = utils::today();
+    let first = date.with_day(1).unwrap_or(date);
+    let offset = first.weekday().num_days_from_monday() as u64;
+    let start = first - Days::new(offset);
+
+    let mut lines = vec![Line::from(Span::styled(
+        " Mo Tu We Th Fr Sa Su",
+        Style::default().fg(theme.muted),
+    ))];
+    for week in 0..6 {
+        let mut spans = Vec::new();
+        for weekday in 0..7 {
+            let day = start + Days::new(week * 7 + weekday);
+            let mut style = if day.month() != date.month() {
+                Style::default().fg(theme.muted)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            if day == today {
+                style = style.fg(theme.accent).add_modifier(Modifier::BOLD);
+            }
+            if day == date {
+                style = style.patch(theme.selected_row());
+            }
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(format!("{:>2}", day.day()), style));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    let key = |action| app.keymap.primary(action);
+    for hint in [
+        format!(
+            " {}/{} day  {}/{} week",
+            key(Action::CalendarPrevDay),
+            key(Action::CalendarNextDay),
+            key(Action::CalendarPrevWeek),
+            key(Action::CalendarNextWeek)
+        ),
+        format!(
+            " {}/{} month",
+            key(Action::CalendarPrevMonth),
+            key(Action::CalendarNextMonth)
+        ),
+        format!(
+            " {} today  {}  {}",
+            key(Action::CalendarToday),
+            key(Action::CalendarPick),
+            key(Action::CalendarClose)
+        ),
+    ] {
+        lines.push(Line::from(Span::styled(hint, Style::default().fg(theme.muted))));
+    }
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" {} ", date.format("%B %Y")))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn render_toggle_field(f: &mut Frame, theme: &Theme, label: &str, value: bool, active: bool, area: Rect) {
     let style = if active {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(theme.accent)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(theme.border_muted)
     };
 
     let display = if value {
-        Span::styled("Yes", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+        Span::styled("Yes", Style::default().fg(theme.positive).add_modifier(Modifier::BOLD))
     } else {
-        Span::styled("No", Style::default().fg(Color::Red))
+        Span::styled("No", Style::default().fg(theme.negative))
     };
 
     let hint = if active {
@@ -156,32 +496,37 @@ fn render_toggle_field(f: &mut Frame, label: &str, value: bool, active: bool, ar
     f.render_widget(paragraph, area);
 }
 
-fn render_recurrence_field(f: &mut Frame, form: &FormState, area: Rect) {
+fn render_recurrence_field(f: &mut Frame, theme: &Theme, form: &FormState, area: Rect) {
     let active = form.active_field == FormField::RecurrenceType;
     let style = if active && form.is_recurring {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(theme.accent)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(theme.border_muted)
     };
 
     let display = if !form.is_recurring {
-        Line::from(Span::styled("N/A", Style::default().fg(Color::DarkGray)))
+        Line::from(Span::styled("N/A", Style::default().fg(theme.muted)))
     } else {
         let names = Recurrence::all_display_names();
         let selected = names.get(form.recurrence_index).unwrap_or(&"Monthly");
         if active {
             Line::from(vec![
-                Span::styled("< ", Style::default().fg(Color::Yellow)),
+                Span::styled("< ", Style::default().fg(theme.accent)),
                 Span::styled(
                     selected.to_string(),
                     Style::default()
-                        .fg(Color::White)
+                        .fg(theme.text)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(" >", Style::default().fg(Color::Yellow)),
+                Span::styled(" >", Style::default().fg(theme.accent)),
             ])
         } else {
-            Line::from(selected.to_string())
+            let arrow = Style::default().fg(theme.border_muted);
+            Line::from(vec![
+                Span::styled("< ", arrow),
+                Span::raw(selected.to_string()),
+                Span::styled(" >", arrow),
+            ])
         }
     };
 
@@ -201,21 +546,21 @@ fn render_recurrence_field(f: &mut Frame, form: &FormState, area: Rect) {
     f.render_widget(paragraph, area);
 }
 
-fn render_validation(f: &mut Frame, form: &FormState, area: Rect) {
+fn render_validation(f: &mut Frame, theme: &Theme, form: &FormState, area: Rect) {
     let mut errors = Vec::new();
 
+    let amount = form.amount();
     if !form.amount_input.is_empty() {
-        if form.amount_input.parse::<f64>().is_err() {
-            errors.push("Amount must be a valid number");
-        } else if form.amount_input.parse::<f64>().unwrap_or(0.0) <= 0.0 {
-            errors.push("Amount must be positive");
+        match &amount {
+            Err(e) => errors.push(e.as_str()),
+            Ok(value) if *value <= 0.0 => errors.push("Amount must be positive"),
+            Ok(_) => {}
         }
     }
 
-    if !form.date_input.is_empty()
-        && chrono::NaiveDate::parse_from_str(&form.date_input, "%Y-%m-%d").is_err()
-    {
-        errors.push("Date must be YYYY-MM-DD format");
+    let date_error = form.date().err();
+    if let (false, Some(e)) = (form.date_input.is_empty(), &date_error) {
+        errors.push(e.as_str());
     }
 
     if !errors.is_empty() {
@@ -224,7 +569,7 @@ fn render_validation(f: &mut Frame, form: &FormState, area: Rect) {
             .map(|e| {
                 Line::from(Span::styled(
                     format!("  * {}", e),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(theme.negative),
                 ))
             })
             .collect();
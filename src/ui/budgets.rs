@@ -0,0 +1,363 @@
+use chrono::{Datelike, NaiveDate};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::app::App;
+use crate::model::{Category, Period, Rollover};
+use crate::utils;
+
+/// Width of the text progress bar, in cells.
+const BAR_WIDTH: usize = 12;
+
+/// How many past periods the history lists.
+const HISTORY_PERIODS: usize = 12;
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let today = utils::today();
+    let as_of = format!(" as of {} ", today.format("%-d %b %Y"));
+    let block = Block::default()
+        .title(" Budgets ")
+        .title_bottom(Line::from(as_of).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border_muted));
+
+    if let Some(error) = &app.budgets_error {
+        super::render_load_error(f, app, area, block, "budgets", error);
+        return;
+    }
+    let categories = app.budget_categories();
+    if categories.is_empty() {
+        let text = Paragraph::new(Span::styled(
+            "No budgets yet. Add them to ~/.cashflow/budgets.csv.",
+            Style::default().fg(app.theme.muted),
+        ))
+        .block(block);
+        f.render_widget(text, area);
+        return;
+    }
+
+    let chunks = super::list_layout(categories.len(), area);
+    let heading = Style::default()
+        .fg(app.theme.heading)
+        .add_modifier(Modifier::BOLD);
+    let header = Row::new(vec![
+        Cell::from("Category"),
+        Cell::from("Period"),
+        Cell::from("Window"),
+        super::right("Spent"),
+        super::right("Budget"),
+        super::right("Left"),
+        Cell::from("Progress"),
+        super::right("Days left"),
+        super::right("Rolled over"),
+    ])
+    .style(heading)
+    .bottom_margin(1);
+
+    let rows: Vec<Row> = categories
+        .iter()
+        .map(|category| budget_row(app, category, today))
+        .collect();
+
+    let widths = [
+        Constraint::Min(12),
+        Constraint::Length(10),
+        Constraint::Length(17),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(BAR_WIDTH as u16 + 6),
+        Constraint::Length(9),
+        Constraint::Length(11),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .row_highlight_style(app.theme.selected_row())
+        .highlight_symbol(">> ");
+    let selected = app.budget_index.min(categories.len() - 1);
+    let mut state = TableState::default();
+    state.select(Some(selected));
+    f.render_stateful_widget(table, chunks[0], &mut state);
+
+    render_history(f, app, categories[selected], today, chunks[1]);
+}
+
+/// The index of the budget row at (`col`, `row`), if any.
+pub fn budget_row_at(app: &App, area: Rect, col: u16, row: u16) -> Option<usize> {
+    let count = app.budget_categories().len();
+    let table_area = super::list_layout(count, area)[0];
+    super::table_row_at(table_area, count, app.budget_index, col, row)
+}
+
+fn budget_row<'a>(app: &App, category: &Category, today: NaiveDate) -> Row<'a> {
+    let name = category.to_string();
+    let text = Style::default().fg(app.theme.text);
+    let muted = Style::default().fg(app.theme.muted);
+    let amount = |value: f64, style: Style| {
+        Cell::from(Line::from(app.fmt_compact(value)).right_aligned()).style(style)
+    };
+    let mut cells = vec![Cell::from(Span::styled(
+        name.clone(),
+        Style::default().fg(app.theme.category_color_by_name(&name)),
+    ))];
+
+    let (Some((budget, _)), Some(envelope)) = (
+        app.budget_for(category, today),
+        app.envelope(category, today),
+    ) else {
+        // Nothing applies today: a version still to come, or a custom
+        // budget outside its dates.
+        let versions = app.budget_versions(category);
+        let (budget, status) = match versions
+            .iter()
+            .find(|b| b.effective_from.is_some_and(|from| from > today))
+        {
+            Some(next) => (
+                *next,
+                next.effective_from
+                    .map(|from| format!("from {}", from.format("%-d %b %Y")))
+                    .unwrap_or_default(),
+            ),
+            None => {
+                let last = versions[versions.len() - 1];
+                let status = match last.start {
+                    Some(start) if start > today => "not started",
+                    Some(_) => "ended",
+                    None => "",
+                };
+                (last, status.to_string())
+            }
+        };
+        let window = match (budget.period, budget.start, budget.end) {
+            (Period::Custom, Some(start), Some(end)) => format_window(start, end),
+            (Period::Custom, _, _) => "no dates set".to_string(),
+            _ => String::new(),
+        };
+        cells.extend([
+            Cell::from(Span::styled(budget.period.to_string(), muted)),
+            Cell::from(Span::styled(window, muted)),
+            Cell::from(""),
+            amount(budget.limit, muted),
+            Cell::from(""),
+            Cell::from(Span::styled(status, muted)),
+        ]);
+        return Row::new(cells);
+    };
+
+    let available = envelope.available();
+    let remaining = envelope.remaining();
+    let ratio = if available > 0.0 {
+        envelope.spent / available
+    } else if envelope.spent > 0.0 {
+        1.0
+    } else {
+        0.0
+    };
+    let bar_style = if ratio > 1.0 {
+        Style::default().fg(app.theme.negative)
+    } else if ratio > 0.9 {
+        Style::default().fg(app.theme.accent)
+    } else {
+        Style::default().fg(app.theme.positive)
+    };
+    let progress = super::progress_bar(app, ratio, BAR_WIDTH, bar_style);
+    let days_left = (envelope.end - today).num_days() + 1;
+    let carried = if budget.rollover == Rollover::None && envelope.carried.abs() < 0.005 {
+        Cell::from(Line::from("·").right_aligned()).style(muted)
+    } else {
+        Cell::from(Line::from(signed(app, envelope.carried)).right_aligned()).style(text)
+    };
+
+    cells.extend([
+        Cell::from(Span::styled(budget.period.to_string(), text)),
+        Cell::from(Span::styled(
+            format_window(envelope.start, envelope.end),
+            text,
+        )),
+        amount(envelope.spent, text),
+        amount(available, text),
+        amount(remaining, remaining_style(app, remaining)),
+        Cell::from(progress),
+        Cell::from(Line::from(days_left.to_string()).right_aligned()).style(text),
+        carried,
+    ]);
+    Row::new(cells)
+}
+
+/// The selected category's budget versions and how its recent periods went.
+fn render_history(f: &mut Frame, app: &App, category: &Category, today: NaiveDate, area: Rect) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(area);
+    let heading = Style::default()
+        .fg(app.theme.heading)
+        .add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(app.theme.text);
+    let muted = Style::default().fg(app.theme.muted);
+    let amount = |value: f64, style: Style| {
+        Cell::from(Line::from(app.fmt_compact(value)).right_aligned()).style(style)
+    };
+
+    let current = app.budget_for(category, today).map(|(budget, _)| budget);
+    let versions: Vec<Row> = app
+        .budget_versions(category)
+        .into_iter()
+        .map(|budget| {
+            let is_current = current.is_some_and(|c| std::ptr::eq(c, budget));
+            let upcoming = budget.effective_from.is_some_and(|from| from > today);
+            let (style, status) = if is_current {
+                (text.add_modifier(Modifier::BOLD), "current")
+            } else if upcoming {
+                (text, "upcoming")
+            } else {
+                (muted, "")
+            };
+            let from = budget
+                .effective_from
+                .map(|d| d.format("%-d %b %Y").to_string())
+                .unwrap_or_else(|| "always".to_string());
+            let period = match (budget.period, budget.start, budget.end) {
+                (Period::Custom, Some(start), Some(end)) => {
+                    format!("custom {}", format_window(start, end))
+                }
+                (period, _, _) => period.to_string(),
+            };
+            let rollover = match (budget.rollover, budget.rollover_cap) {
+                (Rollover::None, _) => "·".to_string(),
+                (rollover, Some(cap)) => format!("{} ≤{}", rollover, app.fmt_compact(cap)),
+                (rollover, None) => rollover.to_string(),
+            };
+            Row::new(vec![
+                Cell::from(from),
+                amount(budget.limit, style),
+                Cell::from(period),
+                Cell::from(rollover),
+                Cell::from(status),
+            ])
+            .style(style)
+        })
+        .collect();
+    let versions = Table::new(
+        versions,
+        [
+            Constraint::Length(12),
+            Constraint::Length(9),
+            Constraint::Min(10),
+            Constraint::Length(14),
+            Constraint::Length(8),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            Cell::from("From"),
+            super::right("Limit"),
+            Cell::from("Period"),
+            Cell::from("Rollover"),
+            Cell::from(""),
+        ])
+        .style(heading)
+        .bottom_margin(1),
+    )
+    .block(
+        Block::default()
+            .title(format!(" {} — Versions ", category))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border_muted)),
+    );
+    f.render_widget(versions, columns[0]);
+
+    let periods: Vec<Row> = app
+        .envelopes(category, today)
+        .into_iter()
+        .rev()
+        .take(HISTORY_PERIODS)
+        .enumerate()
+        .map(|(i, envelope)| {
+            // The current period first.
+            let style = if i == 0 {
+                text.add_modifier(Modifier::BOLD)
+            } else {
+                text
+            };
+            let carried_style = if envelope.carried.abs() < 0.005 {
+                muted
+            } else {
+                style
+            };
+            let remaining = envelope.remaining();
+            Row::new(vec![
+                Cell::from(format_window(envelope.start, envelope.end)).style(style),
+                amount(envelope.limit, style),
+                Cell::from(Line::from(signed(app, envelope.carried)).right_aligned())
+                    .style(carried_style),
+                amount(envelope.spent, style),
+                amount(remaining, remaining_style(app, remaining)),
+            ])
+        })
+        .collect();
+    let mut block = Block::default()
+        .title(" Recent Periods ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border_muted));
+    if periods.is_empty() {
+        block = block.title_bottom(Line::from(" no budget in force today ").centered());
+    }
+    let periods = Table::new(
+        periods,
+        [
+            Constraint::Min(17),
+            Constraint::Length(9),
+            Constraint::Length(10),
+            Constraint::Length(9),
+            Constraint::Length(9),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            Cell::from("Period"),
+            super::right("Limit"),
+            super::right("Carried in"),
+            super::right("Spent"),
+            super::right("Left"),
+        ])
+        .style(heading)
+        .bottom_margin(1),
+    )
+    .block(block);
+    f.render_widget(periods, columns[1]);
+}
+
+fn remaining_style(app: &App, remaining: f64) -> Style {
+    if remaining < 0.0 {
+        Style::default()
+            .fg(app.theme.negative)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(app.theme.text)
+    }
+}
+
+fn signed(app: &App, amount: f64) -> String {
+    let sign = if amount < -0.005 { "-" } else { "+" };
+    format!("{}{}", sign, app.fmt_compact(amount.abs()))
+}
+
+/// "6 Oct – 12 Oct", with the year only when the window crosses one.
+fn format_window(start: NaiveDate, end: NaiveDate) -> String {
+    if start.year() == end.year() {
+        format!("{} – {}", start.format("%-d %b"), end.format("%-d %b"))
+    } else {
+        format!(
+            "{} – {}",
+            start.format("%-d %b %y"),
+            end.format("%-d %b %y")
+        )
+    }
+}
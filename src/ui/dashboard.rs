@@ -1,30 +1,122 @@
 use chrono::{Datelike, Local};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Bar, BarChart, BarGroup, Block, Borders, Paragraph, Sparkline},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Gauge, Paragraph, Sparkline},
     Frame,
 };
 
 use crate::app::App;
+use crate::forecast::Forecast;
 
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    // The cap and unbudgeted row only shows once there's a cap or a budget.
+    let limits = app.monthly_cap.is_some() || !app.budgets.is_empty();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(5),
+            Constraint::Length(if limits { 3 } else { 0 }),
             Constraint::Min(10),
             Constraint::Length(5),
         ])
         .split(area);
+    let forecast = app.forecast();
 
-    render_summary_cards(f, app, chunks[0]);
-    render_category_chart(f, app, chunks[1]);
-    render_sparkline(f, app, chunks[2]);
+    render_summary_cards(f, app, &forecast, chunks[0]);
+    if limits {
+        render_limits(f, app, &forecast, chunks[1]);
+    }
+    render_category_chart(f, app, chunks[2]);
+    render_sparkline(f, app, chunks[3]);
 }
 
-fn render_summary_cards(f: &mut Frame, app: &App, area: Rect) {
+/// The monthly cap gauge next to this month's unbudgeted spending.
+fn render_limits(f: &mut Frame, app: &App, forecast: &Forecast, area: Rect) {
+    let now = Local::now();
+    let (year, month) = (now.year(), now.month());
+    let widths = match (app.monthly_cap.is_some(), app.budgets.is_empty()) {
+        (true, false) => [Constraint::Percentage(50), Constraint::Percentage(50)],
+        (true, true) => [Constraint::Percentage(100), Constraint::Length(0)],
+        (false, _) => [Constraint::Length(0), Constraint::Percentage(100)],
+    };
+    let cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(widths)
+        .split(area);
+    let block = |title: &'static str| {
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border_muted))
+    };
+
+    if let Some(cap) = app.monthly_cap {
+        let spent = app.total_for_month(year, month);
+        let projected = forecast.projected();
+        let ratio = spent / cap;
+        let color = if ratio > 0.9 {
+            app.theme.negative
+        } else if projected > cap {
+            // On course to go over: warn before it happens.
+            app.theme.accent
+        } else {
+            app.theme.positive
+        };
+        let left = if spent <= cap {
+            format!("{} left", app.fmt_compact(cap - spent))
+        } else {
+            format!("{} over", app.fmt_compact(spent - cap))
+        };
+        let label = format!(
+            "{} / {} ({:.0}%)  {}  → {} by month end",
+            app.fmt_compact(spent),
+            app.fmt_compact(cap),
+            ratio * 100.0,
+            left,
+            app.fmt_compact(projected)
+        );
+        let gauge = Gauge::default()
+            .block(block(" Monthly Cap "))
+            .gauge_style(Style::default().fg(color))
+            .label(Span::styled(label, Style::default().fg(app.theme.text)))
+            .ratio(ratio.clamp(0.0, 1.0));
+        f.render_widget(gauge, cols[0]);
+    }
+
+    if !app.budgets.is_empty() {
+        let unbudgeted = app.unbudgeted_spending(year, month);
+        let total: f64 = unbudgeted.iter().map(|(_, amount)| amount).sum();
+        let line = if unbudgeted.is_empty() {
+            Line::from(Span::styled(
+                "All of this month's spending is budgeted",
+                Style::default().fg(app.theme.muted),
+            ))
+        } else {
+            let mut line = Line::from(Span::styled(
+                app.fmt_compact(total),
+                Style::default()
+                    .fg(app.theme.text)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            for (i, (name, amount)) in unbudgeted.iter().enumerate() {
+                line.push_span(Span::raw(if i == 0 { "  " } else { ", " }));
+                line.push_span(Span::styled(
+                    format!("{} {}", name, app.fmt_compact(*amount)),
+                    Style::default().fg(app.theme.category_color_by_name(name)),
+                ));
+            }
+            line
+        };
+        f.render_widget(
+            Paragraph::new(line).block(block(" Unbudgeted This Month ")),
+            cols[1],
+        );
+    }
+}
+
+fn render_summary_cards(f: &mut Frame, app: &App, forecast: &Forecast, area: Rect) {
     let now = Local::now();
     let month_total = app.total_for_month(now.year(), now.month());
     let year_total = app.total_for_year(now.year());
@@ -33,13 +125,14 @@ fn render_summary_cards(f: &mut Frame, app: &App, area: Rect) {
     let cols = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Percentage(33),
-            Constraint::Percentage(34),
-            Constraint::Percentage(33),
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
         ])
         .split(area);
 
-    let card_style = Style::default().fg(Color::White);
+    let card_style = Style::default().fg(app.theme.text);
 
     let month_card = Paragraph::new(vec![
         Line::from(Span::styled(
@@ -50,7 +143,7 @@ fn render_summary_cards(f: &mut Frame, app: &App, area: Rect) {
         Line::from(Span::styled(
             app.fmt(month_total),
             Style::default()
-                .fg(Color::Green)
+                .fg(app.theme.positive)
                 .add_modifier(Modifier::BOLD),
         )),
     ])
@@ -58,7 +151,7 @@ fn render_summary_cards(f: &mut Frame, app: &App, area: Rect) {
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(Style::default().fg(app.theme.border_muted)),
     );
 
     let year_card = Paragraph::new(vec![
@@ -70,7 +163,7 @@ fn render_summary_cards(f: &mut Frame, app: &App, area: Rect) {
         Line::from(Span::styled(
             app.fmt(year_total),
             Style::default()
-                .fg(Color::Yellow)
+                .fg(app.theme.accent)
                 .add_modifier(Modifier::BOLD),
         )),
     ])
@@ -78,7 +171,7 @@ fn render_summary_cards(f: &mut Frame, app: &App, area: Rect) {
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(Style::default().fg(app.theme.border_muted)),
     );
 
     let count_card = Paragraph::new(vec![
@@ -90,7 +183,7 @@ fn render_summary_cards(f: &mut Frame, app: &App, area: Rect) {
         Line::from(Span::styled(
             format!("{}", count),
             Style::default()
-                .fg(Color::Cyan)
+                .fg(app.theme.heading)
                 .add_modifier(Modifier::BOLD),
         )),
     ])
@@ -98,35 +191,61 @@ fn render_summary_cards(f: &mut Frame, app: &App, area: Rect) {
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(Style::default().fg(app.theme.border_muted)),
     );
 
     f.render_widget(month_card, cols[0]);
-    f.render_widget(year_card, cols[1]);
-    f.render_widget(count_card, cols[2]);
+    f.render_widget(forecast_card(app, forecast), cols[1]);
+    f.render_widget(year_card, cols[2]);
+    f.render_widget(count_card, cols[3]);
+}
+
+/// Projected month-end total, and how it compares with the cap or budgets.
+fn forecast_card(app: &App, forecast: &Forecast) -> Paragraph<'static> {
+    let projected = forecast.projected();
+    let now = Local::now();
+    let limit = app.spending_limit(now.year(), now.month());
+
+    let (color, status) = if limit > 0.0 {
+        let left = limit - projected;
+        if left >= 0.0 {
+            (app.theme.positive, format!("{} under budget", app.fmt_compact(left)))
+        } else {
+            (app.theme.negative, format!("{} over budget", app.fmt_compact(-left)))
+        }
+    } else {
+        (
+            app.theme.heading,
+            format!("day {} of {}", forecast.days_elapsed, forecast.days_in_month),
+        )
+    };
+
+    Paragraph::new(vec![
+        Line::from(Span::styled(
+            "Month-End Forecast",
+            Style::default().add_modifier(Modifier::DIM),
+        )),
+        Line::from(Span::styled(
+            app.fmt(projected),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(status, Style::default().fg(color))),
+    ])
+    .style(Style::default().fg(app.theme.text))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border_muted)),
+    )
 }
 
 fn render_category_chart(f: &mut Frame, app: &App, area: Rect) {
     let now = Local::now();
     let data = app.spending_by_category(now.year(), now.month());
 
-    let colors = [
-        Color::Green,
-        Color::Yellow,
-        Color::Blue,
-        Color::Red,
-        Color::Magenta,
-        Color::Cyan,
-        Color::LightGreen,
-        Color::LightYellow,
-        Color::LightBlue,
-        Color::LightRed,
-    ];
-
     let bars: Vec<Bar> = data
         .iter()
-        .enumerate()
-        .map(|(i, (cat, amount))| {
+        .map(|(cat, amount)| {
             let label = if cat.len() > 10 {
                 format!("{}...", &cat[..8])
             } else {
@@ -135,10 +254,10 @@ fn render_category_chart(f: &mut Frame, app: &App, area: Rect) {
             Bar::default()
                 .value(*amount as u64)
                 .label(Line::from(label))
-                .style(Style::default().fg(colors[i % colors.len()]))
+                .style(Style::default().fg(app.theme.category_color_by_name(cat)))
                 .value_style(
                     Style::default()
-                        .fg(Color::White)
+                        .fg(app.theme.text)
                         .add_modifier(Modifier::BOLD),
                 )
         })
@@ -149,7 +268,7 @@ fn render_category_chart(f: &mut Frame, app: &App, area: Rect) {
             Block::default()
                 .title(" Spending by Category (This Month) ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(Style::default().fg(app.theme.border_muted)),
         )
         .data(BarGroup::default().bars(&bars))
         .bar_width(
@@ -158,7 +277,7 @@ fn render_category_chart(f: &mut Frame, app: &App, area: Rect) {
             } else {
                 let available = area.width.saturating_sub(2);
                 let per = available / data.len().max(1) as u16;
-                per.max(3).min(12)
+                per.clamp(3, 12)
             },
         )
         .bar_gap(1);
@@ -174,10 +293,10 @@ fn render_sparkline(f: &mut Frame, app: &App, area: Rect) {
             Block::default()
                 .title(" Daily Spending (Last 30 Days) ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(Style::default().fg(app.theme.border_muted)),
         )
         .data(&data)
-        .style(Style::default().fg(Color::Cyan));
+        .style(Style::default().fg(app.theme.heading));
 
     f.render_widget(sparkline, area);
 }
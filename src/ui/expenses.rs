@@ -1,115 +1,193 @@
+use std::collections::HashSet;
+use std::rc::Rc;
+
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    widgets::{
+        Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Table,
+        TableState,
+    },
     Frame,
 };
 
-use crate::app::{App, InputMode};
+use crate::anomaly::{Basis, Reason, TRAILING_MONTHS};
+use crate::app::{App, BulkActionKind, InputMode};
+use crate::keymap::Action;
+use crate::model::{Category, Column, Expense};
 
-pub fn render(f: &mut Frame, app: &App, area: Rect) {
-    let chunks = Layout::default()
+use unicode_width::UnicodeWidthStr;
+
+fn layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(3), Constraint::Min(5)])
-        .split(area);
+        .split(area)
+}
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = layout(area);
 
     render_search_bar(f, app, chunks[0]);
     render_table(f, app, chunks[1]);
 }
 
 fn render_search_bar(f: &mut Frame, app: &App, area: Rect) {
-    let (style, title) = if app.input_mode == InputMode::Search {
+    let (style, title) = if app.search_error.is_some() {
         (
-            Style::default().fg(Color::Yellow),
-            " Search (Esc to cancel) ",
+            Style::default().fg(app.theme.negative),
+            " Search (invalid query) ".to_string(),
+        )
+    } else if app.input_mode == InputMode::Search {
+        (
+            Style::default().fg(app.theme.accent),
+            " Search (Esc to cancel) ".to_string(),
         )
     } else {
-        (Style::default().fg(Color::DarkGray), " Search (/ to search) ")
+        (
+            Style::default().fg(app.theme.border_muted),
+            format!(" Search ({} to search) ", app.keymap.primary(Action::Search)),
+        )
     };
 
-    let mut spans = vec![Span::raw(&app.search_query)];
-    if app.input_mode == InputMode::Search {
-        spans.push(Span::styled("_", Style::default().fg(Color::Yellow)));
+    const RECURRING_LABEL: &str = " [Recurring Only]";
+    let mut width = area.width.saturating_sub(2);
+    if app.show_recurring_only {
+        width = width.saturating_sub(RECURRING_LABEL.len() as u16);
     }
+    let query = if app.input_mode == InputMode::Search {
+        let (visible, cursor) = app.search_query.view(width);
+        f.set_cursor_position((area.x + 1 + cursor, area.y + 1));
+        visible
+    } else {
+        app.search_query.view(width).0
+    };
+
+    let mut spans = vec![Span::raw(query)];
     if app.show_recurring_only {
         spans.push(Span::styled(
-            " [Recurring Only]",
-            Style::default().fg(Color::Magenta),
+            RECURRING_LABEL,
+            Style::default().fg(app.theme.tag),
         ));
     }
 
-    let search = Paragraph::new(Line::from(spans)).block(
-        Block::default()
-            .title(title)
-            .borders(Borders::ALL)
-            .border_style(style),
-    );
+    let mut block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(style);
+    if let Some(ref err) = app.search_error {
+        block = block.title_bottom(
+            Line::from(Span::styled(
+                format!(" {} ", err),
+                Style::default().fg(app.theme.negative),
+            ))
+            .right_aligned(),
+        );
+    } else if app.input_mode == InputMode::Search && app.search_query.is_empty() {
+        spans.push(Span::styled(
+            " e.g. cat:food amount>20 date:2026-09..2026-10 tag:work -\"coffee\"",
+            Style::default().fg(app.theme.muted),
+        ));
+    }
+
+    let search = Paragraph::new(Line::from(spans)).block(block);
 
     f.render_widget(search, area);
 }
 
 fn render_table(f: &mut Frame, app: &App, area: Rect) {
-    let header_cells = ["ID", "Date", "Amount", "Category", "Description", "Recurring"]
-        .iter()
-        .map(|h| {
-            Cell::from(*h).style(
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            )
-        });
+    let header_cells = app.columns.iter().map(|column| {
+        let title = if *column == app.sort.column {
+            format!("{} {}", column.title(), app.sort.indicator())
+        } else {
+            column.title().to_string()
+        };
+        Cell::from(title).style(
+            Style::default()
+                .fg(app.theme.heading)
+                .add_modifier(Modifier::BOLD),
+        )
+    });
     let header = Row::new(header_cells).height(1);
 
+    let pending_range = app.pending_range_ids();
+    let report = &app.anomalies;
+    let flagged: HashSet<u64> = report.flags.iter().map(|f| f.id).collect();
     let rows: Vec<Row> = app
         .filtered_indices
         .iter()
         .map(|&i| {
             let expense = &app.expenses[i];
-            let recurring_str = if expense.is_recurring {
-                expense
-                    .recurrence
-                    .map(|r| r.to_string())
-                    .unwrap_or_else(|| "Yes".to_string())
+            let unusual = flagged.contains(&expense.id);
+            let row = Row::new(
+                app.columns
+                    .iter()
+                    .map(|column| render_cell(app, expense, *column, unusual))
+                    .collect::<Vec<Cell>>(),
+            );
+            if app.selected_ids.contains(&expense.id) {
+                row.style(app.theme.marked_row())
+            } else if pending_range.contains(&expense.id) {
+                row.style(app.theme.range_row())
             } else {
-                String::from("-")
-            };
-            Row::new(vec![
-                Cell::from(expense.id.to_string()),
-                Cell::from(expense.date.format("%Y-%m-%d").to_string()),
-                Cell::from(app.fmt(expense.amount))
-                    .style(Style::default().fg(Color::Green)),
-                Cell::from(expense.category.to_string()),
-                Cell::from(expense.description.clone()),
-                Cell::from(recurring_str),
-            ])
+                row
+            }
         })
         .collect();
 
-    let selected_style = Style::default()
-        .bg(Color::DarkGray)
-        .add_modifier(Modifier::BOLD);
+    let selected_style = app.theme.selected_row();
 
-    let widths = [
-        Constraint::Length(6),
-        Constraint::Length(12),
-        Constraint::Length(12),
-        Constraint::Length(15),
-        Constraint::Min(20),
-        Constraint::Length(10),
-    ];
+    let widths: Vec<Constraint> = app.columns.iter().map(|c| column_width(*c)).collect();
 
     let count = app.filtered_indices.len();
-    let title = if app.search_query.is_empty() && !app.show_recurring_only {
+    let mut title = if !app.is_filtered() {
         format!(" Expenses ({}) ", count)
     } else {
         format!(" Filtered ({}) ", count)
     };
+    if !app.selected_ids.is_empty() {
+        title.push_str(&format!("[{} selected] ", app.selected_ids.len()));
+    }
+    let unusual = report.flagged_ids().len();
+    if unusual > 0 {
+        title.push_str(&format!(
+            "⚠ {} unusual ({} to review) ",
+            unusual,
+            app.keymap.primary(Action::ReviewAnomalies)
+        ));
+    }
+    let reasons: Vec<String> = app
+        .selected_expense()
+        .map(|e| report.reasons(e.id).map(|r| describe(app, e, r)).collect())
+        .unwrap_or_default();
 
     let hint = if app.input_mode == InputMode::ConfirmDelete {
-        " Press y to confirm delete, n to cancel "
+        " Press y to confirm delete, n to cancel ".to_string()
     } else {
-        " a:add  e:edit  d:delete  r:recurring  /:search  x:export "
+        let actions: &[Action] = if app.selected_ids.is_empty() {
+            &[
+                Action::AddExpense,
+                Action::EditExpense,
+                Action::DeleteExpense,
+                Action::Search,
+                Action::CycleSort,
+                Action::EditColumns,
+                Action::ToggleMark,
+                Action::Export,
+            ]
+        } else {
+            &[
+                Action::ToggleMark,
+                Action::RangeSelect,
+                Action::SelectAll,
+                Action::BulkActions,
+                Action::ClearSelection,
+                Action::Undo,
+            ]
+        };
+        let hints: Vec<String> = actions.iter().map(|a| app.keymap.hint(*a)).collect();
+        format!(" {} ", hints.join("  "))
     };
 
     let table = Table::new(rows, widths)
@@ -117,9 +195,29 @@ fn render_table(f: &mut Frame, app: &App, area: Rect) {
         .block(
             Block::default()
                 .title(title)
+                .title(
+                    Line::from(Span::styled(
+                        if reasons.is_empty() {
+                            String::new()
+                        } else {
+                            format!(" ⚠ {} ", reasons.join("; "))
+                        },
+                        Style::default().fg(app.theme.negative),
+                    ))
+                    .right_aligned(),
+                )
                 .title_bottom(Line::from(hint).centered())
+                .title_bottom(
+                    Line::from(Span::styled(
+                        format!(" Total: {} ", app.fmt(app.filtered_total())),
+                        Style::default()
+                            .fg(app.theme.positive)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                    .right_aligned(),
+                )
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(Style::default().fg(app.theme.border_muted)),
         )
         .row_highlight_style(selected_style)
         .highlight_symbol(">> ");
@@ -131,3 +229,357 @@ fn render_table(f: &mut Frame, app: &App, area: Rect) {
 
     f.render_stateful_widget(table, area, &mut state);
 }
+
+/// Position in `filtered_indices` of the table row at screen `row`, given the
+/// content area the tab was rendered into.
+pub fn table_row_at(app: &App, area: Rect, col: u16, row: u16) -> Option<usize> {
+    let table_area = layout(area)[1];
+    let first_row = table_area.y + 2;
+    let visible = table_area.height.saturating_sub(3) as usize;
+    if visible == 0
+        || row < first_row
+        || row >= first_row + visible as u16
+        || col <= table_area.x
+        || col >= table_area.right() - 1
+    {
+        return None;
+    }
+    // A fresh `TableState` scrolls just far enough to keep the selection visible.
+    let offset = app.expense_table_index.saturating_sub(visible - 1);
+    let index = offset + (row - first_row) as usize;
+    (index < app.filtered_indices.len()).then_some(index)
+}
+
+fn render_cell<'a>(app: &App, expense: &'a Expense, column: Column, unusual: bool) -> Cell<'a> {
+    match column {
+        Column::Id => Cell::from(expense.id.to_string()),
+        Column::Date => Cell::from(expense.date.format("%Y-%m-%d").to_string()),
+        Column::Amount => {
+            Cell::from(app.fmt(expense.amount)).style(Style::default().fg(app.theme.positive))
+        }
+        Column::Category => Cell::from(expense.category.to_string()),
+        Column::Description if unusual => Cell::from(Line::from(vec![
+            Span::styled("⚠ ", Style::default().fg(app.theme.negative)),
+            Span::raw(expense.description.as_str()),
+        ])),
+        Column::Description => Cell::from(expense.description.as_str()),
+        Column::Tags => {
+            Cell::from(expense.tags.join(" ")).style(Style::default().fg(app.theme.tag))
+        }
+        Column::Recurring => {
+            let recurring_str = if expense.is_recurring {
+                expense
+                    .recurrence
+                    .map(|r| r.to_string())
+                    .unwrap_or_else(|| "Yes".to_string())
+            } else {
+                String::from("-")
+            };
+            Cell::from(recurring_str)
+        }
+    }
+}
+
+fn column_width(column: Column) -> Constraint {
+    match column {
+        Column::Id => Constraint::Length(6),
+        Column::Date => Constraint::Length(12),
+        Column::Amount => Constraint::Length(12),
+        Column::Category => Constraint::Length(15),
+        Column::Description => Constraint::Min(20),
+        Column::Tags => Constraint::Length(14),
+        Column::Recurring => Constraint::Length(11),
+    }
+}
+
+/// Why an expense was flagged, in a few words.
+fn describe(app: &App, expense: &Expense, reason: &Reason) -> String {
+    match reason {
+        Reason::Outlier { basis, typical } => {
+            let within = match basis {
+                Basis::Payee(name) => format!("at {}", name),
+                Basis::Category(name) => format!("in {}", name),
+            };
+            format!(
+                "{:.1}× the usual {} {}",
+                expense.amount / typical,
+                app.fmt(*typical),
+                within
+            )
+        }
+        Reason::Duplicate { date, .. } => {
+            format!("same as the charge on {}", date.format("%-d %b"))
+        }
+    }
+}
+
+/// Flagged expenses and category spikes, opened with `!` on the Expenses tab.
+pub fn render_review(f: &mut Frame, app: &App, area: Rect) {
+    let popup_area = super::centered_rect(80, 70, area);
+    f.render_widget(Clear, popup_area);
+
+    let theme = &app.theme;
+    let report = &app.anomalies;
+    let ids = report.flagged_ids();
+    let block = Block::default()
+        .title(" Unusual Spending ")
+        .title_bottom(
+            Line::from(format!(
+                " {}/{}:select {}",
+                app.keymap.primary(Action::ReviewUp),
+                app.keymap.primary(Action::ReviewDown),
+                super::hints(app, &[Action::ReviewShow, Action::ReviewClose])
+            ))
+            .centered(),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let heading = Style::default()
+        .fg(theme.heading)
+        .add_modifier(Modifier::BOLD);
+    let spike_lines: Vec<Line> = report
+        .spikes
+        .iter()
+        .map(|spike| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<10}", spike.month.format("%b %Y").to_string()),
+                    Style::default().fg(theme.muted),
+                ),
+                Span::styled(
+                    format!("{:<15}", spike.category),
+                    Style::default().fg(theme.category_color_by_name(&spike.category)),
+                ),
+                Span::styled(
+                    format!(
+                        "{} spent, {:.1}× the {} average of the {} months before",
+                        app.fmt(spike.spent),
+                        spike.ratio(),
+                        app.fmt(spike.average),
+                        TRAILING_MONTHS
+                    ),
+                    Style::default().fg(theme.text),
+                ),
+            ])
+        })
+        .collect();
+    // The spikes get what they need up to a third of the popup, plus a
+    // title line and a blank line.
+    let spike_height = if spike_lines.is_empty() {
+        0
+    } else {
+        (spike_lines.len() as u16 + 2).min(inner.height / 3)
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(spike_height), Constraint::Min(3)])
+        .split(inner);
+    if !spike_lines.is_empty() {
+        let mut lines = vec![Line::from(Span::styled("Category spikes", heading))];
+        lines.extend(spike_lines);
+        f.render_widget(Paragraph::new(lines), chunks[0]);
+    }
+
+    if ids.is_empty() {
+        let empty = Paragraph::new("No unusual expenses")
+            .centered()
+            .style(Style::default().fg(theme.muted));
+        f.render_widget(empty, chunks[1]);
+        return;
+    }
+    let rows: Vec<Row> = ids
+        .iter()
+        .filter_map(|id| app.expenses.iter().find(|e| e.id == *id))
+        .map(|e| {
+            let reasons: Vec<String> = report.reasons(e.id).map(|r| describe(app, e, r)).collect();
+            Row::new(vec![
+                Cell::from(e.date.format("%Y-%m-%d").to_string()),
+                Cell::from(Line::from(app.fmt(e.amount)).right_aligned())
+                    .style(Style::default().fg(theme.accent)),
+                Cell::from(e.category.to_string())
+                    .style(Style::default().fg(theme.category_color(&e.category))),
+                Cell::from(e.description.clone()),
+                Cell::from(reasons.join("; ")).style(Style::default().fg(theme.negative)),
+            ])
+            .style(Style::default().fg(theme.text))
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(12),
+            Constraint::Length(14),
+            Constraint::Length(18),
+            Constraint::Min(20),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            Cell::from("Date"),
+            Cell::from(Line::from("Amount").right_aligned()),
+            Cell::from("Category"),
+            Cell::from("Description"),
+            Cell::from("Why"),
+        ])
+        .style(heading),
+    )
+    .row_highlight_style(theme.selected_row())
+    .highlight_symbol(">> ");
+    let mut state = TableState::default();
+    state.select(Some(app.anomaly_index.min(ids.len() - 1)));
+    f.render_stateful_widget(table, chunks[1], &mut state);
+}
+
+pub fn render_column_editor(f: &mut Frame, app: &App, area: Rect) {
+    let popup_area = super::centered_rect(40, 50, area);
+    f.render_widget(Clear, popup_area);
+
+    let items: Vec<ListItem> = app
+        .column_editor_entries()
+        .iter()
+        .map(|(column, visible)| {
+            let check = if *visible { "[x]" } else { "[ ]" };
+            let sort = if *column == app.sort.column {
+                format!(" {}", app.sort.indicator())
+            } else {
+                String::new()
+            };
+            let style = if *visible {
+                Style::default().fg(app.theme.text)
+            } else {
+                Style::default().fg(app.theme.muted)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{} {}", check, column.title()), style),
+                Span::styled(sort, Style::default().fg(app.theme.heading)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(" Columns ")
+                .title_bottom(
+                    Line::from(format!(
+                        " {}  {}/{}:move {}",
+                        app.keymap.hint(Action::ColumnsToggle),
+                        app.keymap.primary(Action::ColumnsMoveDown),
+                        app.keymap.primary(Action::ColumnsMoveUp),
+                        super::hints(app, &[Action::ColumnsSort, Action::ColumnsClose])
+                    ))
+                    .centered(),
+                )
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.accent)),
+        )
+        .highlight_style(app.theme.selected_row())
+        .highlight_symbol(">> ");
+
+    let mut state = ListState::default();
+    state.select(Some(app.column_editor_index));
+    f.render_stateful_widget(list, popup_area, &mut state);
+}
+
+pub fn render_bulk_popup(f: &mut Frame, app: &App, area: Rect) {
+    let popup_area = super::centered_rect(50, 40, area);
+    f.render_widget(Clear, popup_area);
+
+    let count = app.selected_ids.len();
+    let block = Block::default()
+        .title(format!(" Bulk Actions ({} selected) ", count))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent));
+
+    match app.input_mode {
+        InputMode::BulkMenu => {
+            let items: Vec<ListItem> = BulkActionKind::all()
+                .iter()
+                .enumerate()
+                .map(|(i, kind)| ListItem::new(format!("{}  {}", i + 1, kind.label())))
+                .collect();
+            let list = List::new(items)
+                .block(block.title_bottom(Line::from(" Enter:choose  Esc:cancel ").centered()))
+                .highlight_style(app.theme.selected_row())
+                .highlight_symbol(">> ");
+            let mut state = ListState::default();
+            state.select(Some(app.bulk.menu_index));
+            f.render_stateful_widget(list, popup_area, &mut state);
+        }
+        InputMode::BulkInput => {
+            // The value line is the third line inside the border.
+            let (x, y) = (popup_area.x + 1, popup_area.y + 3);
+            let width = popup_area.width.saturating_sub(2);
+            let (prompt, value) = match app.bulk.kind {
+                Some(BulkActionKind::SetCategory) => {
+                    let names = Category::all_display_names();
+                    let selected = names.get(app.bulk.category_index).unwrap_or(&"Other");
+                    let mut value = format!("< {} >", selected);
+                    if app.bulk.category_index == 9 {
+                        let prefix = value.width() as u16 + 2;
+                        let (visible, cursor) =
+                            app.bulk.input.view(width.saturating_sub(prefix + 1));
+                        f.set_cursor_position((x + prefix + cursor, y));
+                        value.push_str(&format!(" ({})", visible));
+                    }
+                    ("New category (←/→ to change):", value)
+                }
+                Some(BulkActionKind::AddTag) | Some(BulkActionKind::ShiftDate) => {
+                    let (visible, cursor) = app.bulk.input.view(width);
+                    f.set_cursor_position((x + cursor, y));
+                    let prompt = if app.bulk.kind == Some(BulkActionKind::AddTag) {
+                        "Tags to add (comma separated):"
+                    } else {
+                        "Shift dates by days (e.g. +7, -3):"
+                    };
+                    (prompt, visible)
+                }
+                _ => ("", String::new()),
+            };
+            let text = vec![
+                Line::from(Span::styled(prompt, Style::default().fg(app.theme.heading))),
+                Line::from(""),
+                Line::from(Span::styled(
+                    value,
+                    Style::default()
+                        .fg(app.theme.text)
+                        .add_modifier(Modifier::BOLD),
+                )),
+            ];
+            let paragraph = Paragraph::new(text).block(
+                block.title_bottom(Line::from(" Enter:continue  Esc:back ").centered()),
+            );
+            f.render_widget(paragraph, popup_area);
+        }
+        InputMode::ConfirmBulk => {
+            let action = app
+                .bulk
+                .pending
+                .as_ref()
+                .map(|a| a.describe())
+                .unwrap_or_default();
+            let text = vec![
+                Line::from(Span::styled(
+                    format!("{} for {} expenses?", action, count),
+                    Style::default()
+                        .fg(app.theme.text)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "This can be undone with u.",
+                    Style::default().fg(app.theme.muted),
+                )),
+            ];
+            let paragraph = Paragraph::new(text).block(
+                block.title_bottom(Line::from(" y:apply  any other key:cancel ").centered()),
+            );
+            f.render_widget(paragraph, popup_area);
+        }
+        _ => {}
+    }
+}
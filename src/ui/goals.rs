@@ -0,0 +1,320 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap},
+    Frame,
+};
+
+use crate::app::{App, GoalPrompt};
+use crate::keymap::Action;
+use crate::model::{Goal, GoalProgress, GoalStatus};
+use crate::utils;
+
+/// Width of the text progress bar, in cells.
+const BAR_WIDTH: usize = 16;
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let today = utils::today();
+    let as_of = format!(" as of {} ", today.format("%-d %b %Y"));
+    let block = Block::default()
+        .title(" Savings Goals ")
+        .title_bottom(Line::from(as_of).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border_muted));
+
+    if let Some(error) = &app.goals_error {
+        super::render_load_error(f, app, area, block, "goals", error);
+        return;
+    }
+    if app.goals.is_empty() {
+        let text = Paragraph::new(Span::styled(
+            format!(
+                "No goals yet. Press {} to add one, e.g. Car 12000 @2027-12-31.",
+                app.keymap.primary(Action::GoalsNew)
+            ),
+            Style::default().fg(app.theme.muted),
+        ))
+        .block(block);
+        f.render_widget(text, area);
+        return;
+    }
+
+    let chunks = super::list_layout(app.goals.len(), area);
+    let heading = Style::default()
+        .fg(app.theme.heading)
+        .add_modifier(Modifier::BOLD);
+    let header = Row::new(vec![
+        Cell::from("Goal"),
+        super::right("Saved"),
+        super::right("Target"),
+        Cell::from("Progress"),
+        Cell::from("Target date"),
+        super::right("Months left"),
+        super::right("Needed/mo"),
+        Cell::from("Status"),
+    ])
+    .style(heading)
+    .bottom_margin(1);
+
+    let rows: Vec<Row> = app
+        .goals
+        .iter()
+        .map(|goal| goal_row(app, goal, &app.goal_progress(goal, today)))
+        .collect();
+
+    let widths = [
+        Constraint::Min(14),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(BAR_WIDTH as u16 + 6),
+        Constraint::Length(12),
+        Constraint::Length(11),
+        Constraint::Length(10),
+        Constraint::Length(10),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .row_highlight_style(app.theme.selected_row())
+        .highlight_symbol(">> ");
+    let selected = app.goal_index.min(app.goals.len() - 1);
+    let mut state = TableState::default();
+    state.select(Some(selected));
+    f.render_stateful_widget(table, chunks[0], &mut state);
+
+    render_details(f, app, &app.goals[selected], chunks[1]);
+}
+
+/// The index of the goal row at (`col`, `row`), if any.
+pub fn goal_row_at(app: &App, area: Rect, col: u16, row: u16) -> Option<usize> {
+    let count = app.goals.len();
+    let table_area = super::list_layout(count, area)[0];
+    super::table_row_at(table_area, count, app.goal_index, col, row)
+}
+
+fn goal_row<'a>(app: &App, goal: &Goal, progress: &GoalProgress) -> Row<'a> {
+    let text = Style::default().fg(app.theme.text);
+    let muted = Style::default().fg(app.theme.muted);
+    let amount = |value: f64, style: Style| {
+        Cell::from(Line::from(app.fmt_compact(value)).right_aligned()).style(style)
+    };
+    let status = progress.status();
+    let style = status_style(app, status);
+
+    let bar = super::progress_bar(app, progress.ratio(), BAR_WIDTH, style);
+    let (months_left, needed) = if status == GoalStatus::Overdue {
+        (
+            Cell::from(Line::from("past").right_aligned()).style(style),
+            amount(progress.remaining(), text),
+        )
+    } else if status == GoalStatus::Reached {
+        (
+            Cell::from(""),
+            Cell::from(Line::from("·").right_aligned()).style(muted),
+        )
+    } else {
+        (
+            Cell::from(Line::from(format!("{:.1}", progress.months_left())).right_aligned())
+                .style(text),
+            amount(progress.required_monthly(), text),
+        )
+    };
+
+    Row::new(vec![
+        Cell::from(Span::styled(goal.name.clone(), text)),
+        amount(progress.saved, text),
+        amount(progress.target, text),
+        Cell::from(bar),
+        Cell::from(Span::styled(
+            progress.target_date.format("%-d %b %Y").to_string(),
+            text,
+        )),
+        months_left,
+        needed,
+        Cell::from(Span::styled(status.label(), style)),
+    ])
+}
+
+/// The selected goal's numbers in words, next to its contributions.
+fn render_details(f: &mut Frame, app: &App, goal: &Goal, area: Rect) {
+    let today = utils::today();
+    let progress = app.goal_progress(goal, today);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(area);
+    let heading = Style::default()
+        .fg(app.theme.heading)
+        .add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(app.theme.text);
+    let muted = Style::default().fg(app.theme.muted);
+    let status = progress.status();
+
+    let line = |label: &str, value: String, style: Style| {
+        Line::from(vec![
+            Span::styled(format!("{:<18}", label), muted),
+            Span::styled(value, style),
+        ])
+    };
+    let mut lines = vec![
+        line(
+            "Saved",
+            format!(
+                "{} of {} ({:.0}%)",
+                app.fmt(progress.saved),
+                app.fmt(progress.target),
+                progress.ratio() * 100.0
+            ),
+            text,
+        ),
+        line("Left to save", app.fmt(progress.remaining()), text),
+        line(
+            "Target date",
+            progress.target_date.format("%-d %b %Y").to_string(),
+            text,
+        ),
+        line(
+            "Saving since",
+            progress.start.format("%-d %b %Y").to_string(),
+            text,
+        ),
+        Line::from(""),
+    ];
+    match status {
+        GoalStatus::Reached => {
+            lines.push(Line::from(Span::styled(
+                "Target reached.",
+                status_style(app, status),
+            )));
+        }
+        GoalStatus::Overdue => {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "The target date has passed with {} still to save.",
+                    app.fmt(progress.remaining())
+                ),
+                status_style(app, status),
+            )));
+        }
+        GoalStatus::OnTrack | GoalStatus::Behind => {
+            lines.push(line(
+                "Needed per month",
+                app.fmt(progress.required_monthly()),
+                text.add_modifier(Modifier::BOLD),
+            ));
+            let gap = progress.saved - progress.expected();
+            let word = if gap >= 0.0 { "ahead" } else { "behind" };
+            lines.push(line(
+                "Even pace",
+                format!(
+                    "{} by now, {} {}",
+                    app.fmt(progress.expected()),
+                    app.fmt(gap.abs()),
+                    word
+                ),
+                status_style(app, status),
+            ));
+        }
+    }
+
+    let details = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(format!(" {} ", goal.name))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border_muted)),
+    );
+    f.render_widget(details, columns[0]);
+
+    let rows: Vec<Row> = app
+        .goal_contributions(goal)
+        .into_iter()
+        .rev()
+        .map(|c| {
+            let style = if c.date > today { muted } else { text };
+            let amount_style = if c.amount < 0.0 {
+                Style::default().fg(app.theme.negative)
+            } else {
+                style
+            };
+            let sign = if c.amount < 0.0 { "-" } else { "+" };
+            Row::new(vec![
+                Cell::from(c.date.format("%-d %b %Y").to_string()).style(style),
+                Cell::from(
+                    Line::from(format!("{}{}", sign, app.fmt_compact(c.amount.abs())))
+                        .right_aligned(),
+                )
+                .style(amount_style),
+                Cell::from(c.note.clone()).style(style),
+            ])
+        })
+        .collect();
+    let mut block = Block::default()
+        .title(" Contributions ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border_muted));
+    if rows.is_empty() {
+        block = block.title_bottom(
+            Line::from(format!(
+                " {} to add one ",
+                app.keymap.primary(Action::GoalsContribute)
+            ))
+            .centered(),
+        );
+    }
+    let contributions = Table::new(
+        rows,
+        [
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Min(10),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            Cell::from("Date"),
+            super::right("Amount"),
+            Cell::from("Note"),
+        ])
+        .style(heading)
+        .bottom_margin(1),
+    )
+    .block(block);
+    f.render_widget(contributions, columns[1]);
+}
+
+fn status_style(app: &App, status: GoalStatus) -> Style {
+    match status {
+        GoalStatus::Reached | GoalStatus::OnTrack => Style::default().fg(app.theme.positive),
+        GoalStatus::Behind => Style::default().fg(app.theme.accent),
+        GoalStatus::Overdue => Style::default()
+            .fg(app.theme.negative)
+            .add_modifier(Modifier::BOLD),
+    }
+}
+
+/// The new-goal or contribution prompt, docked like quick add.
+pub fn render_prompt(f: &mut Frame, app: &App, content: Rect) {
+    let (title, example) = match app.goal_prompt {
+        GoalPrompt::NewGoal => (
+            " New goal ".to_string(),
+            "e.g. Emergency fund 10000 @2027-06-30",
+        ),
+        GoalPrompt::Contribute => (
+            format!(
+                " Contribute to {} ",
+                app.selected_goal().map(|g| g.name.as_str()).unwrap_or("")
+            ),
+            "e.g. 250 @-3d bonus, or -100 to withdraw",
+        ),
+    };
+    super::render_entry_prompt(
+        f,
+        app,
+        content,
+        &title,
+        &app.goal_input,
+        example,
+        app.goal_prompt_preview(),
+    );
+}
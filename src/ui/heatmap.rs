@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use chrono::{Datelike, Days, NaiveDate};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::app::App;
+use crate::keymap::Action;
+
+/// Columns taken by the weekday labels left of the grid.
+const LABEL_WIDTH: u16 = 4;
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+fn layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(10),
+            Constraint::Min(0),
+        ])
+        .split(area)
+}
+
+/// Where a year's weeks × weekdays grid sits on screen. Weeks start on
+/// Monday, so the first and last columns can hold days of other years.
+struct Grid {
+    year: i32,
+    /// The Monday of the week holding 1 January.
+    first: NaiveDate,
+    weeks: u16,
+    /// 2 columns per day when the grid fits, else 1.
+    cell: u16,
+    x: u16,
+    /// The month label row; weekdays follow on the next 7 rows.
+    y: u16,
+}
+
+impl Grid {
+    fn new(year: i32, inner: Rect) -> Option<Self> {
+        let jan1 = NaiveDate::from_ymd_opt(year, 1, 1)?;
+        let dec31 = NaiveDate::from_ymd_opt(year, 12, 31)?;
+        let first =
+            jan1.checked_sub_days(Days::new(jan1.weekday().num_days_from_monday() as u64))?;
+        let weeks = ((dec31 - first).num_days() / 7 + 1) as u16;
+        let cell = if inner.width >= LABEL_WIDTH + weeks * 2 {
+            2
+        } else {
+            1
+        };
+        let width = LABEL_WIDTH + weeks * cell;
+        Some(Self {
+            year,
+            first,
+            weeks,
+            cell,
+            x: inner.x + inner.width.saturating_sub(width) / 2,
+            y: inner.y,
+        })
+    }
+
+    fn date(&self, week: u16, weekday: u16) -> Option<NaiveDate> {
+        let date = self
+            .first
+            .checked_add_days(Days::new(week as u64 * 7 + weekday as u64))?;
+        (date.year() == self.year).then_some(date)
+    }
+
+    fn week_of(&self, date: NaiveDate) -> u16 {
+        ((date - self.first).num_days() / 7) as u16
+    }
+}
+
+fn grid_area(area: Rect) -> Rect {
+    Block::default()
+        .borders(Borders::ALL)
+        .inner(layout(area)[1])
+}
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = layout(area);
+
+    render_year_selector(f, app, chunks[0]);
+    render_grid(f, app, chunks[1]);
+    render_summary(f, app, chunks[2]);
+}
+
+/// `-1` or `1` if (`col`, `row`) is on the previous/next arrow of the year
+/// selector.
+pub fn year_arrow_at(app: &App, area: Rect, col: u16, row: u16) -> Option<i32> {
+    let label = app.heatmap_day.year().to_string();
+    super::selector_arrow_at(layout(area)[0], &label, col, row)
+}
+
+/// The day whose cell is under (`col`, `row`).
+pub fn day_at(app: &App, area: Rect, col: u16, row: u16) -> Option<NaiveDate> {
+    let grid = Grid::new(app.heatmap_day.year(), grid_area(area))?;
+    let left = grid.x + LABEL_WIDTH;
+    if row <= grid.y || row > grid.y + 7 || col < left {
+        return None;
+    }
+    let week = (col - left) / grid.cell;
+    if week >= grid.weeks {
+        return None;
+    }
+    grid.date(week, row - grid.y - 1)
+}
+
+fn render_year_selector(f: &mut Frame, app: &App, area: Rect) {
+    let title = format!(
+        " Year ({}/{} to navigate) ",
+        app.keymap.primary(Action::HeatmapPrevYear),
+        app.keymap.primary(Action::HeatmapNextYear)
+    );
+    let label = app.heatmap_day.year().to_string();
+    super::render_selector(f, app, area, &label, &title);
+}
+
+/// Quantiles splitting the year's spending days into `levels` equal groups,
+/// so one big bill doesn't wash out the rest of the year.
+fn thresholds(totals: &HashMap<NaiveDate, f64>, levels: usize) -> Vec<f64> {
+    let mut amounts: Vec<f64> = totals.values().copied().collect();
+    amounts.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    (1..levels)
+        .filter_map(|i| amounts.get(amounts.len() * i / levels).copied())
+        .collect()
+}
+
+fn level(amount: f64, thresholds: &[f64]) -> usize {
+    if amount <= 0.0 {
+        0
+    } else {
+        1 + thresholds.iter().filter(|t| amount > **t).count()
+    }
+}
+
+fn render_grid(f: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let year = app.heatmap_day.year();
+    let totals = app.daily_totals(year);
+    let levels = theme.heatmap.len();
+    let thresholds = thresholds(&totals, levels);
+
+    let mut legend = vec![Span::styled(" Less ", Style::default().fg(theme.muted))];
+    for l in 0..=levels {
+        let (glyph, style) = theme.heat_cell(l);
+        legend.push(Span::styled(format!("{} ", glyph), style));
+    }
+    legend.push(Span::styled("More ", Style::default().fg(theme.muted)));
+
+    let block = Block::default()
+        .title(" Daily Spending ")
+        .title_bottom(Line::from(legend).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_muted));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let Some(grid) = Grid::new(year, inner) else {
+        return;
+    };
+
+    // Month names above the week holding the 1st, skipped if they'd overlap.
+    let mut months = String::new();
+    for month in 1..=12 {
+        let Some(date) = NaiveDate::from_ymd_opt(year, month, 1) else {
+            continue;
+        };
+        let col = (LABEL_WIDTH + grid.week_of(date) * grid.cell) as usize;
+        if months.is_empty() || col > months.len() {
+            months.push_str(&" ".repeat(col.saturating_sub(months.len())));
+            months.push_str(&date.format("%b").to_string());
+        }
+    }
+    let mut lines = vec![Line::from(Span::styled(
+        months,
+        Style::default().fg(theme.heading),
+    ))];
+
+    let gap = if grid.cell == 2 { " " } else { "" };
+    for (weekday, name) in WEEKDAYS.iter().enumerate() {
+        let mut spans = vec![Span::styled(
+            format!("{:<4}", name),
+            Style::default().fg(theme.muted),
+        )];
+        for week in 0..grid.weeks {
+            let Some(date) = grid.date(week, weekday as u16) else {
+                spans.push(Span::raw(" ".repeat(grid.cell as usize)));
+                continue;
+            };
+            let amount = totals.get(&date).copied().unwrap_or(0.0);
+            let (glyph, mut style) = theme.heat_cell(level(amount, &thresholds));
+            if date == app.heatmap_day {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            spans.push(Span::styled(glyph, style));
+            spans.push(Span::raw(gap));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let width = inner.right().saturating_sub(grid.x);
+    f.render_widget(
+        Paragraph::new(lines),
+        Rect::new(grid.x, grid.y, width, inner.height),
+    );
+}
+
+fn render_summary(f: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let day = app.heatmap_day;
+    let totals = app.daily_totals(day.year());
+    let text = Style::default().fg(theme.text);
+    let muted = Style::default().fg(theme.muted);
+    let bold = Style::default()
+        .fg(theme.accent)
+        .add_modifier(Modifier::BOLD);
+
+    let on_day = app.expenses_on(day);
+    let mut day_line = vec![Span::styled(
+        format!("{}: ", day.format("%A %-d %B %Y")),
+        Style::default()
+            .fg(theme.heading)
+            .add_modifier(Modifier::BOLD),
+    )];
+    if on_day.is_empty() {
+        day_line.push(Span::styled("no expenses", muted));
+    } else {
+        let total: f64 = on_day.iter().map(|e| e.amount).sum();
+        day_line.push(Span::styled(app.fmt(total), bold));
+        day_line.push(Span::styled(
+            format!(
+                " across {} expense{}  ({} to list)",
+                on_day.len(),
+                if on_day.len() == 1 { "" } else { "s" },
+                app.keymap.primary(Action::HeatmapShowDay)
+            ),
+            text,
+        ));
+    }
+    let mut lines = vec![Line::from(day_line), Line::from("")];
+
+    if totals.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("No expenses in {}", day.year()),
+            muted,
+        )));
+    } else {
+        let year_total: f64 = totals.values().sum();
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", day.year()), text),
+            Span::styled(app.fmt(year_total), bold),
+            Span::styled(
+                format!(
+                    " over {} days with spending, {} per spending day",
+                    totals.len(),
+                    app.fmt(year_total / totals.len() as f64)
+                ),
+                text,
+            ),
+        ]));
+
+        // Ties go to the earliest day.
+        if let Some((date, amount)) = totals.iter().max_by(|a, b| {
+            a.1.partial_cmp(b.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| b.0.cmp(a.0))
+        }) {
+            lines.push(Line::from(vec![
+                Span::styled("Biggest day: ", text),
+                Span::styled(
+                    date.format("%a %-d %b").to_string(),
+                    Style::default().fg(theme.heading),
+                ),
+                Span::styled(format!(" {}", app.fmt(*amount)), bold),
+            ]));
+        }
+
+        let mut by_weekday = [0.0; 7];
+        for (date, amount) in &totals {
+            by_weekday[date.weekday().num_days_from_monday() as usize] += amount;
+        }
+        let busiest = by_weekday
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(i, _)| i);
+        let mut spans = vec![Span::styled("By weekday: ", text)];
+        for (i, amount) in by_weekday.iter().enumerate() {
+            let style = if Some(i) == busiest { bold } else { muted };
+            spans.push(Span::styled(
+                format!("{} {}  ", WEEKDAYS[i], app.fmt_compact(*amount)),
+                style,
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let summary = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Summary ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_muted)),
+    );
+    f.render_widget(summary, area);
+}
+
+/// The selected day's expenses, opened with Enter on the heatmap.
+pub fn render_day_popup(f: &mut Frame, app: &App, area: Rect) {
+    let popup_area = super::centered_rect(60, 50, area);
+    f.render_widget(Clear, popup_area);
+
+    let theme = &app.theme;
+    let day = app.heatmap_day;
+    let expenses = app.expenses_on(day);
+    let total: f64 = expenses.iter().map(|e| e.amount).sum();
+
+    let block = Block::default()
+        .title(format!(" {} ", day.format("%A %-d %B %Y")))
+        .title(Line::from(format!(" Total: {} ", app.fmt(total))).right_aligned())
+        .title_bottom(
+            Line::from(format!(
+                " {}/{}:day {}",
+                app.keymap.primary(Action::DayPrev),
+                app.keymap.primary(Action::DayNext),
+                super::hints(app, &[Action::DayClose])
+            ))
+            .centered(),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    if expenses.is_empty() {
+        let empty = Paragraph::new("No expenses on this day")
+            .centered()
+            .style(Style::default().fg(theme.muted))
+            .block(block);
+        f.render_widget(empty, popup_area);
+        return;
+    }
+
+    let rows: Vec<Row> = expenses
+        .iter()
+        .map(|e| {
+            let tags: Vec<String> = e.tags.iter().map(|t| format!("#{}", t)).collect();
+            Row::new(vec![
+                Cell::from(e.category.to_string())
+                    .style(Style::default().fg(theme.category_color(&e.category))),
+                Cell::from(e.description.clone()).style(Style::default().fg(theme.text)),
+                Cell::from(tags.join(" ")).style(Style::default().fg(theme.tag)),
+                Cell::from(Line::from(app.fmt(e.amount)).right_aligned())
+                    .style(Style::default().fg(theme.accent)),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(14),
+            Constraint::Min(10),
+            Constraint::Length(16),
+            Constraint::Length(12),
+        ],
+    )
+    .block(block);
+    f.render_widget(table, popup_area);
+}
@@ -0,0 +1,288 @@
+use chrono::NaiveDate;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::app::App;
+use crate::keymap::Action;
+use crate::model::{Loan, LoanSummary};
+use crate::utils;
+
+/// Width of the text progress bar, in cells.
+const BAR_WIDTH: usize = 12;
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let today = utils::today();
+    let as_of = format!(" as of {} ", today.format("%-d %b %Y"));
+    let block = Block::default()
+        .title(" Loans ")
+        .title_bottom(Line::from(as_of).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border_muted));
+
+    if let Some(error) = &app.loans_error {
+        super::render_load_error(f, app, area, block, "loans", error);
+        return;
+    }
+    if app.loans.is_empty() {
+        let text = Paragraph::new(Span::styled(
+            format!(
+                "No loans yet. Press {} to add one, e.g. Car loan 18000 5.9% 60m.",
+                app.keymap.primary(Action::LoansNew)
+            ),
+            Style::default().fg(app.theme.muted),
+        ))
+        .block(block);
+        f.render_widget(text, area);
+        return;
+    }
+
+    let chunks = super::list_layout(app.loans.len(), area);
+    let heading = Style::default()
+        .fg(app.theme.heading)
+        .add_modifier(Modifier::BOLD);
+    let header = Row::new(vec![
+        Cell::from("Loan"),
+        super::right("Rate"),
+        super::right("Monthly"),
+        super::right("Borrowed"),
+        super::right("Balance"),
+        Cell::from("Paid off"),
+        Cell::from("Next due"),
+        Cell::from("Payoff"),
+        Cell::from("Status"),
+    ])
+    .style(heading)
+    .bottom_margin(1);
+
+    let summaries: Vec<LoanSummary> = app.loans.iter().map(|l| app.loan_summary(l)).collect();
+    let rows: Vec<Row> = app
+        .loans
+        .iter()
+        .zip(&summaries)
+        .map(|(loan, summary)| loan_row(app, loan, summary, today))
+        .collect();
+
+    let widths = [
+        Constraint::Min(12),
+        Constraint::Length(6),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(BAR_WIDTH as u16 + 6),
+        Constraint::Length(12),
+        Constraint::Length(9),
+        Constraint::Length(10),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .row_highlight_style(app.theme.selected_row())
+        .highlight_symbol(">> ");
+    let selected = app.loan_index.min(app.loans.len() - 1);
+    let mut state = TableState::default();
+    state.select(Some(selected));
+    f.render_stateful_widget(table, chunks[0], &mut state);
+
+    render_details(
+        f,
+        app,
+        &app.loans[selected],
+        &summaries[selected],
+        chunks[1],
+    );
+}
+
+/// The index of the loan row at (`col`, `row`), if any.
+pub fn loan_row_at(app: &App, area: Rect, col: u16, row: u16) -> Option<usize> {
+    let count = app.loans.len();
+    let table_area = super::list_layout(count, area)[0];
+    super::table_row_at(table_area, count, app.loan_index, col, row)
+}
+
+fn loan_row<'a>(app: &App, loan: &Loan, summary: &LoanSummary, today: NaiveDate) -> Row<'a> {
+    let text = Style::default().fg(app.theme.text);
+    let amount = |value: f64, style: Style| {
+        Cell::from(Line::from(app.fmt_compact(value)).right_aligned()).style(style)
+    };
+    let (status, style) = status(app, summary, today);
+
+    let ratio = if loan.principal > 0.0 {
+        (1.0 - summary.balance / loan.principal).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+    let paid = Style::default().fg(app.theme.positive);
+    let bar = super::progress_bar(app, ratio, BAR_WIDTH, paid);
+    let date = |date: Option<NaiveDate>, format: &str| {
+        Cell::from(Span::styled(
+            date.map(|d| d.format(format).to_string())
+                .unwrap_or_else(|| "·".to_string()),
+            text,
+        ))
+    };
+
+    Row::new(vec![
+        Cell::from(Span::styled(loan.name.clone(), text)),
+        Cell::from(Line::from(format!("{}%", loan.rate)).right_aligned()).style(text),
+        amount(loan.payment(), text),
+        amount(loan.principal, text),
+        amount(summary.balance, text.add_modifier(Modifier::BOLD)),
+        Cell::from(bar),
+        date(summary.next_due, "%-d %b %Y"),
+        date(summary.payoff, "%b %Y"),
+        Cell::from(Span::styled(status, style)),
+    ])
+}
+
+/// "paid off", "overdue" once the next payment is late, "no payoff" if the
+/// payment doesn't cover the interest, otherwise "current".
+fn status(app: &App, summary: &LoanSummary, today: NaiveDate) -> (&'static str, Style) {
+    if summary.paid_off() {
+        ("paid off", Style::default().fg(app.theme.positive))
+    } else if summary.next_due.is_some_and(|due| due < today) {
+        (
+            "overdue",
+            Style::default()
+                .fg(app.theme.negative)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else if summary.payoff.is_none() {
+        ("no payoff", Style::default().fg(app.theme.negative))
+    } else {
+        ("current", Style::default().fg(app.theme.text))
+    }
+}
+
+/// The selected loan's payments so far, split into interest and principal,
+/// next to its amortization schedule.
+fn render_details(f: &mut Frame, app: &App, loan: &Loan, summary: &LoanSummary, area: Rect) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    let heading = Style::default()
+        .fg(app.theme.heading)
+        .add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(app.theme.text);
+    let muted = Style::default().fg(app.theme.muted);
+    let amount = |value: f64, style: Style| {
+        Cell::from(Line::from(app.fmt_compact(value)).right_aligned()).style(style)
+    };
+    let widths = [
+        Constraint::Length(4),
+        Constraint::Length(12),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Min(9),
+    ];
+    let header = |date: &str, payment: &str| {
+        Row::new(vec![
+            super::right("#"),
+            Cell::from(date.to_string()),
+            super::right(payment),
+            super::right("Interest"),
+            super::right("Principal"),
+            super::right("Balance"),
+        ])
+        .style(heading)
+        .bottom_margin(1)
+    };
+
+    let payments: Vec<Row> = summary
+        .payments
+        .iter()
+        .rev()
+        .map(|p| {
+            Row::new(vec![
+                Cell::from(Line::from(p.number.to_string()).right_aligned()),
+                Cell::from(p.date.format("%-d %b %Y").to_string()),
+                amount(p.payment, text),
+                amount(p.interest, text),
+                amount(p.principal, text),
+                amount(p.balance, text),
+            ])
+            .style(text)
+        })
+        .collect();
+    let mut block = Block::default()
+        .title(format!(
+            " {} — Payments: {} interest, {} principal ",
+            loan.name,
+            app.fmt_compact(summary.interest_paid()),
+            app.fmt_compact(summary.principal_paid())
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border_muted));
+    if payments.is_empty() {
+        block = block.title_bottom(
+            Line::from(format!(" tag expenses #{} to record payments ", loan.tag)).centered(),
+        );
+    }
+    f.render_widget(
+        Table::new(payments, widths)
+            .header(header("Paid", "Paid"))
+            .block(block),
+        columns[0],
+    );
+
+    // Start at the last payment made, so the next one due is near the top.
+    let made = summary.payments.len();
+    let schedule: Vec<Row> = loan
+        .schedule()
+        .into_iter()
+        .skip(made.saturating_sub(1))
+        .map(|p| {
+            let style = if (p.number as usize) <= made {
+                muted
+            } else if p.number as usize == made + 1 {
+                text.add_modifier(Modifier::BOLD)
+            } else {
+                text
+            };
+            Row::new(vec![
+                Cell::from(Line::from(p.number.to_string()).right_aligned()),
+                Cell::from(p.date.format("%-d %b %Y").to_string()),
+                amount(p.payment, style),
+                amount(p.interest, style),
+                amount(p.principal, style),
+                amount(p.balance, style),
+            ])
+            .style(style)
+        })
+        .collect();
+    let total_interest = loan.schedule().iter().fold(0.0, |t, p| t + p.interest);
+    let block = Block::default()
+        .title(format!(
+            " Schedule: {} × {}, {} interest ",
+            loan.term_months,
+            app.fmt_compact(loan.payment()),
+            app.fmt_compact(total_interest)
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border_muted));
+    f.render_widget(
+        Table::new(schedule, widths)
+            .header(header("Due", "Payment"))
+            .block(block),
+        columns[1],
+    );
+}
+
+/// The new-loan prompt, docked like quick add.
+pub fn render_prompt(f: &mut Frame, app: &App, content: Rect) {
+    super::render_entry_prompt(
+        f,
+        app,
+        content,
+        " New loan ",
+        &app.loan_input,
+        "e.g. Car loan 18000 5.9% 60m @2026-11-15 #carloan, or Rent from Sam 500 0% 5m",
+        app.loan_prompt_preview(),
+    );
+}
@@ -1,27 +1,46 @@
 pub mod add_form;
+pub mod budgets;
 pub mod dashboard;
 pub mod expenses;
+pub mod goals;
+pub mod heatmap;
+pub mod loans;
 pub mod monthly;
+pub mod palette;
+pub mod subscriptions;
+pub mod templates;
+pub mod yearly;
+
+use std::rc::Rc;
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph, Tabs},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Tabs, Wrap},
     Frame,
 };
 
+use unicode_width::UnicodeWidthStr;
+
 use crate::app::{App, InputMode, Tab};
+use crate::keymap::{Action, KeyContext};
+use crate::text_input::TextInput;
 
-pub fn draw(f: &mut Frame, app: &App) {
-    let chunks = Layout::default()
+/// Split the screen into tab bar, content and status bar.
+pub fn screen_layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),
             Constraint::Min(10),
             Constraint::Length(1),
         ])
-        .split(f.area());
+        .split(area)
+}
+
+pub fn draw(f: &mut Frame, app: &App) {
+    let chunks = screen_layout(f.area());
 
     render_tabs(f, app, chunks[0]);
     render_content(f, app, chunks[1]);
@@ -32,15 +51,66 @@ pub fn draw(f: &mut Frame, app: &App) {
         add_form::render(f, app, f.area());
     }
 
+    if app.input_mode == InputMode::ColumnEditor {
+        expenses::render_column_editor(f, app, f.area());
+    }
+
+    if matches!(
+        app.input_mode,
+        InputMode::BulkMenu | InputMode::BulkInput | InputMode::ConfirmBulk
+    ) {
+        expenses::render_bulk_popup(f, app, f.area());
+    }
+
+    if app.input_mode == InputMode::QuickAdd {
+        render_quick_add(f, app, chunks[1]);
+    }
+
+    if app.input_mode == InputMode::GoalPrompt {
+        goals::render_prompt(f, app, chunks[1]);
+    }
+
+    if app.input_mode == InputMode::LoanPrompt {
+        loans::render_prompt(f, app, chunks[1]);
+    }
+
+    if app.input_mode == InputMode::CancelSubscription {
+        subscriptions::render_cancel_prompt(f, app, chunks[1]);
+    }
+
+    if app.input_mode == InputMode::SubscriptionPrice {
+        subscriptions::render_price_prompt(f, app, chunks[1]);
+    }
+
+    if app.input_mode == InputMode::AnomalyReview {
+        expenses::render_review(f, app, f.area());
+    }
+
+    if app.input_mode == InputMode::CommandPalette {
+        palette::render(f, app, f.area());
+    }
+
+    if app.input_mode == InputMode::DayDetail {
+        heatmap::render_day_popup(f, app, f.area());
+    }
+
+    if app.input_mode == InputMode::BudgetAlert {
+        add_form::render_budget_alert(f, app, f.area());
+    }
+
+    if app.input_mode == InputMode::TemplatePicker {
+        templates::render(f, app, f.area());
+    }
+
     if app.input_mode == InputMode::HelpPopup {
-        render_help_popup(f, f.area());
+        render_help_popup(f, app, f.area());
     }
 }
 
 fn render_tabs(f: &mut Frame, app: &App, area: Rect) {
     let titles: Vec<Line> = Tab::titles()
         .iter()
-        .map(|t| Line::from(Span::styled(*t, Style::default().fg(Color::White))))
+        .map(|t| Line::from(Span::styled(*t, Style::default().fg(app.theme.text))))
         .collect();
 
     let tabs = Tabs::new(titles)
@@ -48,42 +118,118 @@ fn render_tabs(f: &mut Frame, app: &App, area: Rect) {
             Block::default()
                 .title(" Cashflow ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(Style::default().fg(app.theme.border)),
         )
         .select(app.active_tab.index())
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(app.theme.muted))
         .highlight_style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(app.theme.accent)
                 .add_modifier(Modifier::BOLD),
         );
 
     f.render_widget(tabs, area);
 }
 
+/// The tab whose title is under (`col`, `row`), mirroring how `Tabs` lays out
+/// its titles: one space of padding either side and a one-column divider.
+pub fn tab_at(area: Rect, col: u16, row: u16) -> Option<Tab> {
+    let tabs_area = screen_layout(area)[0];
+    if row != tabs_area.y + 1 {
+        return None;
+    }
+    let mut x = tabs_area.x + 1;
+    for (i, title) in Tab::titles().iter().enumerate() {
+        let end = x + title.width() as u16 + 2;
+        if col >= x && col < end {
+            return Some(Tab::from_index(i));
+        }
+        x = end + 1;
+    }
+    None
+}
+
 fn render_content(f: &mut Frame, app: &App, area: Rect) {
     match app.active_tab {
         Tab::Dashboard => dashboard::render(f, app, area),
         Tab::Expenses => expenses::render(f, app, area),
         Tab::Monthly => monthly::render(f, app, area),
+        Tab::Yearly => yearly::render(f, app, area),
+        Tab::Heatmap => heatmap::render(f, app, area),
+        Tab::Budgets => budgets::render(f, app, area),
+        Tab::Goals => goals::render(f, app, area),
+        Tab::Loans => loans::render(f, app, area),
+        Tab::Subscriptions => subscriptions::render(f, app, area),
     }
 }
 
+/// Budgets past an alert threshold this period, e.g. `⚠ Food 104%`.
+fn budget_warning(app: &App) -> Span<'static> {
+    let warnings = &app.budget_warnings;
+    let Some((_, highest)) = warnings.first() else {
+        return Span::raw("");
+    };
+    let color = if *highest >= 100.0 {
+        app.theme.negative
+    } else {
+        app.theme.accent
+    };
+    let list: Vec<String> = warnings
+        .iter()
+        .map(|(name, used)| format!("{} {:.0}%", name, used.min(999.0)))
+        .collect();
+    Span::styled(
+        format!(" ⚠ {} ", list.join(", ")),
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    )
+}
+
 fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
     let text = if let Some(ref msg) = app.status_message {
-        Line::from(Span::styled(msg.as_str(), Style::default().fg(Color::Green)))
+        Line::from(Span::styled(msg.as_str(), Style::default().fg(app.theme.positive)))
+    } else if !app.pending_keys.is_empty() {
+        let pressed: Vec<String> = app.pending_keys.iter().map(|k| k.to_string()).collect();
+        Line::from(Span::styled(
+            format!(" {} …", pressed.join(" ")),
+            Style::default().fg(app.theme.accent),
+        ))
     } else {
+        let keymap = &app.keymap;
+        let tabs = [
+            Action::TabDashboard,
+            Action::TabExpenses,
+            Action::TabMonthly,
+            Action::TabYearly,
+            Action::TabHeatmap,
+            Action::TabBudgets,
+            Action::TabGoals,
+            Action::TabLoans,
+            Action::TabSubscriptions,
+        ]
+            .iter()
+            .map(|a| keymap.primary(*a))
+            .collect::<Vec<_>>()
+            .join("/");
+        let hints = [
+            keymap.hint(Action::Quit),
+            keymap.hint(Action::Help),
+            format!("{}:tabs", tabs),
+            keymap.hint(Action::AddExpense),
+            keymap.hint(Action::CurrencyNext),
+            keymap.hint(Action::Export),
+        ];
         Line::from(vec![
             Span::styled(
-                " q:quit  ?:help  1-3:tabs  a:add  c:currency  x:export ",
-                Style::default().fg(Color::DarkGray),
+                format!(" {} ", hints.join("  ")),
+                Style::default().fg(app.theme.muted),
             ),
             Span::styled(
                 format!(" [{}] ", app.currency.display_name()),
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(app.theme.heading)
                     .add_modifier(Modifier::BOLD),
             ),
+            budget_warning(app),
         ])
     };
 
@@ -91,75 +237,308 @@ fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(bar, area);
 }
 
-fn render_help_popup(f: &mut Frame, area: Rect) {
-    let popup_area = centered_rect(50, 60, area);
-    f.render_widget(Clear, popup_area);
+/// The `:` prompt, docked to the bottom of the content area, with a preview
+/// of the parsed expense underneath.
+fn render_quick_add(f: &mut Frame, app: &App, content: Rect) {
+    let height = 4.min(content.height);
+    let area = Rect::new(content.x, content.bottom() - height, content.width, height);
+    f.render_widget(Clear, area);
 
-    let help_text = vec![
+    let (visible, cursor) = app.quick_add.view(area.width.saturating_sub(4));
+    f.set_cursor_position((area.x + 3 + cursor, area.y + 1));
+
+    let preview = if app.quick_add.is_empty() {
         Line::from(Span::styled(
-            "Global Keybindings",
+            "e.g. 12.50 food lunch with team @yesterday #work",
+            Style::default().fg(app.theme.muted),
+        ))
+    } else {
+        match app.quick_add_preview() {
+            Ok(expense) => {
+                let mut spans = vec![
+                    Span::styled(
+                        app.fmt(expense.amount),
+                        Style::default()
+                            .fg(app.theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("  "),
+                    Span::styled(
+                        expense.category.to_string(),
+                        Style::default().fg(app.theme.category_color(&expense.category)),
+                    ),
+                    Span::raw("  "),
+                    Span::styled(expense.description.clone(), Style::default().fg(app.theme.text)),
+                    Span::styled(
+                        format!("  {}", expense.date.format("%a %-d %b %Y")),
+                        Style::default().fg(app.theme.heading),
+                    ),
+                ];
+                if !expense.tags.is_empty() {
+                    let tags: Vec<String> = expense.tags.iter().map(|t| format!("#{}", t)).collect();
+                    spans.push(Span::styled(
+                        format!("  {}", tags.join(" ")),
+                        Style::default().fg(app.theme.tag),
+                    ));
+                }
+                Line::from(spans)
+            }
+            Err(e) => Line::from(Span::styled(e, Style::default().fg(app.theme.negative))),
+        }
+    };
+
+    let text = vec![
+        Line::from(vec![
+            Span::styled(": ", Style::default().fg(app.theme.accent)),
+            Span::styled(visible, Style::default().fg(app.theme.text)),
+        ]),
+        preview,
+    ];
+    let prompt = Paragraph::new(text).block(
+        Block::default()
+            .title(" Quick add ")
+            .title_bottom(Line::from(" Enter:add  Esc:cancel ").right_aligned())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent)),
+    );
+    f.render_widget(prompt, area);
+}
+
+/// A one-line entry prompt for a tab, docked to the bottom of the content
+/// area like quick add, with an example while empty and then a preview of
+/// what Enter would save.
+fn render_entry_prompt(
+    f: &mut Frame,
+    app: &App,
+    content: Rect,
+    title: &str,
+    input: &TextInput,
+    example: &str,
+    preview: Result<String, String>,
+) {
+    let height = 4.min(content.height);
+    let area = Rect::new(content.x, content.bottom() - height, content.width, height);
+    f.render_widget(Clear, area);
+
+    let (visible, cursor) = input.view(area.width.saturating_sub(4));
+    f.set_cursor_position((area.x + 3 + cursor, area.y + 1));
+
+    let preview = if input.is_empty() {
+        Line::from(Span::styled(
+            example.to_string(),
+            Style::default().fg(app.theme.muted),
+        ))
+    } else {
+        match preview {
+            Ok(text) => Line::from(Span::styled(text, Style::default().fg(app.theme.text))),
+            Err(e) => Line::from(Span::styled(e, Style::default().fg(app.theme.negative))),
+        }
+    };
+
+    let text = vec![
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(app.theme.accent)),
+            Span::styled(visible, Style::default().fg(app.theme.text)),
+        ]),
+        preview,
+    ];
+    let prompt = Paragraph::new(text).block(
+        Block::default()
+            .title(title.to_string())
+            .title_bottom(Line::from(" Enter:save  Esc:cancel ").right_aligned())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent)),
+    );
+    f.render_widget(prompt, area);
+}
+
+/// Help lines for keys that aren't in the keymap because they edit a field.
+const FORM_FIELD_HELP: &[(&str, &str)] = &[
+    ("←/→", "Cycle options / move cursor"),
+    ("Space", "Toggle boolean"),
+    ("Home/End", "Start / end of text"),
+    ("Ctrl+W", "Delete previous word"),
+    ("Ctrl+U/K", "Delete to start / end"),
+];
+
+fn render_help_popup(f: &mut Frame, app: &App, area: Rect) {
+    let popup_area = centered_rect(50, 90, area);
+    f.render_widget(Clear, popup_area);
+
+    let heading = Style::default()
+        .fg(app.theme.heading)
+        .add_modifier(Modifier::BOLD);
+    let mut help_text = Vec::new();
+
+    for context in KeyContext::all() {
+        if !help_text.is_empty() {
+            help_text.push(Line::from(""));
+        }
+        help_text.push(Line::from(Span::styled(context.title(), heading)));
+        help_text.push(Line::from(""));
+        for action in Action::all().iter().filter(|a| a.context() == *context) {
+            help_text.push(help_line(&app.keymap.display(*action), action.description()));
+        }
+        if *context == KeyContext::Form {
+            for (keys, description) in FORM_FIELD_HELP {
+                help_text.push(help_line(keys, description));
+            }
+        }
+    }
+
+    let help = Paragraph::new(help_text)
+        .scroll((app.help_scroll, 0))
+        .block(
+            Block::default()
+                .title(" Help ")
+                .title_bottom(
+                    Line::from(format!(
+                        " {} or {} to close, {}/{} to scroll ",
+                        app.keymap.primary(Action::Help),
+                        app.keymap.primary(Action::HelpClose),
+                        app.keymap.primary(Action::HelpDown),
+                        app.keymap.primary(Action::HelpUp)
+                    ))
+                    .centered(),
+                )
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.accent)),
+        );
+
+    f.render_widget(help, popup_area);
+}
+
+/// ` key:hint  key:hint ` for a popup footer, from the current keymap.
+fn hints(app: &App, actions: &[Action]) -> String {
+    let hints: Vec<String> = actions.iter().map(|a| app.keymap.hint(*a)).collect();
+    format!(" {} ", hints.join("  "))
+}
+
+fn help_line(keys: &str, description: &str) -> Line<'static> {
+    let pad = 13usize.saturating_sub(keys.width()).max(1);
+    Line::from(format!("  {}{}{}", keys, " ".repeat(pad), description))
+}
+
+/// A ` < label > ` selector centered in a bordered block, as used for the
+/// month and year pickers.
+fn render_selector(f: &mut Frame, app: &App, area: Rect, label: &str, title: &str) {
+    let arrow = Style::default()
+        .fg(app.theme.accent)
+        .add_modifier(Modifier::BOLD);
+    let text = Line::from(vec![
+        Span::styled(" < ", arrow),
+        Span::styled(
+            label.to_string(),
             Style::default()
-                .fg(Color::Cyan)
+                .fg(app.theme.text)
                 .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(""),
-        Line::from("  q, Ctrl+C    Quit"),
-        Line::from("  1-3          Switch tabs"),
-        Line::from("  Tab          Next tab"),
-        Line::from("  Shift+Tab    Previous tab"),
-        Line::from("  a            Add new expense"),
-        Line::from("  c/C          Cycle currency forward/back"),
-        Line::from("  x            Export to CSV"),
-        Line::from("  ?            Toggle this help"),
-        Line::from(""),
+        ),
+        Span::styled(" > ", arrow),
+    ]);
+
+    let selector = Paragraph::new(text).centered().block(
+        Block::default()
+            .title(title.to_string())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border_muted)),
+    );
+
+    f.render_widget(selector, area);
+}
+
+/// `-1` or `1` if (`col`, `row`) is on the previous/next arrow of a selector
+/// drawn by `render_selector` in `area`.
+fn selector_arrow_at(area: Rect, label: &str, col: u16, row: u16) -> Option<i32> {
+    if row != area.y + 1 {
+        return None;
+    }
+    let label_width = label.width() as u16;
+    let inner_width = area.width.saturating_sub(2);
+    let start = area.x + 1 + inner_width.saturating_sub(label_width + 6) / 2;
+    if col >= start && col < start + 3 {
+        Some(-1)
+    } else if col >= start + 3 + label_width && col < start + 6 + label_width {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+/// A list tab's table on top and the selection's details below. The table
+/// gets room for every row (borders, header and its margin included), but
+/// never more than half the tab.
+fn list_layout(rows: usize, area: Rect) -> Rc<[Rect]> {
+    let height = (rows as u16).saturating_add(4);
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(height.min(area.height / 2).max(5)),
+            Constraint::Min(5),
+        ])
+        .split(area)
+}
+
+/// The row of a bordered table with a header, `count` rows and `selected`
+/// highlighted that is under (`col`, `row`).
+fn table_row_at(area: Rect, count: usize, selected: usize, col: u16, row: u16) -> Option<usize> {
+    let first_row = area.y + 3;
+    let visible = area.height.saturating_sub(4) as usize;
+    if count == 0
+        || visible == 0
+        || row < first_row
+        || row >= first_row + visible as u16
+        || col <= area.x
+        || col >= area.right() - 1
+    {
+        return None;
+    }
+    // A fresh `TableState` scrolls just far enough to keep the selection visible.
+    let offset = selected.min(count - 1).saturating_sub(visible - 1);
+    let index = offset + (row - first_row) as usize;
+    (index < count).then_some(index)
+}
+
+/// A right-aligned table cell.
+fn right(s: &str) -> Cell<'static> {
+    Cell::from(Line::from(s.to_string()).right_aligned())
+}
+
+/// A `█████░░░  42%` bar `width` cells wide. The bar stops filling at 100%,
+/// the percentage doesn't.
+fn progress_bar(app: &App, ratio: f64, width: usize, style: Style) -> Line<'static> {
+    let filled = ((ratio.clamp(0.0, 1.0) * width as f64).round() as usize).min(width);
+    Line::from(vec![
+        Span::styled("█".repeat(filled), style),
+        Span::styled("░".repeat(width - filled), Style::default().fg(app.theme.muted)),
+        Span::styled(format!(" {:>3.0}%", ratio * 100.0), style),
+    ])
+}
+
+/// In place of a tab's table: why its file couldn't be loaded, e.g. `what`
+/// "budgets" and `error` "budgets.csv: line 3: ...".
+fn render_load_error(f: &mut Frame, app: &App, area: Rect, block: Block, what: &str, error: &str) {
+    let text = vec![
         Line::from(Span::styled(
-            "Expenses Tab",
+            format!("Could not load {}", what),
             Style::default()
-                .fg(Color::Cyan)
+                .fg(app.theme.negative)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
-        Line::from("  j/↓          Move down"),
-        Line::from("  k/↑          Move up"),
-        Line::from("  /            Search"),
-        Line::from("  e            Edit selected"),
-        Line::from("  d            Delete selected"),
-        Line::from("  r            Toggle recurring filter"),
-        Line::from(""),
         Line::from(Span::styled(
-            "Monthly Tab",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
+            error.to_string(),
+            Style::default().fg(app.theme.text),
         )),
         Line::from(""),
-        Line::from("  ←/h          Previous month"),
-        Line::from("  →/l          Next month"),
-        Line::from(""),
         Line::from(Span::styled(
-            "Form",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
+            "The file is left untouched until it is fixed and cashflow restarted.",
+            Style::default().fg(app.theme.muted),
         )),
-        Line::from(""),
-        Line::from("  Tab          Next field"),
-        Line::from("  Shift+Tab    Previous field"),
-        Line::from("  ←/→          Cycle options"),
-        Line::from("  Space        Toggle boolean"),
-        Line::from("  Enter        Save"),
-        Line::from("  Esc          Cancel"),
     ];
-
-    let help = Paragraph::new(help_text).block(
-        Block::default()
-            .title(" Help ")
-            .title_bottom(Line::from(" Press ? or Esc to close ").centered())
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow)),
+    f.render_widget(
+        Paragraph::new(text).wrap(Wrap { trim: false }).block(block),
+        area,
     );
-
-    f.render_widget(help, popup_area);
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -1,24 +1,145 @@
-use chrono::Month;
+use std::rc::Rc;
+
+use chrono::{Datelike, Month, NaiveDate};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Gauge, Paragraph},
+    widgets::{Block, Borders, Cell, Gauge, Paragraph, Row, Table},
     Frame,
 };
 
-use crate::app::App;
-use crate::model::Category;
+use crate::app::{self, App};
+use crate::forecast::Forecast;
+use crate::keymap::Action;
+use crate::model::{Category, Period};
+use crate::utils;
 
-pub fn render(f: &mut Frame, app: &App, area: Rect) {
-    let chunks = Layout::default()
+fn layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(3), Constraint::Min(5), Constraint::Length(3)])
-        .split(area);
+        .split(area)
+}
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = layout(area);
+    let forecast = current_forecast(app);
 
     render_month_selector(f, app, chunks[0]);
-    render_category_breakdown(f, app, chunks[1]);
-    render_total_summary(f, app, chunks[2]);
+    match app.comparison_month() {
+        Some((year, month)) => render_comparison(f, app, chunks[1], year, month),
+        None => render_category_breakdown(f, app, forecast.as_ref(), chunks[1]),
+    }
+    render_total_summary(f, app, forecast.as_ref(), chunks[2]);
+}
+
+/// How many of the largest changes are highlighted in the comparison.
+const HIGHLIGHTED_CHANGES: usize = 3;
+
+fn short_month(year: i32, month: u32) -> String {
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .map(|d| d.format("%b %Y").to_string())
+        .unwrap_or_default()
+}
+
+/// An arrow and color for a change in spending: up is bad, down is good.
+fn change_indicator(app: &App, delta: f64) -> (&'static str, Style) {
+    if delta >= 0.005 {
+        ("▲", Style::default().fg(app.theme.negative))
+    } else if delta <= -0.005 {
+        ("▼", Style::default().fg(app.theme.positive))
+    } else {
+        ("=", Style::default().fg(app.theme.muted))
+    }
+}
+
+fn signed(app: &App, delta: f64) -> String {
+    let sign = if delta < 0.0 { "-" } else { "+" };
+    format!("{}{}", sign, app.fmt(delta.abs()))
+}
+
+fn percent_label(current: f64, previous: f64) -> String {
+    match app::percent_change(current, previous) {
+        Some(p) => format!("{:+.0}%", p),
+        None => "new".to_string(),
+    }
+}
+
+fn render_comparison(f: &mut Frame, app: &App, area: Rect, year: i32, month: u32) {
+    let current = short_month(app.selected_year, app.selected_month);
+    let previous = short_month(year, month);
+    let block = Block::default()
+        .title(format!(" {} vs {} ", current, previous))
+        .title_bottom(view_hint(app))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border_muted));
+
+    let changes = app.category_changes(year, month);
+    if changes.is_empty() {
+        let empty = Paragraph::new("No expenses in either month")
+            .centered()
+            .style(Style::default().fg(app.theme.muted))
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let heading = Style::default()
+        .fg(app.theme.heading)
+        .add_modifier(Modifier::BOLD);
+    let header = Row::new(vec![
+        Cell::from("Category"),
+        Cell::from(Line::from(current).right_aligned()),
+        Cell::from(Line::from(previous).right_aligned()),
+        Cell::from(Line::from("Change").right_aligned()),
+        Cell::from(Line::from("%").right_aligned()),
+    ])
+    .style(heading)
+    .bottom_margin(1);
+
+    // Changes are sorted biggest first, so the leading rows are highlighted.
+    let rows: Vec<Row> = changes
+        .iter()
+        .enumerate()
+        .map(|(i, change)| {
+            let delta = change.delta();
+            let (arrow, style) = change_indicator(app, delta);
+            let highlight = i < HIGHLIGHTED_CHANGES && delta.abs() >= 0.005;
+            let name_style =
+                Style::default().fg(app.theme.category_color_by_name(&change.category));
+            let row = Row::new(vec![
+                Cell::from(change.category.clone()).style(name_style),
+                Cell::from(Line::from(app.fmt(change.current)).right_aligned())
+                    .style(Style::default().fg(app.theme.text)),
+                Cell::from(Line::from(app.fmt(change.previous)).right_aligned())
+                    .style(Style::default().fg(app.theme.muted)),
+                Cell::from(Line::from(format!("{} {}", arrow, signed(app, delta))).right_aligned())
+                    .style(style),
+                Cell::from(Line::from(percent_label(change.current, change.previous)).right_aligned()).style(style),
+            ]);
+            if highlight {
+                row.style(Style::default().add_modifier(Modifier::BOLD))
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(14),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(14),
+            Constraint::Length(7),
+        ],
+    )
+    .header(header)
+    .block(block);
+
+    f.render_widget(table, area);
 }
 
 fn month_name(month: u32) -> &'static str {
@@ -41,50 +162,60 @@ fn month_name(month: u32) -> &'static str {
     }
 }
 
-fn render_month_selector(f: &mut Frame, app: &App, area: Rect) {
-    let text = Line::from(vec![
-        Span::styled(
-            " < ",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(
-            format!("{} {}", month_name(app.selected_month), app.selected_year),
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(
-            " > ",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        ),
-    ]);
+fn selector_label(app: &App) -> String {
+    format!("{} {}", month_name(app.selected_month), app.selected_year)
+}
 
-    let selector = Paragraph::new(text).centered().block(
-        Block::default()
-            .title(" Month (←/→ to navigate) ")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray)),
+/// `-1` or `1` if (`col`, `row`) is on the previous/next arrow of the month
+/// selector.
+pub fn month_arrow_at(app: &App, area: Rect, col: u16, row: u16) -> Option<i32> {
+    super::selector_arrow_at(layout(area)[0], &selector_label(app), col, row)
+}
+
+fn render_month_selector(f: &mut Frame, app: &App, area: Rect) {
+    let title = format!(
+        " Month ({}/{} to navigate) ",
+        app.keymap.primary(Action::PrevMonth),
+        app.keymap.primary(Action::NextMonth)
     );
+    super::render_selector(f, app, area, &selector_label(app), &title);
+}
+
+/// The key to switch to the next view, shown under the main panel.
+fn view_hint(app: &App) -> Line<'static> {
+    Line::from(format!(
+        " {}: {} ",
+        app.keymap.primary(Action::MonthlyCompare),
+        app.monthly_view.next().label()
+    ))
+    .right_aligned()
+}
 
-    f.render_widget(selector, area);
+/// The month-end forecast, when the selected month is the current one.
+fn current_forecast(app: &App) -> Option<Forecast> {
+    let today = utils::today();
+    (app.selected_year == today.year() && app.selected_month == today.month())
+        .then(|| app.forecast())
 }
 
-fn render_category_breakdown(f: &mut Frame, app: &App, area: Rect) {
+fn render_category_breakdown(
+    f: &mut Frame,
+    app: &App,
+    forecast: Option<&Forecast>,
+    area: Rect,
+) {
     let spending = app.spending_by_category(app.selected_year, app.selected_month);
 
     if spending.is_empty() {
         let empty = Paragraph::new("No expenses for this month")
             .centered()
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(app.theme.muted))
             .block(
                 Block::default()
                     .title(" Category Breakdown ")
+                    .title_bottom(view_hint(app))
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::DarkGray)),
+                    .border_style(Style::default().fg(app.theme.border_muted)),
             );
         f.render_widget(empty, area);
         return;
@@ -100,8 +231,9 @@ fn render_category_breakdown(f: &mut Frame, app: &App, area: Rect) {
 
     let inner_block = Block::default()
         .title(" Category Breakdown ")
+        .title_bottom(view_hint(app))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray));
+        .border_style(Style::default().fg(app.theme.border_muted));
     let inner_area = inner_block.inner(area);
     f.render_widget(inner_block, area);
 
@@ -110,38 +242,45 @@ fn render_category_breakdown(f: &mut Frame, app: &App, area: Rect) {
         .constraints(constraints)
         .split(inner_area);
 
-    let colors = [
-        Color::Green,
-        Color::Yellow,
-        Color::Blue,
-        Color::Red,
-        Color::Magenta,
-        Color::Cyan,
-        Color::LightGreen,
-        Color::LightYellow,
-        Color::LightBlue,
-        Color::LightRed,
-    ];
-
     let max_spending = spending.iter().map(|(_, v)| *v).fold(0.0_f64, f64::max);
+    let as_of = app.budget_date(app.selected_year, app.selected_month);
 
     for (i, (cat_name, amount)) in spending.iter().take(num_cats).enumerate() {
-        let cat_enum = category_from_name(cat_name);
-        let budget = cat_enum
+        let category = category_from_name(cat_name);
+        let budget = category
             .as_ref()
-            .and_then(|c| app.budget_for_category(c));
+            .and_then(|c| app.budget_for(c, as_of))
+            .map(|(budget, _)| budget);
+        let envelope = category.as_ref().and_then(|c| app.envelope(c, as_of));
 
-        let (ratio, label) = if let Some(limit) = budget {
-            let r = (amount / limit).min(1.0);
-            (
-                r,
-                format!(
-                    "{}: {} / {}",
-                    cat_name,
-                    app.fmt_compact(*amount),
-                    app.fmt_compact(limit)
-                ),
-            )
+        let (ratio, label) = if let (Some(budget), Some(envelope)) = (budget, envelope) {
+            let limit = envelope.available();
+            // A non-monthly budget is measured over its own window.
+            let spent = if budget.period == Period::Monthly {
+                *amount
+            } else {
+                envelope.spent
+            };
+            // Nothing left to spend after a rolled-over deficit counts as full.
+            let r = if limit > 0.0 { (spent / limit).min(1.0) } else { 1.0 };
+            let mut label = format!(
+                "{}: {} / {}",
+                cat_name,
+                app.fmt_compact(spent),
+                app.fmt_compact(limit)
+            );
+            if budget.period != Period::Monthly {
+                label.push_str(&format!(" {}", budget.period_label()));
+            }
+            if envelope.carried.abs() >= 0.005 {
+                let sign = if envelope.carried < 0.0 { "-" } else { "+" };
+                label.push_str(&format!(
+                    " ({}{} rolled over)",
+                    sign,
+                    app.fmt_compact(envelope.carried.abs())
+                ));
+            }
+            (r, label)
         } else {
             let r = if max_spending > 0.0 {
                 amount / max_spending
@@ -150,50 +289,94 @@ fn render_category_breakdown(f: &mut Frame, app: &App, area: Rect) {
             };
             (r, format!("{}: {}", cat_name, app.fmt(*amount)))
         };
+        // The month-end forecast only says something about monthly limits.
+        let monthly_limit = envelope
+            .filter(|_| budget.is_some_and(|b| b.period == Period::Monthly))
+            .map(|e| e.available());
 
-        let color = if budget.is_some() && ratio > 0.9 {
-            Color::Red
+        let projected = forecast
+            .and_then(|fc| fc.for_category(cat_name))
+            .map(|c| c.projected);
+        let label = match projected {
+            Some(p) => format!("{}  → {} by month end", label, app.fmt_compact(p)),
+            None => label,
+        };
+
+        let color = if envelope.is_some() && ratio > 0.9 {
+            app.theme.negative
+        } else if matches!((monthly_limit, projected), (Some(limit), Some(p)) if p > limit) {
+            // On course to go over: warn before it happens.
+            app.theme.accent
         } else {
-            colors[i % colors.len()]
+            app.theme.category_color_by_name(cat_name)
         };
 
         let gauge = Gauge::default()
             .gauge_style(Style::default().fg(color))
-            .label(Span::styled(label, Style::default().fg(Color::White)))
+            .label(Span::styled(label, Style::default().fg(app.theme.text)))
             .ratio(ratio.min(1.0));
 
         f.render_widget(gauge, rows[i]);
     }
 }
 
-fn render_total_summary(f: &mut Frame, app: &App, area: Rect) {
+fn render_total_summary(f: &mut Frame, app: &App, forecast: Option<&Forecast>, area: Rect) {
     let total = app.total_for_month(app.selected_year, app.selected_month);
-    let total_budget: f64 = app.budgets.iter().map(|b| b.monthly_limit).sum();
+    let limit = app.spending_limit(app.selected_year, app.selected_month);
+    let limit_label = if app.monthly_cap.is_some() { "Cap" } else { "Budget" };
 
-    let text = if total_budget > 0.0 {
-        let remaining = total_budget - total;
+    let mut text = if let Some((year, month)) = app.comparison_month() {
+        let previous = app.total_for_month(year, month);
+        let (arrow, style) = change_indicator(app, total - previous);
+        Line::from(vec![
+            Span::styled(
+                format!("Total: {}", app.fmt(total)),
+                Style::default()
+                    .fg(app.theme.text)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  vs {}  ", app.fmt(previous)),
+                Style::default().fg(app.theme.muted),
+            ),
+            Span::styled(
+                format!(
+                    "{} {} ({})",
+                    arrow,
+                    signed(app, total - previous),
+                    percent_label(total, previous)
+                ),
+                style.add_modifier(Modifier::BOLD),
+            ),
+        ])
+    } else if limit > 0.0 {
+        let remaining = limit - total;
         let status = if remaining >= 0.0 {
             Span::styled(
                 format!("{} remaining", app.fmt(remaining)),
-                Style::default().fg(Color::Green),
+                Style::default().fg(app.theme.positive),
             )
         } else {
             Span::styled(
-                format!("{} over budget!", app.fmt(remaining.abs())),
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                format!(
+                    "{} over {}!",
+                    app.fmt(remaining.abs()),
+                    limit_label.to_lowercase()
+                ),
+                Style::default().fg(app.theme.negative).add_modifier(Modifier::BOLD),
             )
         };
         Line::from(vec![
             Span::styled(
                 format!("Total: {}", app.fmt(total)),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(app.theme.text)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("  |  "),
             Span::styled(
-                format!("Budget: {}", app.fmt(total_budget)),
-                Style::default().fg(Color::Yellow),
+                format!("{}: {}", limit_label, app.fmt(limit)),
+                Style::default().fg(app.theme.accent),
             ),
             Span::raw("  |  "),
             status,
@@ -202,15 +385,45 @@ fn render_total_summary(f: &mut Frame, app: &App, area: Rect) {
         Line::from(Span::styled(
             format!("Total Spent: {}", app.fmt(total)),
             Style::default()
-                .fg(Color::White)
+                .fg(app.theme.text)
                 .add_modifier(Modifier::BOLD),
         ))
     };
 
+    if app.comparison_month().is_none() && !app.budgets.is_empty() {
+        let unbudgeted: f64 = app
+            .unbudgeted_spending(app.selected_year, app.selected_month)
+            .iter()
+            .map(|(_, amount)| amount)
+            .sum();
+        if unbudgeted > 0.0 {
+            text.push_span(Span::raw("  |  "));
+            text.push_span(Span::styled(
+                format!("Unbudgeted: {}", app.fmt(unbudgeted)),
+                Style::default().fg(app.theme.muted),
+            ));
+        }
+    }
+
+    if let (None, Some(forecast)) = (app.comparison_month(), forecast) {
+        let projected = forecast.projected();
+        text.push_span(Span::raw("  |  "));
+        text.push_span(Span::styled(
+            format!("Forecast: {}", app.fmt(projected)),
+            Style::default().fg(app.theme.heading),
+        ));
+        if limit > 0.0 && projected > limit {
+            text.push_span(Span::styled(
+                format!(" ({} over)", app.fmt(projected - limit)),
+                Style::default().fg(app.theme.negative),
+            ));
+        }
+    }
+
     let summary = Paragraph::new(text).centered().block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(Style::default().fg(app.theme.border_muted)),
     );
 
     f.render_widget(summary, area);
@@ -0,0 +1,102 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::{App, PaletteEntry};
+use crate::keymap::KeyContext;
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let popup_area = super::centered_rect(60, 60, area);
+    f.render_widget(Clear, popup_area);
+
+    let theme = &app.theme;
+    let block = Block::default()
+        .title(" Command Palette ")
+        .title_bottom(Line::from(" ↑/↓:select  Enter:run  Esc:close ").centered())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(1)])
+        .split(inner);
+
+    let (visible, cursor) = app.palette_query.view(chunks[0].width.saturating_sub(2));
+    f.set_cursor_position((chunks[0].x + 2 + cursor, chunks[0].y));
+    let prompt = if app.palette_query.is_empty() {
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme.accent)),
+            Span::styled("type to filter actions", Style::default().fg(theme.muted)),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme.accent)),
+            Span::styled(visible, Style::default().fg(theme.text)),
+        ])
+    };
+    f.render_widget(Paragraph::new(prompt), chunks[0]);
+
+    let entries = app.palette_entries();
+    if entries.is_empty() {
+        f.render_widget(
+            Paragraph::new(Span::styled(" No matching actions", Style::default().fg(theme.muted))),
+            chunks[1],
+        );
+        return;
+    }
+
+    // Three columns for the highlight symbol, the rest split between the
+    // description and the right-aligned keys.
+    let width = chunks[1].width.saturating_sub(3) as usize;
+    let items: Vec<ListItem> = entries
+        .iter()
+        .map(|entry| {
+            let (context, description, keys) = match *entry {
+                PaletteEntry::Action(action) => (
+                    action.context(),
+                    action.description().to_string(),
+                    app.keymap.display(action),
+                ),
+                PaletteEntry::GoToMonth(month) => (
+                    KeyContext::Monthly,
+                    format!("Go to {}", month.format("%B %Y")),
+                    String::new(),
+                ),
+            };
+            let tab = match context {
+                KeyContext::Expenses => "Expenses  ",
+                KeyContext::Monthly => "Monthly  ",
+                KeyContext::Yearly => "Yearly  ",
+                KeyContext::Heatmap => "Heatmap  ",
+                KeyContext::Budgets => "Budgets  ",
+                KeyContext::Goals => "Goals  ",
+                KeyContext::Loans => "Loans  ",
+                KeyContext::Subscriptions => "Subscriptions  ",
+                _ => "",
+            };
+            let used = tab.width() + description.width() + keys.width();
+            let gap = width.saturating_sub(used).max(1);
+            ListItem::new(Line::from(vec![
+                Span::styled(tab, Style::default().fg(theme.heading)),
+                Span::styled(description, Style::default().fg(theme.text)),
+                Span::raw(" ".repeat(gap)),
+                Span::styled(keys, Style::default().fg(theme.accent)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(theme.selected_row())
+        .highlight_symbol(">> ");
+    let mut state = ListState::default();
+    state.select(Some(app.palette_index.min(entries.len() - 1)));
+    f.render_stateful_widget(list, chunks[1], &mut state);
+}
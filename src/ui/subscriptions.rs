@@ -0,0 +1,392 @@
+use chrono::NaiveDate;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::app::App;
+use crate::keymap::Action;
+use crate::subscriptions::{Candidate, Subscription};
+use crate::utils;
+
+/// The series table on top, and below it the selection's price history next
+/// to the detected candidates.
+fn layout(series: usize, area: Rect) -> (Rect, Rect, Rect) {
+    let chunks = super::list_layout(series, area);
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(chunks[1]);
+    (chunks[0], bottom[0], bottom[1])
+}
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let today = utils::today();
+    let subscriptions = app.subscriptions();
+    let candidates = app.subscription_candidates();
+    let (top, history, detected) = layout(subscriptions.len(), area);
+    let selected = app
+        .subscription_index
+        .min((subscriptions.len() + candidates.len()).saturating_sub(1));
+
+    render_subscriptions(f, app, &subscriptions, selected, today, top);
+    match subscriptions.get(selected) {
+        Some(subscription) => render_price_history(f, app, subscription, history),
+        None => match candidates.get(selected - subscriptions.len()) {
+            Some(candidate) => render_charges(f, app, candidate, history),
+            None => {
+                let block = Block::default()
+                    .title(" Price History ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(app.theme.border_muted));
+                f.render_widget(block, history);
+            }
+        },
+    }
+    let selected_candidate = selected.checked_sub(subscriptions.len());
+    render_candidates(f, app, &candidates, selected_candidate, detected);
+}
+
+fn render_subscriptions(
+    f: &mut Frame,
+    app: &App,
+    subscriptions: &[Subscription],
+    selected: usize,
+    today: NaiveDate,
+    area: Rect,
+) {
+    let active: Vec<&Subscription> = subscriptions
+        .iter()
+        .filter(|s| s.is_active(today))
+        .collect();
+    let monthly = active.iter().fold(0.0, |total, s| total + s.monthly_cost());
+    let title = if active.is_empty() {
+        " Subscriptions ".to_string()
+    } else {
+        format!(
+            " Subscriptions: {} active, {}/month, {}/year ",
+            active.len(),
+            app.fmt(monthly),
+            app.fmt(monthly * 12.0)
+        )
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border_muted));
+    if subscriptions.is_empty() {
+        let text = Paragraph::new(Span::styled(
+            "No recurring expenses yet. Mark one recurring in the add form, or a detected one below.",
+            Style::default().fg(app.theme.muted),
+        ))
+        .block(block);
+        f.render_widget(text, area);
+        return;
+    }
+
+    let heading = Style::default()
+        .fg(app.theme.heading)
+        .add_modifier(Modifier::BOLD);
+    let header = Row::new(vec![
+        Cell::from("Name"),
+        Cell::from("Category"),
+        Cell::from("Every"),
+        super::right("Price"),
+        super::right("Monthly"),
+        super::right("Yearly"),
+        Cell::from("Since"),
+        Cell::from("Next charge"),
+        Cell::from("Status"),
+    ])
+    .style(heading)
+    .bottom_margin(1);
+
+    let rows: Vec<Row> = subscriptions
+        .iter()
+        .map(|s| subscription_row(app, s, today))
+        .collect();
+    let widths = [
+        Constraint::Min(14),
+        Constraint::Length(14),
+        Constraint::Length(8),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(18),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .row_highlight_style(app.theme.selected_row())
+        .highlight_symbol(">> ");
+    let mut state = TableState::default();
+    state.select((selected < subscriptions.len()).then_some(selected));
+    f.render_stateful_widget(table, area, &mut state);
+}
+
+fn subscription_row<'a>(app: &App, subscription: &Subscription, today: NaiveDate) -> Row<'a> {
+    let active = subscription.is_active(today);
+    let text = if active {
+        Style::default().fg(app.theme.text)
+    } else {
+        Style::default().fg(app.theme.muted)
+    };
+    let amount = |value: f64| Cell::from(Line::from(app.fmt_compact(value)).right_aligned());
+    let date = |date: Option<NaiveDate>| {
+        Cell::from(
+            date.map(|d| d.format("%-d %b %Y").to_string())
+                .unwrap_or_else(|| "·".to_string()),
+        )
+    };
+    let status = match subscription.cancelled {
+        Some(from) if from > today => Span::styled(
+            format!("ends {}", from.format("%-d %b")),
+            Style::default().fg(app.theme.accent),
+        ),
+        Some(from) => Span::styled(format!("cancelled {}", from.format("%b %Y")), text),
+        None => Span::styled("active", Style::default().fg(app.theme.positive)),
+    };
+    let category = subscription.category.to_string();
+
+    Row::new(vec![
+        Cell::from(subscription.name.clone()),
+        Cell::from(Span::styled(
+            category.clone(),
+            Style::default().fg(app.theme.category_color_by_name(&category)),
+        )),
+        Cell::from(subscription.recurrence.to_string().to_lowercase()),
+        Cell::from(Line::from(app.fmt(subscription.amount)).right_aligned()),
+        amount(subscription.monthly_cost()),
+        amount(subscription.yearly_cost()),
+        date(subscription.since()),
+        date(subscription.next_charge),
+        Cell::from(status),
+    ])
+    .style(text)
+}
+
+/// Each price the selected series has charged, with the change from the one
+/// before.
+fn render_price_history(f: &mut Frame, app: &App, subscription: &Subscription, area: Rect) {
+    let heading = Style::default()
+        .fg(app.theme.heading)
+        .add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(app.theme.text);
+    let changes = subscription.price_changes();
+    let rows: Vec<Row> = changes
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, &(from, price))| {
+            let change = match i.checked_sub(1).map(|j| changes[j].1) {
+                Some(before) if before > 0.0 => {
+                    let percent = (price - before) / before * 100.0;
+                    let style = if percent > 0.0 {
+                        Style::default().fg(app.theme.negative)
+                    } else {
+                        Style::default().fg(app.theme.positive)
+                    };
+                    Cell::from(Line::from(format!("{:+.0}%", percent)).right_aligned()).style(style)
+                }
+                _ => Cell::from(""),
+            };
+            Row::new(vec![
+                Cell::from(from.format("%-d %b %Y").to_string()),
+                Cell::from(Line::from(app.fmt(price)).right_aligned()),
+                change,
+            ])
+            .style(text)
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(12),
+            Constraint::Length(11),
+            Constraint::Min(7),
+        ],
+    )
+    .header(
+        Row::new(vec![Cell::from("From"), super::right("Price"), super::right("Change")])
+            .style(heading)
+            .bottom_margin(1),
+    )
+    .block(
+        Block::default()
+            .title(format!(
+                " {} — {} charges ",
+                subscription.name,
+                subscription.charges.len()
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.border_muted)),
+    );
+    f.render_widget(table, area);
+}
+
+/// The charges behind a detected candidate, newest first.
+fn render_charges(f: &mut Frame, app: &App, candidate: &Candidate, area: Rect) {
+    let heading = Style::default()
+        .fg(app.theme.heading)
+        .add_modifier(Modifier::BOLD);
+    let rows: Vec<Row> = candidate
+        .charges
+        .iter()
+        .rev()
+        .map(|(date, amount)| {
+            Row::new(vec![
+                Cell::from(date.format("%-d %b %Y").to_string()),
+                Cell::from(Line::from(app.fmt(*amount)).right_aligned()),
+            ])
+            .style(Style::default().fg(app.theme.text))
+        })
+        .collect();
+    let table = Table::new(rows, [Constraint::Length(12), Constraint::Length(11)])
+        .header(
+            Row::new(vec![Cell::from("Date"), super::right("Amount")])
+                .style(heading)
+                .bottom_margin(1),
+        )
+        .block(
+            Block::default()
+                .title(format!(" {} — charges ", candidate.description))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border_muted)),
+        );
+    f.render_widget(table, area);
+}
+
+fn render_candidates(
+    f: &mut Frame,
+    app: &App,
+    candidates: &[Candidate],
+    selected: Option<usize>,
+    area: Rect,
+) {
+    let heading = Style::default()
+        .fg(app.theme.heading)
+        .add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(app.theme.text);
+    let mut block = Block::default()
+        .title(" Possible Subscriptions ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border_muted));
+    if candidates.is_empty() {
+        block = block.title_bottom(Line::from(" no unmarked repeating charges found ").centered());
+    } else {
+        block = block.title_bottom(
+            Line::from(format!(
+                " {}:mark as subscription ",
+                app.keymap.primary(Action::SubscriptionsMark)
+            ))
+            .right_aligned(),
+        );
+    }
+    let rows: Vec<Row> = candidates
+        .iter()
+        .map(|c| {
+            let category = c.category.to_string();
+            Row::new(vec![
+                Cell::from(c.description.clone()),
+                Cell::from(Span::styled(
+                    category.clone(),
+                    Style::default().fg(app.theme.category_color_by_name(&category)),
+                )),
+                Cell::from(c.recurrence.to_string().to_lowercase()),
+                Cell::from(Line::from(app.fmt(c.amount())).right_aligned()),
+                Cell::from(Line::from(app.fmt_compact(c.monthly_cost())).right_aligned()),
+                Cell::from(Line::from(c.charges.len().to_string()).right_aligned()),
+                Cell::from(
+                    c.charges
+                        .last()
+                        .map(|(date, _)| date.format("%-d %b %Y").to_string())
+                        .unwrap_or_default(),
+                ),
+            ])
+            .style(text)
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(12),
+            Constraint::Length(14),
+            Constraint::Length(8),
+            Constraint::Length(9),
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(12),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            Cell::from("Description"),
+            Cell::from("Category"),
+            Cell::from("Every"),
+            super::right("Price"),
+            super::right("Monthly"),
+            super::right("Charges"),
+            Cell::from("Last"),
+        ])
+        .style(heading)
+        .bottom_margin(1),
+    )
+    .block(block)
+    .row_highlight_style(app.theme.selected_row())
+    .highlight_symbol(">> ");
+    let mut state = TableState::default();
+    state.select(selected);
+    f.render_stateful_widget(table, area, &mut state);
+}
+
+/// The selection index of the series or candidate row at (`col`, `row`).
+pub fn row_at(app: &App, area: Rect, col: u16, row: u16) -> Option<usize> {
+    let subscriptions = app.subscriptions().len();
+    let candidates = app.subscription_candidates().len();
+    let (top, _, detected) = layout(subscriptions, area);
+    let index = app.subscription_index;
+    if let Some(i) = super::table_row_at(top, subscriptions, index.min(subscriptions), col, row) {
+        return Some(i);
+    }
+    let selected = index.saturating_sub(subscriptions);
+    super::table_row_at(detected, candidates, selected, col, row).map(|i| subscriptions + i)
+}
+
+/// The cancel prompt: the first day the selected series no longer charges.
+pub fn render_cancel_prompt(f: &mut Frame, app: &App, content: Rect) {
+    let name = app
+        .selected_subscription()
+        .map(|s| s.name)
+        .unwrap_or_default();
+    super::render_entry_prompt(
+        f,
+        app,
+        content,
+        &format!(" Cancel {} from ", name),
+        &app.subscription_input,
+        "e.g. today, 2026-11-01 or +1m",
+        app.cancel_preview(),
+    );
+}
+
+/// The price prompt: what the selected series charges from its next charge.
+pub fn render_price_prompt(f: &mut Frame, app: &App, content: Rect) {
+    let name = app
+        .selected_subscription()
+        .map(|s| s.name)
+        .unwrap_or_default();
+    super::render_entry_prompt(
+        f,
+        app,
+        content,
+        &format!(" New price for {} ", name),
+        &app.subscription_input,
+        "e.g. 12.99",
+        app.price_change_preview(),
+    );
+}
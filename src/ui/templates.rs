@@ -0,0 +1,79 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::app::App;
+use crate::keymap::Action;
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let popup_area = super::centered_rect(50, 50, area);
+    f.render_widget(Clear, popup_area);
+
+    let theme = &app.theme;
+    let items: Vec<ListItem> = app
+        .templates
+        .iter()
+        .enumerate()
+        .map(|(i, template)| {
+            let number = if i < 9 {
+                format!("{} ", i + 1)
+            } else {
+                "  ".to_string()
+            };
+            let amount = template
+                .amount
+                .map(|a| app.fmt(a))
+                .unwrap_or_else(|| "—".to_string());
+            let mut spans = vec![
+                Span::styled(number, Style::default().fg(theme.muted)),
+                Span::styled(
+                    format!("{:<20}", template.name),
+                    Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("{:>10}  ", amount), Style::default().fg(theme.accent)),
+                Span::styled(
+                    template.category.to_string(),
+                    Style::default().fg(theme.category_color(&template.category)),
+                ),
+            ];
+            if !template.tags.is_empty() {
+                let tags: Vec<String> = template.tags.iter().map(|t| format!("#{}", t)).collect();
+                spans.push(Span::styled(
+                    format!("  {}", tags.join(" ")),
+                    Style::default().fg(theme.tag),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(" Templates ")
+                .title_bottom(
+                    Line::from(super::hints(
+                        app,
+                        &[
+                            Action::TemplatesUse,
+                            Action::TemplatesEdit,
+                            Action::TemplatesDelete,
+                            Action::TemplatesClose,
+                        ],
+                    ))
+                    .centered(),
+                )
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.accent)),
+        )
+        .highlight_style(theme.selected_row())
+        .highlight_symbol(">> ");
+
+    let mut state = ListState::default();
+    state.select(Some(app.template_index));
+    f.render_stateful_widget(list, popup_area, &mut state);
+}
@@ -0,0 +1,256 @@
+use std::rc::Rc;
+
+use chrono::Datelike;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{
+        Axis, Bar, BarChart, BarGroup, Block, Borders, Cell, Chart, Dataset, GraphType,
+        LegendPosition, Paragraph, Row, Table,
+    },
+    Frame,
+};
+
+use crate::app::App;
+use crate::keymap::Action;
+use crate::utils;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// How many categories get a line in the trend chart.
+const TREND_CATEGORIES: usize = 5;
+
+fn layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Percentage(45),
+            Constraint::Min(5),
+        ])
+        .split(area)
+}
+
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = layout(area);
+
+    render_year_selector(f, app, chunks[0]);
+
+    let charts = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+    render_monthly_totals(f, app, charts[0]);
+    render_category_trends(f, app, charts[1]);
+
+    render_category_table(f, app, chunks[2]);
+}
+
+/// `-1` or `1` if (`col`, `row`) is on the previous/next arrow of the year
+/// selector.
+pub fn year_arrow_at(app: &App, area: Rect, col: u16, row: u16) -> Option<i32> {
+    super::selector_arrow_at(layout(area)[0], &app.overview_year.to_string(), col, row)
+}
+
+fn render_year_selector(f: &mut Frame, app: &App, area: Rect) {
+    let title = format!(
+        " Year ({}/{} to navigate) ",
+        app.keymap.primary(Action::PrevYear),
+        app.keymap.primary(Action::NextYear)
+    );
+    super::render_selector(f, app, area, &app.overview_year.to_string(), &title);
+}
+
+/// The month to highlight: the current one when showing this year.
+fn current_month0(app: &App) -> Option<usize> {
+    let today = utils::today();
+    (today.year() == app.overview_year).then(|| today.month0() as usize)
+}
+
+fn render_monthly_totals(f: &mut Frame, app: &App, area: Rect) {
+    let totals = app.monthly_totals(app.overview_year);
+    let current = current_month0(app);
+
+    let bars: Vec<Bar> = totals
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| {
+            let color = if current == Some(i) {
+                app.theme.accent
+            } else {
+                app.theme.heading
+            };
+            Bar::default()
+                .value(amount.round() as u64)
+                .text_value(if *amount > 0.0 {
+                    app.fmt_compact(*amount)
+                } else {
+                    String::new()
+                })
+                .label(Line::from(MONTHS[i]))
+                .style(Style::default().fg(color))
+                .value_style(
+                    Style::default()
+                        .fg(app.theme.text)
+                        .add_modifier(Modifier::BOLD),
+                )
+        })
+        .collect();
+
+    let total: f64 = totals.iter().sum();
+    let chart = BarChart::default()
+        .block(
+            Block::default()
+                .title(" Monthly Totals ")
+                .title_bottom(Line::from(format!(" Year: {} ", app.fmt(total))).right_aligned())
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border_muted)),
+        )
+        .data(BarGroup::default().bars(&bars))
+        .bar_width((area.width.saturating_sub(2) / 12).saturating_sub(1).clamp(1, 8))
+        .bar_gap(1);
+
+    f.render_widget(chart, area);
+}
+
+fn render_category_trends(f: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .title(format!(" Top {} Category Trends ", TREND_CATEGORIES))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border_muted));
+
+    let by_category = app.category_totals_by_month(app.overview_year);
+    if by_category.is_empty() {
+        let empty = Paragraph::new("No expenses for this year")
+            .centered()
+            .style(Style::default().fg(app.theme.muted))
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let top = &by_category[..by_category.len().min(TREND_CATEGORIES)];
+    let points: Vec<Vec<(f64, f64)>> = top
+        .iter()
+        .map(|(_, months)| {
+            months
+                .iter()
+                .enumerate()
+                .map(|(i, amount)| (i as f64, *amount))
+                .collect()
+        })
+        .collect();
+    let max = top
+        .iter()
+        .flat_map(|(_, months)| months.iter().copied())
+        .fold(0.0_f64, f64::max);
+
+    let datasets: Vec<Dataset> = top
+        .iter()
+        .zip(&points)
+        .map(|((name, _), data)| {
+            Dataset::default()
+                .name(name.clone())
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(app.theme.category_color_by_name(name)))
+                .data(data)
+        })
+        .collect();
+
+    let muted = Style::default().fg(app.theme.muted);
+    let chart = Chart::new(datasets)
+        .block(block)
+        .legend_position(Some(LegendPosition::TopLeft))
+        .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)))
+        .x_axis(
+            Axis::default()
+                .style(muted)
+                .bounds([0.0, 11.0])
+                .labels(["Jan", "Jun", "Dec"]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(muted)
+                .bounds([0.0, max.max(1.0)])
+                .labels([app.fmt_compact(0.0), app.fmt_compact(max)]),
+        );
+
+    f.render_widget(chart, area);
+}
+
+fn render_category_table(f: &mut Frame, app: &App, area: Rect) {
+    let by_category = app.category_totals_by_month(app.overview_year);
+    let totals = app.monthly_totals(app.overview_year);
+    let current = current_month0(app);
+
+    let heading = Style::default()
+        .fg(app.theme.heading)
+        .add_modifier(Modifier::BOLD);
+    let mut header = vec![Cell::from("Category")];
+    header.extend(MONTHS.iter().enumerate().map(|(i, m)| {
+        let style = if current == Some(i) {
+            heading.fg(app.theme.accent)
+        } else {
+            heading
+        };
+        Cell::from(Line::from(*m).right_aligned()).style(style)
+    }));
+    header.push(Cell::from(Line::from("Total").right_aligned()));
+
+    let amount_cells = |months: &[f64; 12], style: Style| -> Vec<Cell> {
+        let mut cells: Vec<Cell> = months
+            .iter()
+            .map(|amount| {
+                if *amount > 0.0 {
+                    Cell::from(Line::from(app.fmt_compact(*amount)).right_aligned()).style(style)
+                } else {
+                    Cell::from(Line::from("·").right_aligned())
+                        .style(Style::default().fg(app.theme.muted))
+                }
+            })
+            .collect();
+        cells.push(
+            Cell::from(Line::from(app.fmt_compact(months.iter().sum())).right_aligned())
+                .style(style.add_modifier(Modifier::BOLD)),
+        );
+        cells
+    };
+
+    let text = Style::default().fg(app.theme.text);
+    let mut rows: Vec<Row> = by_category
+        .iter()
+        .map(|(name, months)| {
+            let mut cells = vec![Cell::from(Span::styled(
+                name.clone(),
+                Style::default().fg(app.theme.category_color_by_name(name)),
+            ))];
+            cells.extend(amount_cells(months, text));
+            Row::new(cells)
+        })
+        .collect();
+    if !rows.is_empty() {
+        let mut cells = vec![Cell::from(Span::styled("Total", heading))];
+        cells.extend(amount_cells(&totals, heading));
+        rows.push(Row::new(cells).top_margin(1));
+    }
+
+    let mut widths = vec![Constraint::Min(10)];
+    widths.extend([Constraint::Length(7); 12]);
+    widths.push(Constraint::Length(9));
+
+    let table = Table::new(rows, widths)
+        .header(Row::new(header).style(heading).bottom_margin(1))
+        .block(
+            Block::default()
+                .title(" Category × Month ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border_muted)),
+        );
+
+    f.render_widget(table, area);
+}
@@ -1,5 +1,253 @@
-use chrono::{Local, NaiveDate};
+use chrono::{Datelike, Days, Local, Month, Months, NaiveDate, Weekday};
 
-pub fn _today() -> NaiveDate {
+pub fn today() -> NaiveDate {
     Local::now().date_naive()
 }
+
+/// Resolve a typed date relative to `today`. Accepts `YYYY-MM-DD`, `today`,
+/// `yesterday`, `tomorrow`, offsets like `-3d`, `+1w` or `-2m`, and weekdays:
+/// `fri` is the latest Friday up to today, `last fri` the latest one before
+/// today and `next fri` the first one after it.
+pub fn parse_date(input: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let s = input.trim().to_lowercase();
+    if s.is_empty() {
+        return Err("Enter a date".to_string());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(&s, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    let out_of_range = || "Date is out of range".to_string();
+    match s.as_str() {
+        "today" | "now" => return Ok(today),
+        "yesterday" => return today.pred_opt().ok_or_else(out_of_range),
+        "tomorrow" => return today.succ_opt().ok_or_else(out_of_range),
+        _ => {}
+    }
+    if s.starts_with(['+', '-']) {
+        return parse_offset(&s, today);
+    }
+
+    let words: Vec<&str> = s.split_whitespace().collect();
+    let (direction, day) = match words.as_slice() {
+        [day] => (0, *day),
+        ["last", day] => (-1, *day),
+        ["next", day] => (1, *day),
+        _ => return Err(unrecognised(input)),
+    };
+    let weekday: Weekday = day.parse().map_err(|_| unrecognised(input))?;
+    let from = today.weekday().num_days_from_monday();
+    let to = weekday.num_days_from_monday();
+    let back = (from + 7 - to) % 7;
+    let forward = (to + 7 - from) % 7;
+    let date = match direction {
+        0 => today.checked_sub_days(Days::new(back as u64)),
+        d if d < 0 && back == 0 => today.checked_sub_days(Days::new(7)),
+        d if d < 0 => today.checked_sub_days(Days::new(back as u64)),
+        _ if forward == 0 => today.checked_add_days(Days::new(7)),
+        _ => today.checked_add_days(Days::new(forward as u64)),
+    };
+    date.ok_or_else(out_of_range)
+}
+
+/// `-3d`, `+2w`, `-1m`, `+1y` or spelled out like `-3 days`; the unit
+/// defaults to days.
+fn parse_offset(s: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let (sign, rest) = s.split_at(1);
+    let digits = rest.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let unit = rest[digits.len()..].trim_end_matches('s');
+    let n: u32 = digits
+        .trim()
+        .parse()
+        .map_err(|_| unrecognised(s))?;
+    let forward = sign == "+";
+
+    let date = match unit {
+        "" | "d" | "day" => {
+            let days = Days::new(n as u64);
+            if forward {
+                today.checked_add_days(days)
+            } else {
+                today.checked_sub_days(days)
+            }
+        }
+        "w" | "week" => {
+            let days = Days::new(n as u64 * 7);
+            if forward {
+                today.checked_add_days(days)
+            } else {
+                today.checked_sub_days(days)
+            }
+        }
+        "m" | "month" | "y" | "year" => {
+            let years = unit.starts_with('y');
+            let months = Months::new(if years { n.saturating_mul(12) } else { n });
+            if forward {
+                today.checked_add_months(months)
+            } else {
+                today.checked_sub_months(months)
+            }
+        }
+        _ => return Err(unrecognised(s)),
+    };
+    date.ok_or_else(|| "Date is out of range".to_string())
+}
+
+/// Resolve a typed month to its first day: `2025-03`, `mar 2025`,
+/// `2025 march`, or just `march` for this year's. Anything else is `None`.
+pub fn parse_month(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let s = input.trim().to_lowercase();
+    let words: Vec<&str> = s.split_whitespace().collect();
+    let (month, year) = match words.as_slice() {
+        [word] if word.contains('-') => return parse_date(&format!("{}-01", word), today).ok(),
+        [name] => (name.parse::<Month>().ok()?, today.year()),
+        [name, year] | [year, name] if year.chars().all(|c| c.is_ascii_digit()) => {
+            (name.parse::<Month>().ok()?, year.parse().ok()?)
+        }
+        _ => return None,
+    };
+    parse_date(&format!("{:04}-{:02}-01", year, month.number_from_month()), today).ok()
+}
+
+fn unrecognised(input: &str) -> String {
+    format!(
+        "Unrecognised date '{}' (try YYYY-MM-DD, today, yesterday, -3d, mon, last friday)",
+        input.trim()
+    )
+}
+
+/// Evaluate an amount such as `45.80/3`, `12+4.5` or `100*1.15`. Supports
+/// `+ - * /`, unary minus and parentheses; `x` also works for multiplication.
+pub fn eval_amount(input: &str) -> Result<f64, String> {
+    let tokens: Vec<char> = input.trim().chars().collect();
+    if tokens.is_empty() {
+        return Err("Enter an amount".to_string());
+    }
+    let mut parser = ExprParser { tokens, pos: 0, depth: 0 };
+    let value = parser.expr()?;
+    if let Some(c) = parser.peek() {
+        return Err(format!("Unexpected '{}' in amount", c));
+    }
+    if !value.is_finite() {
+        return Err("Amount is too large".to_string());
+    }
+    Ok(value)
+}
+
+/// Whether `word` could be an amount for `eval_amount`, as opposed to a name.
+pub fn starts_like_amount(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_digit() || c == '.' || c == '(')
+}
+
+/// Recursive descent over `expr = term (+|- term)*`,
+/// `term = factor (*|/ factor)*`, `factor = -factor | number | (expr)`.
+struct ExprParser {
+    tokens: Vec<char>,
+    pos: usize,
+    depth: usize,
+}
+
+/// Deep enough for any real bill, shallow enough to never blow the stack.
+const MAX_NESTING: usize = 32;
+
+impl ExprParser {
+    /// The next non-space character.
+    fn peek(&mut self) -> Option<char> {
+        while self.tokens.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn expr(&mut self) -> Result<f64, String> {
+        let mut value = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.term()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<f64, String> {
+        let mut value = self.factor()?;
+        while let Some(op @ ('*' | 'x' | 'X' | '/')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.factor()?;
+            if op == '/' {
+                if rhs == 0.0 {
+                    return Err("Division by zero".to_string());
+                }
+                value /= rhs;
+            } else {
+                value *= rhs;
+            }
+        }
+        Ok(value)
+    }
+
+    fn factor(&mut self) -> Result<f64, String> {
+        self.depth += 1;
+        if self.depth > MAX_NESTING {
+            return Err("Amount expression is nested too deeply".to_string());
+        }
+        let value = match self.peek() {
+            Some('-') => {
+                self.pos += 1;
+                -self.factor()?
+            }
+            Some('+') => {
+                self.pos += 1;
+                self.factor()?
+            }
+            Some('(') => {
+                self.pos += 1;
+                let value = self.expr()?;
+                if self.peek() != Some(')') {
+                    return Err("Missing ')' in amount".to_string());
+                }
+                self.pos += 1;
+                value
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.number()?,
+            Some(c) => return Err(format!("Unexpected '{}' in amount", c)),
+            None => return Err("Amount expression is incomplete".to_string()),
+        };
+        self.depth -= 1;
+        Ok(value)
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        let start = self.pos;
+        while matches!(self.tokens.get(self.pos), Some(c) if c.is_ascii_digit() || *c == '.') {
+            self.pos += 1;
+        }
+        let text: String = self.tokens[start..self.pos].iter().collect();
+        text.parse()
+            .map_err(|_| format!("'{}' is not a valid number", text))
+    }
+}
+
+/// Score `text` against a fuzzy `query`: every query character must appear in
+/// order. Consecutive matches and matches at word starts score higher, so
+/// `nm` ranks "Next month" above "Mark / unmark". `None` means no match.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut last_match: Option<usize> = None;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = (pos..text.len()).find(|&i| text[i] == q)?;
+        let word_start = found == 0 || !text[found - 1].is_alphanumeric();
+        score += match (last_match, word_start) {
+            (Some(last), _) if last + 1 == found => 5,
+            (_, true) => 8,
+            _ => 1,
+        };
+        score -= (found - pos).min(5) as i64;
+        last_match = Some(found);
+        pos = found + 1;
+    }
+    Some(score)
+}